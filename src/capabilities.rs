@@ -0,0 +1,67 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Introspection of the algorithms that are available in this build.
+
+use crate::types::Kdf;
+use crate::types::MasterCipher;
+use crate::types::StreamCipher;
+
+/// Returns the key derivation functions that can be used in this build.
+///
+/// # Examples
+///
+/// ```rust
+/// use kpdb::Kdf;
+///
+/// assert!(kpdb::supported_kdfs().contains(&Kdf::Aes));
+/// ```
+pub fn supported_kdfs() -> &'static [Kdf] {
+    Kdf::all()
+}
+
+/// Returns the master ciphers that can be used in this build.
+///
+/// Use this to offer only the ciphers that `Database::set_ciphers` accepts.
+///
+/// # Examples
+///
+/// ```rust
+/// use kpdb::MasterCipher;
+///
+/// assert!(kpdb::supported_master_ciphers().contains(&MasterCipher::Aes256));
+/// ```
+pub fn supported_master_ciphers() -> &'static [MasterCipher] {
+    MasterCipher::all()
+}
+
+/// Returns the stream ciphers that can be used in this build.
+///
+/// # Examples
+///
+/// ```rust
+/// use kpdb::StreamCipher;
+///
+/// assert!(kpdb::supported_stream_ciphers().contains(&StreamCipher::Salsa20));
+/// ```
+pub fn supported_stream_ciphers() -> &'static [StreamCipher] {
+    StreamCipher::all()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_supported_functions_are_not_empty() {
+        assert!(!supported_kdfs().is_empty());
+        assert!(!supported_master_ciphers().is_empty());
+        assert!(!supported_stream_ciphers().is_empty());
+    }
+}
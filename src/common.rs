@@ -8,18 +8,38 @@
 
 //! Module containing constants for the application.
 
+/// The length of a binary key file.
+#[cfg(any(feature = "database", feature = "keyfile"))]
+pub const BINARY_KEY_FILE_LEN: usize = 32;
+
+/// The default maximum size of a payload block in bytes.
+#[cfg(feature = "database")]
+pub const BLOCK_SIZE_DEFAULT: usize = 1024 * 1024;
+
+/// The default GZip compression level.
+#[cfg(feature = "database")]
+pub const COMPRESSION_LEVEL_DEFAULT: u32 = 6;
+
 /// The database signature.
+#[cfg(feature = "database")]
 pub const DB_SIGNATURE: [u8; 4] = [0x03, 0xd9, 0xa2, 0x9a];
 
 /// The name of this generator.
+#[cfg(feature = "database")]
 pub const GENERATOR_NAME: &'static str = "rust-kpdb";
 
 /// The default value for history max items.
+#[cfg(feature = "database")]
 pub const HISTORY_MAX_ITEMS_DEFAULT: i32 = 10;
 
 /// The default value for history max size.
+#[cfg(feature = "database")]
 pub const HISTORY_MAX_SIZE_DEFAULT: i32 = 6291456;
 
+/// The length of a hexadecimal key file.
+#[cfg(any(feature = "database", feature = "keyfile"))]
+pub const HEX_KEY_FILE_LEN: usize = 64;
+
 /// The major version for kdb2 databases.
 pub const KDB2_MAJOR_VERSION: u16 = 3;
 
@@ -27,37 +47,98 @@ pub const KDB2_MAJOR_VERSION: u16 = 3;
 pub const KDB2_MINOR_VERSION: u16 = 1;
 
 /// The signature for kdb1 databases.
+#[cfg(feature = "database")]
 pub const KDB1_SIGNATURE: [u8; 4] = [0x65, 0xfb, 0x4b, 0xb5];
 
 /// The signature for kdb2 databases.
+#[cfg(feature = "database")]
 pub const KDB2_SIGNATURE: [u8; 4] = [0x67, 0xfb, 0x4b, 0xb5];
 
+/// The start of the <KeyFile> tag of an XML key file.
+#[cfg(any(feature = "database", feature = "keyfile"))]
+pub const KEY_FILE_TAG_START: &[u8] = b"<KeyFile";
+
 /// The default value for maintenance history days.
+#[cfg(feature = "database")]
 pub const MAINTENANCE_HISTORY_DAYS_DEFAULT: i32 = 365;
 
 /// The default value for master key change force.
+#[cfg(feature = "database")]
 pub const MASTER_KEY_CHANGE_FORCE_DEFAULT: i32 = -1;
 
 /// The default value for master key change rec.
+#[cfg(feature = "database")]
 pub const MASTER_KEY_CHANGE_REC_DEFAULT: i32 = -1;
 
+/// The default maximum number of data blocks while reading.
+#[cfg(feature = "database")]
+pub const MAX_BLOCKS_DEFAULT: u32 = 1048576;
+
+/// The default maximum number of header fields while reading.
+#[cfg(feature = "database")]
+pub const MAX_HEADERS_DEFAULT: usize = 256;
+
+/// The maximum number of field references resolved in a single text.
+#[cfg(feature = "database")]
+pub const MAX_REFERENCES: usize = 100;
+
+/// The maximum depth of nested field references.
+#[cfg(feature = "database")]
+pub const MAX_REFERENCE_DEPTH: usize = 10;
+
+/// The default maximum number of transform rounds while reading.
+#[cfg(feature = "database")]
+pub const MAX_TRANSFORM_ROUNDS_DEFAULT: u64 = 1000000000;
+
+/// The default maximum size of the decompressed XML data while reading.
+#[cfg(feature = "database")]
+pub const MAX_XML_SIZE_DEFAULT: u64 = 1073741824;
+
+/// The minimum estimated entropy in bits of a password that is not considered
+/// weak.
+#[cfg(feature = "database")]
+pub const PASSWORD_ENTROPY_MIN: u32 = 60;
+
 /// The default value for protect notes.
+#[cfg(feature = "database")]
 pub const PROTECT_NOTES_DEFAULT: bool = false;
 
 /// The default value for protect password.
+#[cfg(feature = "database")]
 pub const PROTECT_PASSWORD_DEFAULT: bool = true;
 
 /// The default value for protect title.
+#[cfg(feature = "database")]
 pub const PROTECT_TITLE_DEFAULT: bool = false;
 
 /// The default value for protect url.
+#[cfg(feature = "database")]
 pub const PROTECT_URL_DEFAULT: bool = false;
 
 /// The default value for protect username.
+#[cfg(feature = "database")]
 pub const PROTECT_USERNAME_DEFAULT: bool = false;
 
 /// The default value for recycle bin enabled.
+#[cfg(feature = "database")]
 pub const RECYCLE_BIN_ENABLED_DEFAULT: bool = true;
 
+/// The placeholder for redacted protected values.
+#[cfg(feature = "database")]
+pub const REDACTED_PLACEHOLDER: &str = "***";
+
 /// The name of the root group.
+#[cfg(feature = "database")]
 pub const ROOT_GROUP_NAME: &'static str = "Root";
+
+/// The minimum number of transform rounds that is not considered weak.
+#[cfg(feature = "database")]
+pub const TRANSFORM_ROUNDS_MIN: u64 = 6000;
+
+/// The UTF-8 byte order mark.
+#[cfg(any(feature = "database", feature = "keyfile"))]
+pub const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// The start of an XML declaration.
+#[cfg(any(feature = "database", feature = "keyfile"))]
+pub const XML_DECLARATION_START: &[u8] = b"<?xml";
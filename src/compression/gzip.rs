@@ -20,9 +20,19 @@ pub fn decode(input: &[u8]) -> Result<Vec<u8>> {
     Ok(output)
 }
 
+/// Create a reader that decodes (decompresses) the input using GZip.
+pub fn decoder<R: Read>(reader: R) -> GzDecoder<R> {
+    GzDecoder::new(reader)
+}
+
 /// Encode (compress) the input using GZip.
 pub fn encode(input: &[u8]) -> Result<Vec<u8>> {
-    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encode_with_level(input, Compression::default().level())
+}
+
+/// Encode (compress) the input using GZip with the supplied level (0-9).
+pub fn encode_with_level(input: &[u8], level: u32) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level));
     encoder.write_all(input)?;
     let output = encoder.finish()?;
     Ok(output)
@@ -39,5 +49,18 @@ mod tests {
             let decoded = decode(&encoded).unwrap();
             decoded == data
         }
+
+        fn test_decode_inverses_encode_with_level(data: Vec<u8>, level: u32) -> bool {
+            let encoded = encode_with_level(&data, level % 10).unwrap();
+            let decoded = decode(&encoded).unwrap();
+            decoded == data
+        }
+
+        fn test_decoder_inverses_encode(data: Vec<u8>) -> bool {
+            let encoded = encode(&data).unwrap();
+            let mut decoded = Vec::new();
+            decoder(&encoded[..]).read_to_end(&mut decoded).unwrap();
+            decoded == data
+        }
     }
 }
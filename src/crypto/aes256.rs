@@ -7,41 +7,108 @@
 // except according to those terms.
 
 use crate::rust_crypto::aes::{self, KeySize};
-use crate::rust_crypto::blockmodes::PkcsPadding;
+use crate::rust_crypto::blockmodes::{NoPadding, PkcsPadding};
 use crate::rust_crypto::buffer::{
     BufferResult, ReadBuffer, RefReadBuffer, RefWriteBuffer, WriteBuffer,
 };
-use crate::types::{MasterIV, MasterKey, Result};
+use crate::rust_crypto::symmetriccipher::Decryptor;
+use crate::types::{Error, MasterIV, MasterKey, Result};
+use std::cmp;
+use std::io::{self, Read};
 
-/// Decrypt the input using the key and initialization vector.
-pub fn decrypt(key: &MasterKey, iv: &MasterIV, input: &[u8]) -> Result<Vec<u8>> {
-    let mut cipher = aes::cbc_decryptor(KeySize::KeySize256, &key.unsecure(), &iv.0, PkcsPadding);
-    let mut output = Vec::new();
-    let mut read_buffer = RefReadBuffer::new(input);
-    let mut buffer = [0; 4096];
-    let mut write_buffer = RefWriteBuffer::new(&mut buffer);
+/// The size of the chunks read from the underlying reader.
+const CHUNK_SIZE: usize = 4096;
 
-    loop {
-        let result = cipher.decrypt(&mut read_buffer, &mut write_buffer, true)?;
-        output.extend(
-            write_buffer
-                .take_read_buffer()
-                .take_remaining()
-                .iter()
-                .map(|&i| i),
-        );
-        match result {
-            BufferResult::BufferUnderflow => break,
-            BufferResult::BufferOverflow => {}
+/// Reader that decrypts the data read from the underlying reader.
+///
+/// The data is decrypted in chunks so the whole input never has to be in
+/// memory. When reading fails the actual error can be retrieved using
+/// `take_error`.
+pub struct DecryptReader<R> {
+    reader: R,
+    cipher: Box<dyn Decryptor>,
+    buffer: Vec<u8>,
+    pos: usize,
+    eof: bool,
+    error: Option<Error>,
+}
+
+impl<R: Read> DecryptReader<R> {
+    /// Attempts to create a new reader decrypting the input using the key
+    /// and initialization vector.
+    pub fn new(reader: R, key: &MasterKey, iv: &MasterIV) -> Result<DecryptReader<R>> {
+        let key = key.try_unsecure()?;
+        Ok(DecryptReader {
+            reader,
+            cipher: aes::cbc_decryptor(KeySize::KeySize256, &key, &iv.0, PkcsPadding),
+            buffer: Vec::new(),
+            pos: 0,
+            eof: false,
+            error: None,
+        })
+    }
+
+    /// Takes the error that caused the last read to fail if any.
+    pub fn take_error(&mut self) -> Option<Error> {
+        self.error.take()
+    }
+
+    fn fill_buffer(&mut self) -> Result<()> {
+        let mut input = [0u8; CHUNK_SIZE];
+        let mut output = [0u8; CHUNK_SIZE];
+        while self.pos == self.buffer.len() && !self.eof {
+            let size = self.reader.read(&mut input)?;
+            self.eof = size == 0;
+            self.buffer.clear();
+            self.pos = 0;
+
+            let mut read_buffer = RefReadBuffer::new(&input[..size]);
+            loop {
+                let mut write_buffer = RefWriteBuffer::new(&mut output);
+                let result = self
+                    .cipher
+                    .decrypt(&mut read_buffer, &mut write_buffer, self.eof)?;
+                self.buffer
+                    .extend_from_slice(write_buffer.take_read_buffer().take_remaining());
+                match result {
+                    BufferResult::BufferUnderflow => break,
+                    BufferResult::BufferOverflow => {}
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecryptReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if let Err(err) = self.fill_buffer() {
+            let io_err = io::Error::new(io::ErrorKind::InvalidData, format!("{}", err));
+            self.error = Some(err);
+            return Err(io_err);
         }
+        let size = cmp::min(buf.len(), self.buffer.len() - self.pos);
+        buf[..size].copy_from_slice(&self.buffer[self.pos..self.pos + size]);
+        self.pos += size;
+        Ok(size)
     }
+}
 
+/// Decrypt whole blocks of input without removing the padding.
+pub fn decrypt_blocks(key: &MasterKey, iv: &MasterIV, input: &[u8]) -> Result<Vec<u8>> {
+    let mut cipher =
+        aes::cbc_decryptor(KeySize::KeySize256, &key.try_unsecure()?, &iv.0, NoPadding);
+    let mut output = vec![0; input.len()];
+    let mut read_buffer = RefReadBuffer::new(input);
+    let mut write_buffer = RefWriteBuffer::new(&mut output);
+    cipher.decrypt(&mut read_buffer, &mut write_buffer, true)?;
     Ok(output)
 }
 
 /// Encrypt the input using the key and initialization vector.
 pub fn encrypt(key: &MasterKey, iv: &MasterIV, input: &[u8]) -> Result<Vec<u8>> {
-    let mut cipher = aes::cbc_encryptor(KeySize::KeySize256, &key.unsecure(), &iv.0, PkcsPadding);
+    let mut cipher =
+        aes::cbc_encryptor(KeySize::KeySize256, &key.try_unsecure()?, &iv.0, PkcsPadding);
     let mut output = Vec::new();
     let mut read_buffer = RefReadBuffer::new(input);
     let mut buffer = [0; 4096];
@@ -77,8 +144,29 @@ mod tests {
     use crate::types::TransformSeed;
     use crate::types::TransformedKey;
 
+    #[test]
+    fn test_decrypt_reader_with_invalid_padding_returns_crypto_error() {
+        let master_key = MasterKey::new(
+            &MasterSeed([2u8; 32]),
+            &TransformedKey::new(
+                &CompositeKey::from_password("secret"),
+                &TransformSeed([1u8; 32]),
+                &TransformRounds(10),
+            ),
+        );
+        let master_iv = MasterIV([3u8; 16]);
+        let encrypted = [0u8; 32];
+        let mut reader = DecryptReader::new(&encrypted[..], &master_key, &master_iv).unwrap();
+        let mut decrypted = Vec::new();
+        assert!(reader.read_to_end(&mut decrypted).is_err());
+        match reader.take_error() {
+            Some(Error::CryptoError(_)) => (),
+            _ => panic!("expected crypto error"),
+        }
+    }
+
     quickcheck! {
-        fn test_decrypt_inverses_encrypt(data: Vec<u8>) -> bool {
+        fn test_decrypt_reader_inverses_encrypt(data: Vec<u8>) -> bool {
             let composite_key = CompositeKey::from_password("secret");
             let rounds = TransformRounds(10);
             let transform_seed = TransformSeed([1u8; 32]);
@@ -87,8 +175,24 @@ mod tests {
             let master_key = MasterKey::new(&master_seed, &transformed_key);
             let master_iv = MasterIV([3u8; 16]);
             let encrypted = encrypt(&master_key, &master_iv, &data).unwrap();
-            let decrypted = decrypt(&master_key, &master_iv, &encrypted).unwrap();
+            let mut reader = DecryptReader::new(&encrypted[..], &master_key, &master_iv).unwrap();
+            let mut decrypted = Vec::new();
+            reader.read_to_end(&mut decrypted).unwrap();
             decrypted == data
         }
+
+        fn test_decrypt_blocks_returns_start_of_decrypted_data(data: Vec<u8>) -> bool {
+            let composite_key = CompositeKey::from_password("secret");
+            let rounds = TransformRounds(10);
+            let transform_seed = TransformSeed([1u8; 32]);
+            let transformed_key = TransformedKey::new(&composite_key, &transform_seed, &rounds);
+            let master_seed = MasterSeed([2u8; 32]);
+            let master_key = MasterKey::new(&master_seed, &transformed_key);
+            let master_iv = MasterIV([3u8; 16]);
+            let encrypted = encrypt(&master_key, &master_iv, &data).unwrap();
+            let size = data.len() / 16 * 16;
+            let decrypted = decrypt_blocks(&master_key, &master_iv, &encrypted[..size]).unwrap();
+            decrypted[..] == data[..size]
+        }
     }
 }
@@ -6,7 +6,9 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#[cfg(feature = "database")]
 pub mod aes256;
 pub mod random_gen;
+#[cfg(feature = "database")]
 pub mod salsa20;
 pub mod sha256;
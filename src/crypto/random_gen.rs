@@ -21,6 +21,7 @@ impl RandomGen {
     }
 
     /// Gets next 16 random bytes.
+    #[cfg(feature = "database")]
     pub fn next_16_bytes(&mut self) -> [u8; 16] {
         let mut buffer = [0u8; 16];
         self.0.fill_bytes(&mut buffer);
@@ -40,6 +41,7 @@ mod tests {
 
     use super::*;
 
+    #[cfg(feature = "database")]
     #[test]
     fn test_next_16_bytes_returns_random_bytes() {
         let mut gen = RandomGen::new().unwrap();
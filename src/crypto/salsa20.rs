@@ -11,6 +11,9 @@ use crate::types::StreamKey;
 
 const SALSA20_NOUNCE: [u8; 8] = [0xe8, 0x30, 0x09, 0x4b, 0x97, 0x20, 0x5d, 0x2a];
 
+/// The default chunk size for processing large values in place.
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
 pub use crate::rust_crypto::salsa20::Salsa20;
 
 /// Decrypt the input using the Salsa20 stream cipher.
@@ -18,11 +21,27 @@ pub fn decrypt(cipher: &mut Salsa20, input: &Vec<u8>) -> Vec<u8> {
     process(cipher, input)
 }
 
+/// Decrypt the data in place using the Salsa20 stream cipher.
+///
+/// The data is processed in chunks of at most `chunk_size` bytes, so only a
+/// buffer of one chunk is allocated instead of a copy of the whole data.
+pub fn decrypt_in_place(cipher: &mut Salsa20, data: &mut [u8], chunk_size: usize) {
+    process_in_place(cipher, data, chunk_size)
+}
+
 /// Encrypt the input using the Salsa20 stream cipher.
 pub fn encrypt(cipher: &mut Salsa20, input: &Vec<u8>) -> Vec<u8> {
     process(cipher, input)
 }
 
+/// Encrypt the data in place using the Salsa20 stream cipher.
+///
+/// The data is processed in chunks of at most `chunk_size` bytes, so only a
+/// buffer of one chunk is allocated instead of a copy of the whole data.
+pub fn encrypt_in_place(cipher: &mut Salsa20, data: &mut [u8], chunk_size: usize) {
+    process_in_place(cipher, data, chunk_size)
+}
+
 /// Create a new Salsa20 stream cipher using the specified key.
 pub fn new_cipher(key: &StreamKey) -> Salsa20 {
     Salsa20::new(&key.unpack(), &SALSA20_NOUNCE)
@@ -34,6 +53,16 @@ fn process(cipher: &mut Salsa20, input: &Vec<u8>) -> Vec<u8> {
     output
 }
 
+fn process_in_place(cipher: &mut Salsa20, data: &mut [u8], chunk_size: usize) {
+    let chunk_size = chunk_size.max(1);
+    let mut buffer = vec![0; chunk_size.min(data.len())];
+    for chunk in data.chunks_mut(chunk_size) {
+        let output = &mut buffer[..chunk.len()];
+        cipher.process(chunk, output);
+        chunk.copy_from_slice(output);
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -51,4 +80,42 @@ mod tests {
             decrypted == data
         }
     }
+
+    quickcheck! {
+        fn test_encrypt_in_place_equals_encrypt(data: Vec<u8>, chunk_size: u8) -> bool {
+            let stream_key = StreamKey::new(&ProtectedStreamKey([1u8; 32]));
+            let mut encryptor = new_cipher(&stream_key);
+            let mut chunked = new_cipher(&stream_key);
+            let expected = encrypt(&mut encryptor, &data);
+            let mut actual = data.clone();
+            encrypt_in_place(&mut chunked, &mut actual, chunk_size as usize);
+            actual == expected
+        }
+    }
+
+    quickcheck! {
+        fn test_decrypt_in_place_inverses_encrypt_in_place(data: Vec<u8>, chunk_size: u8) -> bool {
+            let stream_key = StreamKey::new(&ProtectedStreamKey([1u8; 32]));
+            let mut encryptor = new_cipher(&stream_key);
+            let mut decryptor = new_cipher(&stream_key);
+            let mut processed = data.clone();
+            encrypt_in_place(&mut encryptor, &mut processed, chunk_size as usize);
+            decrypt_in_place(&mut decryptor, &mut processed, CHUNK_SIZE);
+            processed == data
+        }
+    }
+
+    #[test]
+    fn test_process_in_place_keeps_stream_position_between_values() {
+        let stream_key = StreamKey::new(&ProtectedStreamKey([1u8; 32]));
+        let mut whole = new_cipher(&stream_key);
+        let mut chunked = new_cipher(&stream_key);
+        let expected = encrypt(&mut whole, &vec![7u8; 100]);
+        let mut first = vec![7u8; 30];
+        let mut second = vec![7u8; 70];
+        encrypt_in_place(&mut chunked, &mut first, 8);
+        encrypt_in_place(&mut chunked, &mut second, 16);
+        assert_eq!(&expected[..30], &first[..]);
+        assert_eq!(&expected[30..], &second[..]);
+    }
 }
@@ -97,6 +97,9 @@ pub const DEFAULT_AUTO_TYPE_SEQUENCE_TAG: &'static str = "DefaultAutoTypeSequenc
 pub const DEFAULT_SEQUENCE_TAG: &'static str = "DefaultSequence";
 pub const DEFAULT_USERNAME_CHANGED_TAG: &'static str = "DefaultUserNameChanged";
 pub const DEFAULT_USERNAME_TAG: &'static str = "DefaultUserName";
+pub const DELETED_OBJECT_TAG: &str = "DeletedObject";
+pub const DELETED_OBJECTS_TAG: &str = "DeletedObjects";
+pub const DELETION_TIME_TAG: &str = "DeletionTime";
 pub const ENABLED_TAG: &'static str = "Enabled";
 pub const ENABLE_AUTO_TYPE_TAG: &'static str = "EnableAutoType";
 pub const ENABLE_SEARCHING_TAG: &'static str = "EnableSearching";
@@ -143,6 +146,7 @@ pub const RECYCLE_BIN_CHANGED_TAG: &'static str = "RecycleBinChanged";
 pub const RECYCLE_BIN_ENABLED_TAG: &'static str = "RecycleBinEnabled";
 pub const RECYCLE_BIN_UUID_TAG: &'static str = "RecycleBinUUID";
 pub const ROOT_TAG: &'static str = "Root";
+pub const SETTINGS_CHANGED_TAG: &str = "SettingsChanged";
 pub const STRING_TAG: &'static str = "String";
 pub const TAGS_TAG: &'static str = "Tags";
 pub const TIMES_TAG: &'static str = "Times";
@@ -0,0 +1,229 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The block reader for the payload of KeePass 2 databases.
+
+use super::kdb2;
+use crate::crypto::sha256;
+use crate::types::Error;
+use crate::types::Result;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::cmp;
+use std::io::{self, Read};
+
+/// Reader that returns the data of the hashed blocks read from the
+/// underlying reader.
+///
+/// The blocks are read and verified one at a time. When reading fails the
+/// actual error can be retrieved using `take_error`.
+pub struct BlockReader<R> {
+    reader: R,
+    block_id: u32,
+    max_blocks: u32,
+    buffer: Vec<u8>,
+    pos: usize,
+    done: bool,
+    error: Option<Error>,
+}
+
+impl<R: Read> BlockReader<R> {
+    /// Create a new block reader that accepts at most max_blocks data blocks.
+    pub fn new(reader: R, max_blocks: u32) -> BlockReader<R> {
+        BlockReader {
+            reader,
+            block_id: 0,
+            max_blocks,
+            buffer: Vec::new(),
+            pos: 0,
+            done: false,
+            error: None,
+        }
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Takes the error that caused the last read to fail if any.
+    pub fn take_error(&mut self) -> Option<Error> {
+        self.error.take()
+    }
+
+    fn read_block(&mut self) -> Result<()> {
+        let id = self.reader.read_u32::<LittleEndian>()?;
+        let mut hash = [0u8; 32];
+        self.reader.read_exact(&mut hash)?;
+        let size = self.reader.read_u32::<LittleEndian>()? as usize;
+
+        if id != self.block_id {
+            return Err(Error::InvalidBlockId(id));
+        }
+
+        if size == 0 {
+            if hash == kdb2::FINAL_BLOCK_HASH {
+                self.done = true;
+                return Ok(());
+            } else {
+                return Err(Error::InvalidFinalBlockHash(hash));
+            }
+        }
+
+        if self.block_id >= self.max_blocks {
+            return Err(Error::TooManyBlocks(self.max_blocks));
+        }
+
+        // Don't trust the size for the allocation, the data might be shorter.
+        self.buffer.clear();
+        let count = (&mut self.reader)
+            .take(size as u64)
+            .read_to_end(&mut self.buffer)?;
+        if count != size {
+            return Err(Error::from(io::Error::from(io::ErrorKind::UnexpectedEof)));
+        }
+        self.pos = 0;
+
+        let block_hash = sha256::hash(&[&self.buffer]);
+        if block_hash != hash {
+            return Err(Error::InvalidBlockHash);
+        }
+
+        self.block_id += 1;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for BlockReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.buffer.len() && !self.done {
+            if let Err(err) = self.read_block() {
+                let io_err = io::Error::new(io::ErrorKind::InvalidData, format!("{}", err));
+                self.error = Some(err);
+                return Err(io_err);
+            }
+        }
+        let size = cmp::min(buf.len(), self.buffer.len() - self.pos);
+        buf[..size].copy_from_slice(&self.buffer[self.pos..self.pos + size]);
+        self.pos += size;
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use byteorder::WriteBytesExt;
+
+    fn write_block(payload: &mut Vec<u8>, id: u32, data: &[u8]) {
+        payload.write_u32::<LittleEndian>(id).unwrap();
+        payload.extend_from_slice(&sha256::hash(&[data]));
+        payload
+            .write_u32::<LittleEndian>(data.len() as u32)
+            .unwrap();
+        payload.extend_from_slice(data);
+    }
+
+    fn write_block_final(payload: &mut Vec<u8>, id: u32) {
+        payload.write_u32::<LittleEndian>(id).unwrap();
+        payload.extend_from_slice(&kdb2::FINAL_BLOCK_HASH);
+        payload.write_u32::<LittleEndian>(0).unwrap();
+    }
+
+    #[test]
+    fn test_read_returns_data_of_all_blocks() {
+        let mut payload = Vec::new();
+        write_block(&mut payload, 0, b"first ");
+        write_block(&mut payload, 1, b"second");
+        write_block_final(&mut payload, 2);
+        payload.extend_from_slice(b"ignored");
+
+        let mut reader = BlockReader::new(&payload[..], u32::MAX);
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).unwrap();
+        assert_eq!(data, b"first second");
+    }
+
+    #[test]
+    fn test_read_with_invalid_block_id_returns_error() {
+        let mut payload = Vec::new();
+        write_block(&mut payload, 1, b"data");
+        write_block_final(&mut payload, 2);
+
+        let mut reader = BlockReader::new(&payload[..], u32::MAX);
+        assert!(reader.read_to_end(&mut Vec::new()).is_err());
+        match reader.take_error() {
+            Some(Error::InvalidBlockId(1)) => (),
+            _ => panic!("expected invalid block id error"),
+        }
+    }
+
+    #[test]
+    fn test_read_with_invalid_block_hash_returns_error() {
+        let mut payload = Vec::new();
+        write_block(&mut payload, 0, b"data");
+        write_block_final(&mut payload, 1);
+        payload[40] = b'x';
+
+        let mut reader = BlockReader::new(&payload[..], u32::MAX);
+        assert!(reader.read_to_end(&mut Vec::new()).is_err());
+        match reader.take_error() {
+            Some(Error::InvalidBlockHash) => (),
+            _ => panic!("expected invalid block hash error"),
+        }
+    }
+
+    #[test]
+    fn test_read_with_missing_final_block_returns_error() {
+        let mut payload = Vec::new();
+        write_block(&mut payload, 0, b"data");
+
+        let mut reader = BlockReader::new(&payload[..], u32::MAX);
+        assert!(reader.read_to_end(&mut Vec::new()).is_err());
+        match reader.take_error() {
+            Some(Error::Io(_)) => (),
+            _ => panic!("expected I/O error"),
+        }
+    }
+
+    #[test]
+    fn test_read_with_too_many_blocks_returns_error() {
+        let mut payload = Vec::new();
+        write_block(&mut payload, 0, b"first ");
+        write_block(&mut payload, 1, b"second");
+        write_block_final(&mut payload, 2);
+
+        let mut data = Vec::new();
+        let mut reader = BlockReader::new(&payload[..], 2);
+        reader.read_to_end(&mut data).unwrap();
+
+        let mut reader = BlockReader::new(&payload[..], 1);
+        assert!(reader.read_to_end(&mut data).is_err());
+        match reader.take_error() {
+            Some(Error::TooManyBlocks(1)) => (),
+            _ => panic!("expected TooManyBlocks error"),
+        }
+    }
+
+    #[test]
+    fn test_read_with_truncated_block_returns_error() {
+        let mut payload = Vec::new();
+        payload.write_u32::<LittleEndian>(0).unwrap();
+        payload.extend_from_slice(&[0u8; 32]);
+        payload.write_u32::<LittleEndian>(u32::MAX).unwrap();
+        payload.extend_from_slice(b"short");
+
+        let mut data = Vec::new();
+        let mut reader = BlockReader::new(&payload[..], u32::MAX);
+        assert!(reader.read_to_end(&mut data).is_err());
+        match reader.take_error() {
+            Some(Error::Io(_)) => (),
+            _ => panic!("expected Io error"),
+        }
+    }
+}
@@ -9,11 +9,13 @@
 //! The database reader for KeePass 2 databases.
 
 use super::kdb2;
+use super::kdb2_block_reader::BlockReader;
 use super::kdb2_xml_reader;
 use crate::compression::gzip;
 use crate::crypto::aes256;
 use crate::crypto::sha256;
-use crate::io::Log;
+use crate::io::{LimitReader, Log};
+use crate::rust_crypto::symmetriccipher::SymmetricCipherError;
 use crate::types::Comment;
 use crate::types::CompositeKey;
 use crate::types::Compression;
@@ -24,7 +26,10 @@ use crate::types::MasterIV;
 use crate::types::MasterKey;
 use crate::types::MasterSeed;
 use crate::types::MetaData;
+use crate::types::OpenOptions;
 use crate::types::ProtectedStreamKey;
+use crate::types::ReadLimits;
+use crate::types::RecoveredError;
 use crate::types::Result;
 use crate::types::StreamCipher;
 use crate::types::StreamKey;
@@ -35,14 +40,183 @@ use crate::types::TransformedKey;
 use crate::types::Version;
 use crate::types::XmlData;
 use byteorder::{LittleEndian, ReadBytesExt};
-use std::io::{Cursor, Read};
+use std::io::{self, Read, Seek, SeekFrom};
 
 /// Attempts to read the database content from the reader.
-pub fn read<R>(reader: &mut R, composite_key: &CompositeKey) -> Result<(MetaData, XmlData)>
+///
+/// When the compression header is missing the payload is assumed to be
+/// uncompressed.
+///
+/// The payload is decrypted, verified and decompressed while the XML is
+/// parsed, so the decrypted payload is never completely in memory.
+pub fn read<R>(
+    reader: &mut R,
+    composite_key: &CompositeKey,
+    options: &OpenOptions,
+) -> Result<(MetaData, XmlData)>
 where
     R: Log + Read,
 {
+    let header = read_header(reader, options)?;
+    let transformed_key = transform_key(&header, composite_key, &options.limits)?;
+    read_payload(reader, header, &transformed_key, options)
+}
+
+/// Attempts to read the unencrypted XML data from the reader.
+pub fn read_plaintext_xml<R: Read>(reader: &mut R, stream_key: &StreamKey) -> Result<XmlData> {
+    kdb2_xml_reader::read(reader, stream_key, false)
+}
+
+/// Attempts to read the database content after verifying that the size of
+/// the encrypted data is valid.
+pub fn read_seek<R>(
+    reader: &mut R,
+    composite_key: &CompositeKey,
+    options: &OpenOptions,
+) -> Result<(MetaData, XmlData)>
+where
+    R: Log + Read + Seek,
+{
+    let header = read_header(reader, options)?;
+    let start = reader.stream_position()?;
+    let end = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(start))?;
+
+    let size = end.saturating_sub(start);
+    if size < 32 || size % 16 != 0 {
+        return Err(Error::CryptoError(SymmetricCipherError::InvalidLength));
+    }
+
+    let transformed_key = transform_key(&header, composite_key, &options.limits)?;
+    read_payload(&mut reader.take(size), header, &transformed_key, options)
+}
+
+/// Attempts to read the database content using a previously transformed key.
+pub fn read_with_transformed_key<R>(
+    reader: &mut R,
+    transformed_key: &TransformedKey,
+    options: &OpenOptions,
+) -> Result<(MetaData, XmlData)>
+where
+    R: Log + Read,
+{
+    let header = read_header(reader, options)?;
+    read_payload(reader, header, transformed_key, options)
+}
+
+/// Attempts to transform the composite key using the seed and rounds from
+/// the headers.
+pub fn transformed_key<R>(
+    reader: &mut R,
+    composite_key: &CompositeKey,
+    options: &OpenOptions,
+) -> Result<TransformedKey>
+where
+    R: Log + Read,
+{
+    let header = read_header(reader, options)?;
+    transform_key(&header, composite_key, &options.limits)
+}
+
+fn read_payload<R>(
+    reader: &mut R,
+    header: Header,
+    transformed_key: &TransformedKey,
+    options: &OpenOptions,
+) -> Result<(MetaData, XmlData)>
+where
+    R: Read,
+{
+    let master_key = MasterKey::new(&header.master_seed, transformed_key);
+    let stream_key = StreamKey::new(&header.protected_stream_key);
+
+    let mut decrypted = aes256::DecryptReader::new(reader, &master_key, &header.master_iv)?;
+    let mut start = [0u8; 32];
+    if let Err(err) = decrypted.read_exact(&mut start) {
+        return Err(decrypted.take_error().unwrap_or(Error::from(err)));
+    }
+
+    if start != header.stream_start_bytes.0 {
+        return Err(Error::InvalidKey);
+    }
+
+    let mut blocks = BlockReader::new(decrypted, options.limits.max_blocks);
+    let mut xml_data = match read_xml_data(&mut blocks, &header.compression, &stream_key, options) {
+        Ok(xml_data) => xml_data,
+        Err(err) => {
+            let cause = blocks
+                .take_error()
+                .or_else(|| blocks.get_mut().take_error());
+            return Err(cause.unwrap_or(err));
+        }
+    };
+
+    if !header.version.is_supported() {
+        let err = Error::UnhandledVersion(header.version.clone());
+        xml_data.recovered.insert(
+            0,
+            RecoveredError {
+                element: String::from("Version"),
+                message: err.to_string(),
+            },
+        );
+    }
+
+    let meta_data = MetaData {
+        comment: header.comment,
+        compression: header.compression,
+        header_hash: header.header_hash,
+        master_cipher: header.master_cipher,
+        stream_cipher: header.stream_cipher,
+        transform_rounds: header.transform_rounds,
+        version: header.version,
+    };
+
+    Ok((meta_data, xml_data))
+}
+
+/// Checks whether the composite key can decrypt the database.
+///
+/// Only the headers and the first two encrypted blocks are read.
+pub fn verify_key<R>(
+    reader: &mut R,
+    composite_key: &CompositeKey,
+    options: &OpenOptions,
+) -> Result<bool>
+where
+    R: Log + Read,
+{
+    let header = read_header(reader, options)?;
+    let master_key = get_master_key(&header, composite_key, &options.limits)?;
+
+    let mut encrypted = [0u8; 32];
+    reader.read_exact(&mut encrypted)?;
+    let start = aes256::decrypt_blocks(&master_key, &header.master_iv, &encrypted)?;
+    Ok(start[..] == header.stream_start_bytes.0[..])
+}
+
+struct Header {
+    comment: Option<Comment>,
+    compression: Compression,
+    header_hash: HeaderHash,
+    master_cipher: MasterCipher,
+    master_iv: MasterIV,
+    master_seed: MasterSeed,
+    protected_stream_key: ProtectedStreamKey,
+    stream_cipher: StreamCipher,
+    stream_start_bytes: StreamStartBytes,
+    transform_rounds: TransformRounds,
+    transform_seed: TransformSeed,
+    version: Version,
+}
+
+fn read_header<R>(reader: &mut R, options: &OpenOptions) -> Result<Header>
+where
+    R: Log + Read,
+{
+    let limits = &options.limits;
     let version = read_version(reader)?;
+    check_version(&version, options)?;
     let mut comment: Option<Comment> = None;
     let mut compression: Option<Compression> = None;
     let mut master_cipher: Option<MasterCipher> = None;
@@ -54,7 +228,12 @@ where
     let mut transform_rounds: Option<TransformRounds> = None;
     let mut transform_seed: Option<TransformSeed> = None;
 
+    let mut count = 0;
     loop {
+        count += 1;
+        if count > limits.max_headers {
+            return Err(Error::TooManyHeaders(limits.max_headers));
+        }
         let header_id = reader.read_u8()?;
         match header_id {
             kdb2::COMMENT_HID => {
@@ -110,7 +289,7 @@ where
     reader.stop();
     reader.clear();
 
-    let compression = get_header(compression, kdb2::COMPRESSION_HID)?;
+    let compression = compression.unwrap_or(Compression::None);
     let master_cipher = get_header(master_cipher, kdb2::MASTER_CIPHER_HID)?;
     let master_iv = get_header(master_iv, kdb2::MASTER_IV_HID)?;
     let master_seed = get_header(master_seed, kdb2::MASTER_SEED_HID)?;
@@ -120,30 +299,20 @@ where
     let transform_rounds = get_header(transform_rounds, kdb2::TRANSFORM_ROUNDS_HID)?;
     let transform_seed = get_header(transform_seed, kdb2::TRANSFORM_SEED_HID)?;
 
-    let transformed_key = TransformedKey::new(&composite_key, &transform_seed, &transform_rounds);
-    let master_key = MasterKey::new(&master_seed, &transformed_key);
-    let stream_key = StreamKey::new(&protected_stream_key);
-
-    let encrypted = read_enc_payload(reader)?;
-    let payload = aes256::decrypt(&master_key, &master_iv, &encrypted)?;
-
-    if payload[0..32] != stream_start_bytes.0 {
-        return Err(Error::InvalidKey);
-    }
-
-    let xml_bytes = read_xml_bytes(&compression, &payload[32..])?;
-    let xml_data = kdb2_xml_reader::read(&mut Cursor::new(xml_bytes), &stream_key)?;
-    let meta_data = MetaData {
-        comment: comment,
-        compression: compression,
-        header_hash: header_hash,
-        master_cipher: master_cipher,
-        stream_cipher: stream_cipher,
-        transform_rounds: transform_rounds,
-        version: version,
-    };
-
-    Ok((meta_data, xml_data))
+    Ok(Header {
+        comment,
+        compression,
+        header_hash,
+        master_cipher,
+        master_iv,
+        master_seed,
+        protected_stream_key,
+        stream_cipher,
+        stream_start_bytes,
+        transform_rounds,
+        transform_seed,
+        version,
+    })
 }
 
 fn read_comment<R: Read>(reader: &mut R) -> Result<Comment> {
@@ -176,12 +345,6 @@ fn read_end_header<R: Read>(reader: &mut R) -> Result<()> {
     Ok(())
 }
 
-fn read_enc_payload<R: Read>(reader: &mut R) -> Result<Vec<u8>> {
-    let mut data = Vec::new();
-    reader.read_to_end(&mut data)?;
-    Ok(data)
-}
-
 fn read_master_cipher<R: Read>(reader: &mut R) -> Result<MasterCipher> {
     let size = reader.read_u16::<LittleEndian>()?;
     if size == kdb2::MASTER_CIPHER_SIZE {
@@ -310,65 +473,365 @@ fn read_version<R: Read>(reader: &mut R) -> Result<Version> {
     })
 }
 
-fn read_xml_bytes(compression: &Compression, payload: &[u8]) -> Result<Vec<u8>> {
-    let mut reader = Cursor::new(payload);
-    let mut xml = Vec::new();
-
-    for block_id in 0..u32::max_value() {
-        let id = reader.read_u32::<LittleEndian>()?;
-        let hash = read_bytes_32(&mut reader)?;
-        let size = reader.read_u32::<LittleEndian>()? as usize;
-        let raw_data = read_bytes_size(&mut reader, &size)?;
-
-        if id != block_id {
-            return Err(Error::InvalidBlockId(id));
-        }
+// The version is checked before the other headers are read, since their
+// meaning depends on it. Unknown minor versions of a supported major version
+// are only accepted in lenient mode, where the reader reports them together
+// with the recovered XML values.
+fn check_version(version: &Version, options: &OpenOptions) -> Result<()> {
+    if version.is_supported() || (options.lenient && version.is_supported_major()) {
+        Ok(())
+    } else {
+        Err(Error::UnhandledVersion(version.clone()))
+    }
+}
 
-        if size == 0 {
-            if hash == kdb2::FINAL_BLOCK_HASH {
-                break;
-            } else {
-                return Err(Error::InvalidFinalBlockHash(hash));
-            }
+fn read_xml_data<R: Read>(
+    blocks: &mut BlockReader<R>,
+    compression: &Compression,
+    stream_key: &StreamKey,
+    options: &OpenOptions,
+) -> Result<XmlData> {
+    let max_xml_size = options.limits.max_xml_size;
+    let xml_data = match *compression {
+        Compression::None => {
+            let mut limited = LimitReader::new(&mut *blocks, max_xml_size);
+            let xml_data = read_xml_limited(&mut limited, stream_key, options)?;
+            io::copy(limited.get_mut(), &mut io::sink())?;
+            xml_data
         }
-
-        let block_hash = sha256::hash(&[&raw_data]);
-        if block_hash != hash {
-            return Err(Error::InvalidBlockHash);
+        Compression::GZip => {
+            let mut limited = LimitReader::new(gzip::decoder(&mut *blocks), max_xml_size);
+            let xml_data = read_xml_limited(&mut limited, stream_key, options)?;
+            io::copy(limited.get_mut(), &mut io::sink())?;
+            io::copy(blocks, &mut io::sink())?;
+            xml_data
         }
+    };
 
-        let mut block_data = decompress(compression, &raw_data)?;
-        xml.append(&mut block_data);
+    // Read the remaining data to verify the padding of the encrypted data,
+    // nothing but the padding may follow the final block.
+    let trailing = io::copy(blocks.get_mut(), &mut io::sink())?;
+    if trailing != 0 {
+        return Err(Error::TrailingData(trailing));
     }
+    Ok(xml_data)
+}
 
-    Ok(xml)
+fn read_xml_limited<R: Read>(
+    reader: &mut LimitReader<R>,
+    stream_key: &StreamKey,
+    options: &OpenOptions,
+) -> Result<XmlData> {
+    match kdb2_xml_reader::read(reader, stream_key, options.lenient) {
+        Err(_) if reader.is_exceeded() => Err(Error::XmlDataTooLarge(options.limits.max_xml_size)),
+        result => result,
+    }
 }
 
 fn read_bytes_16<R: Read>(reader: &mut R) -> Result<[u8; 16]> {
     let mut data = [0; 16];
-    reader.read(&mut data)?;
+    reader.read_exact(&mut data)?;
     Ok(data)
 }
 
 fn read_bytes_32<R: Read>(reader: &mut R) -> Result<[u8; 32]> {
     let mut data = [0; 32];
-    reader.read(&mut data)?;
+    reader.read_exact(&mut data)?;
     Ok(data)
 }
 
 fn read_bytes_size<R: Read>(reader: &mut R, size: &usize) -> Result<Vec<u8>> {
     let mut data = vec![0; *size];
-    reader.read(&mut data)?;
+    reader.read_exact(&mut data)?;
     Ok(data)
 }
 
+fn get_master_key(
+    header: &Header,
+    composite_key: &CompositeKey,
+    limits: &ReadLimits,
+) -> Result<MasterKey> {
+    let transformed_key = transform_key(header, composite_key, limits)?;
+    Ok(MasterKey::new(&header.master_seed, &transformed_key))
+}
+
+fn transform_key(
+    header: &Header,
+    composite_key: &CompositeKey,
+    limits: &ReadLimits,
+) -> Result<TransformedKey> {
+    if header.transform_rounds.0 > limits.max_transform_rounds {
+        return Err(Error::TooManyTransformRounds(limits.max_transform_rounds));
+    }
+    Ok(TransformedKey::new(composite_key, &header.transform_seed, &header.transform_rounds))
+}
+
 fn get_header<T>(header: Option<T>, header_id: u8) -> Result<T> {
     header.ok_or(Error::MissingHeader(header_id))
 }
 
-fn decompress(compression: &Compression, data: &[u8]) -> Result<Vec<u8>> {
-    match *compression {
-        Compression::None => Ok(data.to_vec()),
-        Compression::GZip => gzip::decode(data),
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::format::kdb2_writer;
+    use crate::io::{LogReader, LogWriter};
+    use crate::types::Database;
+    use crate::types::SaveOptions;
+    use byteorder::WriteBytesExt;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_without_compression_header_reads_uncompressed_payload() {
+        let key = CompositeKey::from_password("test");
+        let mut db = Database::new(&key);
+        db.compression = Compression::None;
+        db.name = String::from("name");
+
+        let mut data = Vec::new();
+        kdb2_writer::write_with_options(
+            &mut LogWriter::new(&mut data),
+            &db,
+            &SaveOptions::default(),
+        )
+        .unwrap();
+        let data = remove_header(data, kdb2::COMPRESSION_HID);
+
+        let mut reader = LogReader::new(Cursor::new(&data[8..]));
+        let (meta_data, xml_data) = read(&mut reader, &key, &OpenOptions::default()).unwrap();
+        assert_eq!(meta_data.compression, Compression::None);
+        assert_eq!(xml_data.name, "name");
+    }
+
+    #[test]
+    fn test_read_with_invalid_compression_header_size_returns_error() {
+        let key = CompositeKey::from_password("test");
+        let db = Database::new(&key);
+
+        let mut data = Vec::new();
+        kdb2_writer::write_with_options(
+            &mut LogWriter::new(&mut data),
+            &db,
+            &SaveOptions::default(),
+        )
+        .unwrap();
+        let pos = find_header(&data, kdb2::COMPRESSION_HID);
+        data[pos + 1] = 2;
+
+        let mut reader = LogReader::new(Cursor::new(&data[8..]));
+        match read(&mut reader, &key, &OpenOptions::default()) {
+            Err(Error::InvalidHeaderSize { id, .. }) => assert_eq!(id, kdb2::COMPRESSION_HID),
+            _ => panic!("expected invalid header size error"),
+        }
+    }
+
+    #[test]
+    fn test_read_with_partial_reads_computes_correct_header_hash() {
+        let key = CompositeKey::from_password("test");
+        let db = Database::new(&key);
+
+        let mut data = Vec::new();
+        kdb2_writer::write_with_options(
+            &mut LogWriter::new(&mut data),
+            &db,
+            &SaveOptions::default(),
+        )
+        .unwrap();
+
+        let mut reader = LogReader::new(OneByteReader(Cursor::new(&data)));
+        reader.read_exact(&mut [0u8; 8]).unwrap();
+        let (meta_data, xml_data) = read(&mut reader, &key, &OpenOptions::default()).unwrap();
+        assert_eq!(xml_data.header_hash, Some(meta_data.header_hash));
+    }
+
+    #[test]
+    fn test_read_with_unhandled_version_returns_error() {
+        let key = CompositeKey::from_password("test");
+        let data = write_with_version(&key, 3, 2);
+
+        let mut reader = LogReader::new(Cursor::new(&data[8..]));
+        match read(&mut reader, &key, &OpenOptions::default()) {
+            Err(Error::UnhandledVersion(version)) => assert_eq!(version.to_string(), "3.2"),
+            _ => panic!("expected unhandled version error"),
+        }
+    }
+
+    #[test]
+    fn test_read_lenient_with_unhandled_version_reports_version() {
+        let key = CompositeKey::from_password("test");
+        let data = write_with_version(&key, 3, 2);
+
+        let mut reader = LogReader::new(Cursor::new(&data[8..]));
+        let options = OpenOptions::new().lenient(true);
+        let (meta_data, xml_data) = read(&mut reader, &key, &options).unwrap();
+        assert_eq!(meta_data.version, Version { major: 3, minor: 2 });
+        assert_eq!(xml_data.recovered.len(), 1);
+        assert_eq!(xml_data.recovered[0].message, "Unhandled version: 3.2");
+    }
+
+    #[test]
+    fn test_read_lenient_with_unhandled_major_version_returns_error() {
+        let key = CompositeKey::from_password("test");
+        let mut data = write_with_version(&key, 4, 0);
+        let pos = find_header(&data, kdb2::COMPRESSION_HID);
+        data[pos + 1] = 2;
+
+        let mut reader = LogReader::new(Cursor::new(&data[8..]));
+        let options = OpenOptions::new().lenient(true);
+        match read(&mut reader, &key, &options) {
+            Err(Error::UnhandledVersion(version)) => assert_eq!(version.to_string(), "4.0"),
+            _ => panic!("expected unhandled version error"),
+        }
+    }
+
+    #[test]
+    fn test_read_with_too_many_headers_returns_error() {
+        let key = CompositeKey::from_password("test");
+        let db = Database::new(&key);
+
+        let mut data = Vec::new();
+        kdb2_writer::write_with_options(
+            &mut LogWriter::new(&mut data),
+            &db,
+            &SaveOptions::default(),
+        )
+        .unwrap();
+
+        let options = OpenOptions::new().max_headers(3);
+        let mut reader = LogReader::new(Cursor::new(&data[8..]));
+        match read(&mut reader, &key, &options) {
+            Err(Error::TooManyHeaders(3)) => (),
+            _ => panic!("expected too many headers error"),
+        }
+    }
+
+    #[test]
+    fn test_read_with_too_many_blocks_returns_error() {
+        let key = CompositeKey::from_password("test");
+        let db = Database::new(&key);
+
+        let mut data = Vec::new();
+        let options = SaveOptions::new().block_size(16);
+        kdb2_writer::write_with_options(&mut LogWriter::new(&mut data), &db, &options).unwrap();
+
+        let options = OpenOptions::new().max_blocks(2);
+        let mut reader = LogReader::new(Cursor::new(&data[8..]));
+        match read(&mut reader, &key, &options) {
+            Err(Error::TooManyBlocks(2)) => (),
+            _ => panic!("expected too many blocks error"),
+        }
+    }
+
+    #[test]
+    fn test_read_with_too_many_transform_rounds_returns_error() {
+        let key = CompositeKey::from_password("test");
+        let db = Database::new(&key);
+
+        let mut data = Vec::new();
+        kdb2_writer::write_with_options(
+            &mut LogWriter::new(&mut data),
+            &db,
+            &SaveOptions::default(),
+        )
+        .unwrap();
+
+        let options = OpenOptions::new().max_transform_rounds(9999);
+        let mut reader = LogReader::new(Cursor::new(&data[8..]));
+        match read(&mut reader, &key, &options) {
+            Err(Error::TooManyTransformRounds(9999)) => (),
+            _ => panic!("expected too many transform rounds error"),
+        }
+    }
+
+    #[test]
+    fn test_read_with_too_large_xml_data_returns_error() {
+        let key = CompositeKey::from_password("test");
+        for compression in [Compression::None, Compression::GZip] {
+            let mut db = Database::new(&key);
+            db.compression = compression;
+
+            let mut data = Vec::new();
+            kdb2_writer::write_with_options(
+                &mut LogWriter::new(&mut data),
+                &db,
+                &SaveOptions::default(),
+            )
+            .unwrap();
+
+            let options = OpenOptions::new().max_xml_size(100);
+            let mut reader = LogReader::new(Cursor::new(&data[8..]));
+            match read(&mut reader, &key, &options) {
+                Err(Error::XmlDataTooLarge(100)) => (),
+                _ => panic!("expected XML data too large error"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_xml_data_with_data_after_final_block_returns_error() {
+        let db = Database::new(&CompositeKey::from_password("test"));
+        let mut xml = Vec::new();
+        kdb2_writer::write_plaintext_xml(&mut xml, &db).unwrap();
+
+        let mut payload = Vec::new();
+        payload.write_u32::<LittleEndian>(0).unwrap();
+        payload.extend_from_slice(&sha256::hash(&[&xml]));
+        payload.write_u32::<LittleEndian>(xml.len() as u32).unwrap();
+        payload.extend_from_slice(&xml);
+        payload.write_u32::<LittleEndian>(1).unwrap();
+        payload.extend_from_slice(&kdb2::FINAL_BLOCK_HASH);
+        payload.write_u32::<LittleEndian>(0).unwrap();
+        payload.extend_from_slice(b"garbage");
+
+        let mut blocks = BlockReader::new(&payload[..], u32::MAX);
+        let stream_key = StreamKey::new(&ProtectedStreamKey([0; 32]));
+        let options = OpenOptions::default();
+        match read_xml_data(&mut blocks, &Compression::None, &stream_key, &options) {
+            Err(Error::TrailingData(7)) => (),
+            _ => panic!("expected trailing data error"),
+        }
+    }
+
+    struct OneByteReader<R>(R);
+
+    impl<R: Read> Read for OneByteReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let size = buf.len().min(1);
+            self.0.read(&mut buf[..size])
+        }
+    }
+
+    fn write_with_version(key: &CompositeKey, major: u16, minor: u16) -> Vec<u8> {
+        let db = Database::new(key);
+        let mut data = Vec::new();
+        kdb2_writer::write_with_options(
+            &mut LogWriter::new(&mut data),
+            &db,
+            &SaveOptions::default(),
+        )
+        .unwrap();
+        data[8..10].copy_from_slice(&minor.to_le_bytes());
+        data[10..12].copy_from_slice(&major.to_le_bytes());
+        data
+    }
+
+    fn find_header(data: &[u8], header_id: u8) -> usize {
+        let mut pos = 12;
+        loop {
+            let id = data[pos];
+            let size = u16::from_le_bytes([data[pos + 1], data[pos + 2]]) as usize;
+            if id == header_id {
+                return pos;
+            }
+            assert!(id != kdb2::END_HID, "header not found");
+            pos += 3 + size;
+        }
+    }
+
+    fn remove_header(mut data: Vec<u8>, header_id: u8) -> Vec<u8> {
+        let pos = find_header(&data, header_id);
+        let size = u16::from_le_bytes([data[pos + 1], data[pos + 2]]) as usize;
+        data.drain(pos..pos + 3 + size);
+        data
     }
 }
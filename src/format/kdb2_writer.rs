@@ -12,12 +12,14 @@ use super::{kdb2, kdb2_xml_writer};
 use crate::common;
 use crate::compression::gzip;
 use crate::crypto::aes256;
-use crate::crypto::random_gen::RandomGen;
 use crate::crypto::sha256;
 use crate::io::Log;
+use crate::types::BinaryValue;
 use crate::types::Comment;
 use crate::types::Compression;
 use crate::types::Database;
+use crate::types::Entry;
+use crate::types::Error;
 use crate::types::HeaderHash;
 use crate::types::MasterCipher;
 use crate::types::MasterIV;
@@ -25,6 +27,7 @@ use crate::types::MasterKey;
 use crate::types::MasterSeed;
 use crate::types::ProtectedStreamKey;
 use crate::types::Result;
+use crate::types::SaveOptions;
 use crate::types::StreamCipher;
 use crate::types::StreamKey;
 use crate::types::StreamStartBytes;
@@ -33,20 +36,47 @@ use crate::types::TransformSeed;
 use crate::types::TransformedKey;
 use crate::types::Version;
 use byteorder::{LittleEndian, WriteBytesExt};
+use std::borrow::Cow;
 use std::io::Write;
 
-/// Attempts to write the database content to the writer.
-pub fn write<W: Log + Write>(writer: &mut W, db: &Database) -> Result<()> {
-    let mut random = RandomGen::new()?;
-    let transform_seed = TransformSeed(random.next_32_bytes());
+/// Attempts to write the unencrypted XML data with all protected values in
+/// cleartext to the writer.
+pub fn write_plaintext_xml<W: Write>(writer: &mut W, db: &Database) -> Result<()> {
+    kdb2_xml_writer::write_plaintext(writer, db)
+}
+
+/// Returns the size of the comment header, or an error when the comment
+/// doesn't fit in the 16-bit header size field.
+pub fn comment_size(comment: &Comment) -> Result<u16> {
+    u16::try_from(comment.0.len()).map_err(|_| Error::CommentTooLarge(comment.0.len()))
+}
+
+/// Attempts to write the database content to the writer using the supplied
+/// options.
+pub fn write_with_options<W>(writer: &mut W, db: &Database, options: &SaveOptions) -> Result<()>
+where
+    W: Log + Write,
+{
+    if options.block_size == 0 || options.block_size > u32::MAX as usize {
+        return Err(Error::InvalidBlockSize(options.block_size));
+    }
+    if db.composite_key.is_empty() {
+        return Err(Error::MissingCompositeKey);
+    }
+    if let Some(ref comment) = db.comment {
+        comment_size(comment)?;
+    }
+    check_binary_refs(db)?;
+
+    let transform_seed = TransformSeed::new_random()?;
     let transformed_key =
         TransformedKey::new(&db.composite_key, &transform_seed, &db.transform_rounds);
-    let master_iv = MasterIV(random.next_16_bytes());
-    let master_seed = MasterSeed(random.next_32_bytes());
+    let master_iv = MasterIV::new_random()?;
+    let master_seed = MasterSeed::new_random()?;
     let master_key = MasterKey::new(&master_seed, &transformed_key);
-    let protected_stream_key = ProtectedStreamKey(random.next_32_bytes());
+    let protected_stream_key = ProtectedStreamKey::new_random()?;
     let stream_key = StreamKey::new(&protected_stream_key);
-    let stream_start_bytes = StreamStartBytes(random.next_32_bytes());
+    let stream_start_bytes = StreamStartBytes::new_random()?;
 
     write_sig_1(writer)?;
     write_sig_2(writer)?;
@@ -68,14 +98,13 @@ pub fn write<W: Log + Write>(writer: &mut W, db: &Database) -> Result<()> {
     writer.clear();
 
     let mut xml = Vec::new();
-    kdb2_xml_writer::write(&mut xml, db, &hash, &stream_key)?;
+    kdb2_xml_writer::write(&mut xml, db, &hash, &stream_key, options)?;
 
     let mut payload = Vec::new();
     payload.write(&stream_start_bytes.0)?;
 
-    let compressed = compress(&db.compression, &xml)?;
-    write_block(&mut payload, 0, &compressed)?;
-    write_block_final(&mut payload, 1)?;
+    let compressed = compress(&db.compression, &xml, options.compression_level)?;
+    write_blocks(&mut payload, &compressed, options.block_size)?;
 
     let encrypted = aes256::encrypt(&master_key, &master_iv, &payload)?;
     writer.write(&encrypted)?;
@@ -91,6 +120,15 @@ fn write_block<W: Write>(writer: &mut W, id: u32, data: &[u8]) -> Result<()> {
     Ok(())
 }
 
+fn write_blocks<W: Write>(writer: &mut W, data: &[u8], block_size: usize) -> Result<()> {
+    let mut id = 0;
+    for chunk in data.chunks(block_size) {
+        write_block(writer, id, chunk)?;
+        id += 1;
+    }
+    write_block_final(writer, id)
+}
+
 fn write_block_final<W: Write>(writer: &mut W, id: u32) -> Result<()> {
     writer.write_u32::<LittleEndian>(id)?;
     writer.write(&kdb2::FINAL_BLOCK_HASH)?;
@@ -107,7 +145,7 @@ fn write_comment<W: Write>(writer: &mut W, opt: &Option<Comment>) -> Result<()>
     match *opt {
         Some(ref comment) => {
             write_header_id(writer, kdb2::COMMENT_HID)?;
-            write_header_size(writer, comment.0.len() as u16)?;
+            write_header_size(writer, comment_size(comment)?)?;
             write_bytes(writer, &comment.0)?;
             Ok(())
         }
@@ -219,9 +257,166 @@ fn write_version<W: Write>(writer: &mut W, version: &Version) -> Result<()> {
     Ok(())
 }
 
-fn compress(compression: &Compression, data: &[u8]) -> Result<Vec<u8>> {
+fn check_binary_refs(db: &Database) -> Result<()> {
+    for entry in db.entries() {
+        check_entry_binary_refs(db, entry)?;
+    }
+    Ok(())
+}
+
+fn check_entry_binary_refs(db: &Database, entry: &Entry) -> Result<()> {
+    for value in entry.binaries.values() {
+        if let BinaryValue::Ref(ref id) = *value {
+            if !db.binaries.contains_key(id) {
+                return Err(Error::MissingBinary {
+                    entry: entry.uuid,
+                    id: id.clone(),
+                });
+            }
+        }
+    }
+    for old in entry.history.iter() {
+        check_entry_binary_refs(db, old)?;
+    }
+    Ok(())
+}
+
+fn compress<'a>(compression: &Compression, data: &'a [u8], level: u32) -> Result<Cow<'a, [u8]>> {
     match *compression {
-        Compression::None => Ok(data.to_vec()),
-        Compression::GZip => gzip::encode(data),
+        Compression::None => Ok(Cow::Borrowed(data)),
+        Compression::GZip => Ok(Cow::Owned(gzip::encode_with_level(data, level)?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::io::LogWriter;
+    use crate::types::BinaryId;
+    use crate::types::BinaryKey;
+    use crate::types::CompositeKey;
+    use byteorder::{LittleEndian, ReadBytesExt};
+    use std::io::{Cursor, Read};
+
+    #[test]
+    fn test_compress_with_none_borrows_data() {
+        let data = vec![1, 2, 3];
+        match compress(&Compression::None, &data, 6).unwrap() {
+            Cow::Borrowed(slice) => assert_eq!(slice, &data[..]),
+            Cow::Owned(_) => panic!("expected borrowed data"),
+        }
+    }
+
+    #[test]
+    fn test_compress_with_gzip_returns_decodable_data() {
+        let data = vec![1, 2, 3];
+        let compressed = compress(&Compression::GZip, &data, 6).unwrap();
+        assert_eq!(gzip::decode(&compressed).unwrap(), data);
+    }
+
+    #[test]
+    fn test_write_with_missing_binary_returns_error() {
+        let key = CompositeKey::from_password("test");
+        let mut db = Database::new(&key);
+        let mut entry = Entry::new();
+        let entry_uuid = entry.uuid;
+        let mut old = entry.clone();
+        old.binaries
+            .insert(BinaryKey(String::from("file")), BinaryValue::Ref(BinaryId(String::from("0"))));
+        entry.history.push(old);
+        db.root_group.add_entry(entry);
+
+        let options = SaveOptions::default();
+        match write_with_options(&mut LogWriter::new(Vec::new()), &db, &options) {
+            Err(Error::MissingBinary { entry, id }) => {
+                assert_eq!(entry, entry_uuid);
+                assert_eq!(id, BinaryId(String::from("0")));
+            }
+            _ => panic!("expected missing binary error"),
+        }
+
+        db.binaries.insert(BinaryId(String::from("0")), vec![1]);
+        assert!(write_with_options(&mut LogWriter::new(Vec::new()), &db, &options).is_ok());
+    }
+
+    #[test]
+    fn test_write_with_zero_block_size_returns_error() {
+        let db = Database::new(&CompositeKey::from_password("test"));
+        let options = SaveOptions::new().block_size(0);
+        match write_with_options(&mut LogWriter::new(Vec::new()), &db, &options) {
+            Err(Error::InvalidBlockSize(0)) => (),
+            _ => panic!("expected invalid block size error"),
+        }
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_write_with_too_large_block_size_returns_error() {
+        let db = Database::new(&CompositeKey::from_password("test"));
+        let size = u32::MAX as usize + 1;
+        let options = SaveOptions::new().block_size(size);
+        match write_with_options(&mut LogWriter::new(Vec::new()), &db, &options) {
+            Err(Error::InvalidBlockSize(val)) if val == size => (),
+            _ => panic!("expected invalid block size error"),
+        }
+    }
+
+    #[test]
+    fn test_write_with_too_large_comment_returns_error() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.comment = Some(Comment(vec![0; 65536]));
+        let options = SaveOptions::new();
+        match write_with_options(&mut LogWriter::new(Vec::new()), &db, &options) {
+            Err(Error::CommentTooLarge(65536)) => (),
+            _ => panic!("expected comment too large error"),
+        }
+    }
+
+    #[test]
+    fn test_write_with_empty_composite_key_returns_error() {
+        let db = Database::new(&CompositeKey::empty());
+        let options = SaveOptions::new();
+        match write_with_options(&mut LogWriter::new(Vec::new()), &db, &options) {
+            Err(Error::MissingCompositeKey) => (),
+            _ => panic!("expected missing composite key error"),
+        }
+    }
+
+    #[test]
+    fn test_write_blocks_splits_data_into_blocks() {
+        let data = vec![7u8; 2500];
+        let mut payload = Vec::new();
+        write_blocks(&mut payload, &data, 1000).unwrap();
+
+        let mut reader = Cursor::new(payload);
+        let mut sizes = Vec::new();
+        for block_id in 0..4 {
+            let id = reader.read_u32::<LittleEndian>().unwrap();
+            let mut hash = [0u8; 32];
+            reader.read_exact(&mut hash).unwrap();
+            let size = reader.read_u32::<LittleEndian>().unwrap();
+            let mut block = vec![0u8; size as usize];
+            reader.read_exact(&mut block).unwrap();
+            assert_eq!(id, block_id);
+            if size == 0 {
+                assert_eq!(hash, kdb2::FINAL_BLOCK_HASH);
+            } else {
+                assert_eq!(hash, sha256::hash(&[&block]));
+            }
+            sizes.push(size);
+        }
+        assert_eq!(sizes, vec![1000, 1000, 500, 0]);
+        assert_eq!(reader.position() as usize, reader.get_ref().len());
+    }
+
+    #[test]
+    fn test_write_blocks_with_empty_data_writes_final_block() {
+        let mut payload = Vec::new();
+        write_blocks(&mut payload, &[], 1000).unwrap();
+        let mut expected = vec![0u8; 4];
+        expected.extend_from_slice(&kdb2::FINAL_BLOCK_HASH);
+        expected.extend_from_slice(&[0u8; 4]);
+        assert_eq!(payload, expected);
     }
 }
@@ -21,30 +21,38 @@ use crate::types::CustomIconsMap;
 use crate::types::Entry;
 use crate::types::EntryState;
 use crate::types::EntryUuid;
+use crate::types::Error;
 use crate::types::Group;
 use crate::types::GroupUuid;
 use crate::types::HeaderHash;
+use crate::types::RecoveredError;
 use crate::types::Result;
 use crate::types::StreamKey;
 use crate::types::StringKey;
 use crate::types::StringValue;
 use crate::types::Times;
 use crate::types::XmlData;
+use chrono::{DateTime, Utc};
 use rust_xml::attribute::OwnedAttribute;
 use rust_xml::reader::{EventReader, XmlEvent};
 use std::io::Read;
+use uuid::Uuid;
 
 /// Attempts to read the XML data from the reader.
-pub fn read<R: Read>(reader: &mut R, stream_key: &StreamKey) -> Result<XmlData> {
+///
+/// In lenient mode unreadable values are replaced by their defaults and
+/// recorded in `XmlData::recovered` instead of returning an error.
+pub fn read<R: Read>(reader: &mut R, stream_key: &StreamKey, lenient: bool) -> Result<XmlData> {
     let mut data = XmlData::default();
     let mut reader = EventReader::new(reader);
     let mut cipher = salsa20::new_cipher(stream_key);
+    let mut recovery = Recovery::new(lenient);
     loop {
         let event = reader.next()?;
         match event {
             XmlEvent::StartElement { name, .. } => match name.local_name.as_str() {
                 kdb2::KEE_PASS_FILE_TAG => {
-                    read_kee_pass_file(&mut reader, &mut data, &mut cipher)?;
+                    read_kee_pass_file(&mut reader, &mut data, &mut cipher, &mut recovery)?;
                 }
                 _ => return xml::read_err(&mut reader, "Invalid root node"),
             },
@@ -57,23 +65,66 @@ pub fn read<R: Read>(reader: &mut R, stream_key: &StreamKey) -> Result<XmlData>
         }
     }
 
+    data.recovered = recovery.errors;
     Ok(data)
 }
 
+struct Recovery {
+    lenient: bool,
+    errors: Vec<RecoveredError>,
+}
+
+impl Recovery {
+    fn new(lenient: bool) -> Recovery {
+        Recovery {
+            lenient,
+            errors: Vec::new(),
+        }
+    }
+
+    // Records a skipped element, outside of lenient mode it is only logged.
+    fn dropped(&mut self, element: &str, err: Error) {
+        if self.lenient {
+            self.push(element, err);
+        } else {
+            eprintln!("{}", err);
+        }
+    }
+
+    // Replaces an unreadable value by the default in lenient mode.
+    fn value<T>(&mut self, element: &str, result: Result<T>, default: T) -> Result<T> {
+        match result {
+            Err(err @ Error::XmlError(_)) if self.lenient => {
+                self.push(element, err);
+                Ok(default)
+            }
+            result => result,
+        }
+    }
+
+    fn push(&mut self, element: &str, err: Error) {
+        self.errors.push(RecoveredError {
+            element: String::from(element),
+            message: err.to_string(),
+        });
+    }
+}
+
 fn read_kee_pass_file<R: Read>(
     reader: &mut EventReader<R>,
     data: &mut XmlData,
     cipher: &mut Salsa20,
+    recovery: &mut Recovery,
 ) -> Result<()> {
     loop {
         let event = reader.next()?;
         match event {
             XmlEvent::StartElement { name, .. } => match name.local_name.as_str() {
                 kdb2::META_TAG => {
-                    read_meta(reader, data)?;
+                    read_meta(reader, data, recovery)?;
                 }
                 kdb2::ROOT_TAG => {
-                    read_root(reader, data, cipher)?;
+                    read_root(reader, data, cipher, recovery)?;
                 }
                 _ => {}
             },
@@ -91,7 +142,11 @@ fn read_kee_pass_file<R: Read>(
     Ok(())
 }
 
-fn read_meta<R: Read>(reader: &mut EventReader<R>, data: &mut XmlData) -> Result<()> {
+fn read_meta<R: Read>(
+    reader: &mut EventReader<R>,
+    data: &mut XmlData,
+    recovery: &mut Recovery,
+) -> Result<()> {
     loop {
         let event = reader.next()?;
         match event {
@@ -100,7 +155,11 @@ fn read_meta<R: Read>(reader: &mut EventReader<R>, data: &mut XmlData) -> Result
                     data.binaries = read_binaries(reader)?;
                 }
                 kdb2::COLOR_TAG => {
-                    data.color = xml::read_color_opt(reader)?;
+                    data.color = recovery.value(
+                        kdb2::COLOR_TAG,
+                        xml::read_color_opt(reader),
+                        data.color.clone(),
+                    )?;
                 }
                 kdb2::CUSTOM_DATA_TAG => {
                     data.custom_data = read_custom_data(reader)?;
@@ -112,25 +171,45 @@ fn read_meta<R: Read>(reader: &mut EventReader<R>, data: &mut XmlData) -> Result
                     data.description = xml::read_string(reader)?;
                 }
                 kdb2::DATABASE_DESCRIPTION_CHANGED_TAG => {
-                    data.description_changed = xml::read_datetime(reader)?;
+                    data.description_changed = recovery.value(
+                        kdb2::DATABASE_DESCRIPTION_CHANGED_TAG,
+                        xml::read_datetime(reader),
+                        data.description_changed,
+                    )?;
                 }
                 kdb2::DATABASE_NAME_TAG => {
                     data.name = xml::read_string(reader)?;
                 }
                 kdb2::DATABASE_NAME_CHANGED_TAG => {
-                    data.name_changed = xml::read_datetime(reader)?;
+                    data.name_changed = recovery.value(
+                        kdb2::DATABASE_NAME_CHANGED_TAG,
+                        xml::read_datetime(reader),
+                        data.name_changed,
+                    )?;
                 }
                 kdb2::DEFAULT_USERNAME_TAG => {
                     data.def_username = xml::read_string(reader)?;
                 }
                 kdb2::DEFAULT_USERNAME_CHANGED_TAG => {
-                    data.def_username_changed = xml::read_datetime(reader)?;
+                    data.def_username_changed = recovery.value(
+                        kdb2::DEFAULT_USERNAME_CHANGED_TAG,
+                        xml::read_datetime(reader),
+                        data.def_username_changed,
+                    )?;
                 }
                 kdb2::ENTRY_TEMPLATES_GROUP_TAG => {
-                    data.entry_templates_group_uuid = GroupUuid(xml::read_uuid(reader)?);
+                    data.entry_templates_group_uuid = recovery.value(
+                        kdb2::ENTRY_TEMPLATES_GROUP_TAG,
+                        xml::read_uuid(reader).map(GroupUuid),
+                        data.entry_templates_group_uuid,
+                    )?;
                 }
                 kdb2::ENTRY_TEMPLATES_GROUP_CHANGED_TAG => {
-                    data.entry_templates_group_changed = xml::read_datetime(reader)?;
+                    data.entry_templates_group_changed = recovery.value(
+                        kdb2::ENTRY_TEMPLATES_GROUP_CHANGED_TAG,
+                        xml::read_datetime(reader),
+                        data.entry_templates_group_changed,
+                    )?;
                 }
                 kdb2::GENERATOR_TAG => {
                     data.generator = xml::read_string(reader)?;
@@ -139,40 +218,91 @@ fn read_meta<R: Read>(reader: &mut EventReader<R>, data: &mut XmlData) -> Result
                     data.header_hash = Some(HeaderHash(xml::read_binary(reader)?));
                 }
                 kdb2::HISTORY_MAX_ITEMS_TAG => {
-                    data.history_max_items = xml::read_i32(reader)?;
+                    data.history_max_items = recovery.value(
+                        kdb2::HISTORY_MAX_ITEMS_TAG,
+                        xml::read_i32(reader),
+                        data.history_max_items,
+                    )?;
                 }
                 kdb2::HISTORY_MAX_SIZE_TAG => {
-                    data.history_max_size = xml::read_i32(reader)?;
+                    data.history_max_size = recovery.value(
+                        kdb2::HISTORY_MAX_SIZE_TAG,
+                        xml::read_i32(reader),
+                        data.history_max_size,
+                    )?;
                 }
                 kdb2::LAST_SELECTED_GROUP_TAG => {
-                    data.last_selected_group = GroupUuid(xml::read_uuid(reader)?);
+                    data.last_selected_group = recovery.value(
+                        kdb2::LAST_SELECTED_GROUP_TAG,
+                        xml::read_uuid(reader).map(GroupUuid),
+                        data.last_selected_group,
+                    )?;
                 }
                 kdb2::LAST_TOP_VISIBLE_GROUP_TAG => {
-                    data.last_top_visible_group = GroupUuid(xml::read_uuid(reader)?);
+                    data.last_top_visible_group = recovery.value(
+                        kdb2::LAST_TOP_VISIBLE_GROUP_TAG,
+                        xml::read_uuid(reader).map(GroupUuid),
+                        data.last_top_visible_group,
+                    )?;
                 }
                 kdb2::MAINTENANCE_HISTORY_DAYS_TAG => {
-                    data.maintenance_history_days = xml::read_i32(reader)?;
+                    data.maintenance_history_days = recovery.value(
+                        kdb2::MAINTENANCE_HISTORY_DAYS_TAG,
+                        xml::read_i32(reader),
+                        data.maintenance_history_days,
+                    )?;
                 }
                 kdb2::MASTER_KEY_CHANGE_FORCE_TAG => {
-                    data.master_key_change_force = xml::read_i32(reader)?;
+                    data.master_key_change_force = recovery.value(
+                        kdb2::MASTER_KEY_CHANGE_FORCE_TAG,
+                        xml::read_i32(reader),
+                        data.master_key_change_force,
+                    )?;
                 }
                 kdb2::MASTER_KEY_CHANGE_REC_TAG => {
-                    data.master_key_change_rec = xml::read_i32(reader)?;
+                    data.master_key_change_rec = recovery.value(
+                        kdb2::MASTER_KEY_CHANGE_REC_TAG,
+                        xml::read_i32(reader),
+                        data.master_key_change_rec,
+                    )?;
                 }
                 kdb2::MASTER_KEY_CHANGED_TAG => {
-                    data.master_key_changed = xml::read_datetime(reader)?;
+                    data.master_key_changed = recovery.value(
+                        kdb2::MASTER_KEY_CHANGED_TAG,
+                        xml::read_datetime(reader),
+                        data.master_key_changed,
+                    )?;
                 }
                 kdb2::MEMORY_PROTECTION_TAG => {
-                    read_memory_protection(reader, data)?;
+                    read_memory_protection(reader, data, recovery)?;
                 }
                 kdb2::RECYCLE_BIN_CHANGED_TAG => {
-                    data.recycle_bin_changed = xml::read_datetime(reader)?;
+                    data.recycle_bin_changed = recovery.value(
+                        kdb2::RECYCLE_BIN_CHANGED_TAG,
+                        xml::read_datetime(reader),
+                        data.recycle_bin_changed,
+                    )?;
                 }
                 kdb2::RECYCLE_BIN_ENABLED_TAG => {
-                    data.recycle_bin_enabled = xml::read_bool(reader)?;
+                    data.recycle_bin_enabled = recovery.value(
+                        kdb2::RECYCLE_BIN_ENABLED_TAG,
+                        xml::read_bool(reader),
+                        data.recycle_bin_enabled,
+                    )?;
                 }
                 kdb2::RECYCLE_BIN_UUID_TAG => {
-                    data.recycle_bin_uuid = GroupUuid(xml::read_uuid(reader)?);
+                    data.recycle_bin_uuid = recovery.value(
+                        kdb2::RECYCLE_BIN_UUID_TAG,
+                        xml::read_uuid(reader).map(GroupUuid),
+                        data.recycle_bin_uuid,
+                    )?;
+                }
+                kdb2::SETTINGS_CHANGED_TAG => {
+                    data.settings_changed = recovery.value(
+                        kdb2::SETTINGS_CHANGED_TAG,
+                        xml::read_datetime(reader).map(Some),
+                        data.settings_changed,
+                    )?;
                 }
                 _ => {}
             },
@@ -194,13 +324,17 @@ fn read_root<R: Read>(
     reader: &mut EventReader<R>,
     data: &mut XmlData,
     cipher: &mut Salsa20,
+    recovery: &mut Recovery,
 ) -> Result<()> {
     loop {
         let event = reader.next()?;
         match event {
             XmlEvent::StartElement { name, .. } => match name.local_name.as_str() {
+                kdb2::DELETED_OBJECTS_TAG => {
+                    data.deleted_objects = read_deleted_objects(reader)?;
+                }
                 kdb2::GROUP_TAG => {
-                    data.root_group = Some(read_group(reader, cipher, GroupUuid::nil())?);
+                    data.root_group = Some(read_group(reader, cipher, recovery, GroupUuid::nil())?);
                 }
                 _ => {}
             },
@@ -382,25 +516,108 @@ fn read_custom_icon<R: Read>(reader: &mut EventReader<R>) -> Result<(CustomIconU
     Ok((uuid, data))
 }
 
-fn read_memory_protection<R: Read>(reader: &mut EventReader<R>, data: &mut XmlData) -> Result<()> {
+fn read_deleted_objects<R: Read>(
+    reader: &mut EventReader<R>,
+) -> Result<Vec<(Uuid, DateTime<Utc>)>> {
+    let mut list = Vec::new();
+    loop {
+        let event = reader.next()?;
+        match event {
+            XmlEvent::StartElement { name, .. } if name.local_name == kdb2::DELETED_OBJECT_TAG => {
+                list.push(read_deleted_object(reader)?);
+            }
+
+            XmlEvent::EndElement { name, .. } if name.local_name == kdb2::DELETED_OBJECTS_TAG => {
+                break;
+            }
+
+            _ => {}
+        }
+    }
+
+    Ok(list)
+}
+
+fn read_deleted_object<R: Read>(reader: &mut EventReader<R>) -> Result<(Uuid, DateTime<Utc>)> {
+    let mut uuid: Option<Uuid> = None;
+    let mut time: Option<DateTime<Utc>> = None;
+    loop {
+        let event = reader.next()?;
+        match event {
+            XmlEvent::StartElement { name, .. } => match name.local_name.as_str() {
+                kdb2::DELETION_TIME_TAG => {
+                    time = Some(xml::read_datetime(reader)?);
+                }
+                kdb2::UUID_TAG => {
+                    uuid = xml::read_uuid_opt(reader)?;
+                }
+                _ => {}
+            },
+
+            XmlEvent::EndElement { name, .. } if name.local_name == kdb2::DELETED_OBJECT_TAG => {
+                break;
+            }
+
+            _ => {}
+        }
+    }
+
+    let uuid = match uuid {
+        Some(u) => u,
+        None => return xml::read_err(reader, "UUID element not found"),
+    };
+
+    let time = match time {
+        Some(t) => t,
+        None => return xml::read_err(reader, "DeletionTime element not found"),
+    };
+
+    Ok((uuid, time))
+}
+
+fn read_memory_protection<R: Read>(
+    reader: &mut EventReader<R>,
+    data: &mut XmlData,
+    recovery: &mut Recovery,
+) -> Result<()> {
     loop {
         let event = reader.next()?;
         match event {
             XmlEvent::StartElement { name, .. } => match name.local_name.as_str() {
                 kdb2::PROTECT_NOTES_TAG => {
-                    data.protect_notes = xml::read_bool(reader)?;
+                    data.protect_notes = recovery.value(
+                        kdb2::PROTECT_NOTES_TAG,
+                        xml::read_bool(reader),
+                        data.protect_notes,
+                    )?;
                 }
                 kdb2::PROTECT_PASSWORD_TAG => {
-                    data.protect_password = xml::read_bool(reader)?;
+                    data.protect_password = recovery.value(
+                        kdb2::PROTECT_PASSWORD_TAG,
+                        xml::read_bool(reader),
+                        data.protect_password,
+                    )?;
                 }
                 kdb2::PROTECT_TITLE_TAG => {
-                    data.protect_title = xml::read_bool(reader)?;
+                    data.protect_title = recovery.value(
+                        kdb2::PROTECT_TITLE_TAG,
+                        xml::read_bool(reader),
+                        data.protect_title,
+                    )?;
                 }
                 kdb2::PROTECT_URL_TAG => {
-                    data.protect_url = xml::read_bool(reader)?;
+                    data.protect_url = recovery.value(
+                        kdb2::PROTECT_URL_TAG,
+                        xml::read_bool(reader),
+                        data.protect_url,
+                    )?;
                 }
                 kdb2::PROTECT_USERNAME_TAG => {
-                    data.protect_username = xml::read_bool(reader)?;
+                    data.protect_username = recovery.value(
+                        kdb2::PROTECT_USERNAME_TAG,
+                        xml::read_bool(reader),
+                        data.protect_username,
+                    )?;
                 }
                 _ => {}
             },
@@ -421,6 +638,7 @@ fn read_memory_protection<R: Read>(reader: &mut EventReader<R>, data: &mut XmlDa
 fn read_group<R: Read>(
     reader: &mut EventReader<R>,
     cipher: &mut Salsa20,
+    recovery: &mut Recovery,
     parent: GroupUuid,
 ) -> Result<Group> {
     let mut node = Group::default();
@@ -430,37 +648,59 @@ fn read_group<R: Read>(
         match event {
             XmlEvent::StartElement { name, .. } => match name.local_name.as_str() {
                 kdb2::CUSTOM_ICON_UUID_TAG => {
-                    node.custom_icon_uuid = xml::read_custom_icon_uuid_opt(reader)?;
+                    node.custom_icon_uuid = recovery.value(
+                        kdb2::CUSTOM_ICON_UUID_TAG,
+                        xml::read_custom_icon_uuid_opt(reader),
+                        node.custom_icon_uuid,
+                    )?;
                 }
                 kdb2::DEFAULT_AUTO_TYPE_SEQUENCE_TAG => {
                     node.def_auto_type_sequence = xml::read_string(reader)?;
                 }
                 kdb2::ENABLE_AUTO_TYPE_TAG => {
-                    node.enable_auto_type = xml::read_bool_opt(reader)?;
+                    node.enable_auto_type = recovery.value(
+                        kdb2::ENABLE_AUTO_TYPE_TAG,
+                        xml::read_bool_opt(reader),
+                        node.enable_auto_type,
+                    )?;
                 }
                 kdb2::ENABLE_SEARCHING_TAG => {
-                    node.enable_searching = xml::read_bool_opt(reader)?;
+                    node.enable_searching = recovery.value(
+                        kdb2::ENABLE_SEARCHING_TAG,
+                        xml::read_bool_opt(reader),
+                        node.enable_searching,
+                    )?;
                 }
                 kdb2::ENTRY_TAG => {
                     node.entries.push(read_entry(
                         reader,
                         cipher,
+                        recovery,
                         EntryState::Active,
                         GroupUuid::nil(),
                     )?);
                 }
                 kdb2::GROUP_TAG => {
                     node.groups
-                        .push(read_group(reader, cipher, GroupUuid::nil())?);
+                        .push(read_group(reader, cipher, recovery, GroupUuid::nil())?);
                 }
                 kdb2::ICON_ID_TAG => {
-                    node.icon = xml::read_icon(reader)?;
+                    node.icon =
+                        recovery.value(kdb2::ICON_ID_TAG, xml::read_icon(reader), node.icon)?;
                 }
                 kdb2::IS_EXPANDED_TAG => {
-                    node.is_expanded = xml::read_bool(reader)?;
+                    node.is_expanded = recovery.value(
+                        kdb2::IS_EXPANDED_TAG,
+                        xml::read_bool(reader),
+                        node.is_expanded,
+                    )?;
                 }
                 kdb2::LAST_TOP_VISIBLE_ENTRY_TAG => {
-                    node.last_top_visible_entry = EntryUuid(xml::read_uuid(reader)?);
+                    node.last_top_visible_entry = recovery.value(
+                        kdb2::LAST_TOP_VISIBLE_ENTRY_TAG,
+                        xml::read_uuid(reader).map(EntryUuid),
+                        node.last_top_visible_entry,
+                    )?;
                 }
                 kdb2::NAME_TAG => {
                     node.name = xml::read_string(reader)?;
@@ -469,10 +709,14 @@ fn read_group<R: Read>(
                     node.notes = xml::read_string(reader)?;
                 }
                 kdb2::TIMES_TAG => {
-                    read_times(reader, &mut node)?;
+                    read_times(reader, &mut node, recovery)?;
                 }
                 kdb2::UUID_TAG => {
-                    node.uuid = GroupUuid(xml::read_uuid(reader)?);
+                    node.uuid = recovery.value(
+                        kdb2::UUID_TAG,
+                        xml::read_uuid(reader).map(GroupUuid),
+                        node.uuid,
+                    )?;
                 }
                 _ => {}
             },
@@ -491,6 +735,9 @@ fn read_group<R: Read>(
     let parent = node.uuid.clone();
     for entry in node.entries.iter_mut() {
         entry.parent = parent;
+        for old in entry.history.iter_mut() {
+            old.parent = parent;
+        }
     }
     for group in node.groups.iter_mut() {
         group.parent = parent;
@@ -502,60 +749,76 @@ fn read_group<R: Read>(
 fn read_entry<R: Read>(
     reader: &mut EventReader<R>,
     cipher: &mut Salsa20,
+    recovery: &mut Recovery,
     state: EntryState,
     parent: GroupUuid,
 ) -> Result<Entry> {
-    let mut node = Entry::default();
+    let mut node = Entry {
+        parent,
+        ..Entry::default()
+    };
     loop {
         let event = reader.next()?;
         match event {
-            XmlEvent::StartElement { name, .. } => {
-                node.parent = parent;
-                match name.local_name.as_str() {
-                    kdb2::AUTO_TYPE_TAG => {
-                        read_auto_type(reader, &mut node)?;
-                    }
-                    kdb2::BACKGROUND_COLOR_TAG => {
-                        node.background_color = xml::read_color_opt(reader)?;
-                    }
-                    kdb2::BINARY_TAG => {
-                        let (key, value) = read_binary(reader, cipher)?;
-                        node.binaries.insert(key, value);
-                    }
-                    kdb2::CUSTOM_ICON_UUID_TAG => {
-                        node.custom_icon_uuid = xml::read_custom_icon_uuid_opt(reader)?;
-                    }
-                    kdb2::FOREGROUND_COLOR_TAG => {
-                        node.foreground_color = xml::read_color_opt(reader)?;
-                    }
-                    kdb2::HISTORY_TAG => {
-                        if state == EntryState::Active {
-                            node.history
-                                .append(&mut read_history(reader, cipher, parent.clone())?);
-                        }
-                    }
-                    kdb2::ICON_ID_TAG => {
-                        node.icon = xml::read_icon(reader)?;
-                    }
-                    kdb2::OVERRIDE_URL_TAG => {
-                        node.override_url = xml::read_string(reader)?;
-                    }
-                    kdb2::STRING_TAG => {
-                        let (key, value) = read_string(reader, cipher)?;
-                        node.strings.insert(key, value);
-                    }
-                    kdb2::TAGS_TAG => {
-                        node.tags = xml::read_string(reader)?;
-                    }
-                    kdb2::TIMES_TAG => {
-                        read_times(reader, &mut node)?;
-                    }
-                    kdb2::UUID_TAG => {
-                        node.uuid = EntryUuid(xml::read_uuid(reader)?);
-                    }
-                    _ => {}
+            XmlEvent::StartElement { name, .. } => match name.local_name.as_str() {
+                kdb2::AUTO_TYPE_TAG => {
+                    read_auto_type(reader, &mut node, recovery)?;
                 }
-            }
+                kdb2::BACKGROUND_COLOR_TAG => {
+                    node.background_color = recovery.value(
+                        kdb2::BACKGROUND_COLOR_TAG,
+                        xml::read_color_opt(reader),
+                        node.background_color.clone(),
+                    )?;
+                }
+                kdb2::BINARY_TAG => {
+                    let (key, value) = read_binary(reader, cipher)?;
+                    node.binaries.insert(key, value);
+                }
+                kdb2::CUSTOM_ICON_UUID_TAG => {
+                    node.custom_icon_uuid = recovery.value(
+                        kdb2::CUSTOM_ICON_UUID_TAG,
+                        xml::read_custom_icon_uuid_opt(reader),
+                        node.custom_icon_uuid,
+                    )?;
+                }
+                kdb2::FOREGROUND_COLOR_TAG => {
+                    node.foreground_color = recovery.value(
+                        kdb2::FOREGROUND_COLOR_TAG,
+                        xml::read_color_opt(reader),
+                        node.foreground_color.clone(),
+                    )?;
+                }
+                kdb2::HISTORY_TAG if state == EntryState::Active => {
+                    node.history
+                        .append(&mut read_history(reader, cipher, recovery, parent)?);
+                }
+                kdb2::ICON_ID_TAG => {
+                    node.icon =
+                        recovery.value(kdb2::ICON_ID_TAG, xml::read_icon(reader), node.icon)?;
+                }
+                kdb2::OVERRIDE_URL_TAG => {
+                    node.override_url = xml::read_string(reader)?;
+                }
+                kdb2::STRING_TAG => {
+                    let (key, value) = read_string(reader, cipher)?;
+                    node.strings.insert(key, value);
+                }
+                kdb2::TAGS_TAG => {
+                    node.tags = xml::read_string(reader)?;
+                }
+                kdb2::TIMES_TAG => {
+                    read_times(reader, &mut node, recovery)?;
+                }
+                kdb2::UUID_TAG => {
+                    node.uuid = recovery.value(
+                        kdb2::UUID_TAG,
+                        xml::read_uuid(reader).map(EntryUuid),
+                        node.uuid,
+                    )?;
+                }
+                _ => {}
+            },
 
             XmlEvent::EndElement { name, .. } => {
                 if name.local_name == kdb2::ENTRY_TAG {
@@ -569,31 +832,39 @@ fn read_entry<R: Read>(
     Ok(node)
 }
 
-fn read_auto_type<R: Read>(reader: &mut EventReader<R>, node: &mut Entry) -> Result<()> {
+#[allow(deprecated)]
+fn read_auto_type<R: Read>(
+    reader: &mut EventReader<R>,
+    node: &mut Entry,
+    recovery: &mut Recovery,
+) -> Result<()> {
     loop {
         let event = reader.next()?;
         match event {
-            XmlEvent::StartElement { name, .. } =>
-            {
-                #[allow(unused_must_use)]
-                match name.local_name.as_str() {
-                    kdb2::ASSOCIATION_TAG => {
-                        read_association(reader)
-                            .map(|x| node.associations.push(x))
-                            .map_err(|err| eprintln!("{}", err));
-                    }
-                    kdb2::DATA_TRANSFER_OBFUSCATION_TAG => {
-                        node.auto_type_obfuscation = xml::read_obfuscation(reader)?;
-                    }
-                    kdb2::DEFAULT_SEQUENCE_TAG => {
-                        node.auto_type_def_sequence = xml::read_string(reader)?;
-                    }
-                    kdb2::ENABLED_TAG => {
-                        node.auto_type_enabled = xml::read_bool(reader)?;
-                    }
-                    _ => {}
+            XmlEvent::StartElement { name, .. } => match name.local_name.as_str() {
+                kdb2::ASSOCIATION_TAG => match read_association(reader) {
+                    Ok(association) => node.associations.push(association),
+                    Err(err) => recovery.dropped(kdb2::ASSOCIATION_TAG, err),
+                },
+                kdb2::DATA_TRANSFER_OBFUSCATION_TAG => {
+                    node.auto_type_obfuscation = recovery.value(
+                        kdb2::DATA_TRANSFER_OBFUSCATION_TAG,
+                        xml::read_obfuscation(reader),
+                        node.auto_type_obfuscation,
+                    )?;
+                }
+                kdb2::DEFAULT_SEQUENCE_TAG => {
+                    node.auto_type_def_sequence = xml::read_string(reader)?;
+                }
+                kdb2::ENABLED_TAG => {
+                    node.auto_type_enabled = recovery.value(
+                        kdb2::ENABLED_TAG,
+                        xml::read_bool(reader),
+                        node.auto_type_enabled,
+                    )?;
                 }
-            }
+                _ => {}
+            },
 
             XmlEvent::EndElement { name, .. } => {
                 if name.local_name == kdb2::AUTO_TYPE_TAG {
@@ -615,7 +886,8 @@ fn read_association<R: Read>(reader: &mut EventReader<R>) -> Result<Association>
         match event {
             XmlEvent::StartElement { name, .. } => match name.local_name.as_str() {
                 kdb2::KEYSTROKE_SEQUENCE_TAG => {
-                    keystroke = xml::read_string_opt(reader)?;
+                    // An empty sequence means the default sequence is used.
+                    keystroke = Some(xml::read_string(reader)?);
                 }
                 kdb2::WINDOW_TAG => {
                     window = xml::read_string_opt(reader)?;
@@ -696,6 +968,7 @@ fn read_binary<R: Read>(
 fn read_history<R: Read>(
     reader: &mut EventReader<R>,
     cipher: &mut Salsa20,
+    recovery: &mut Recovery,
     parent: GroupUuid,
 ) -> Result<Vec<Entry>> {
     let mut list = Vec::new();
@@ -704,7 +977,7 @@ fn read_history<R: Read>(
         match event {
             XmlEvent::StartElement { name, .. } => match name.local_name.as_str() {
                 kdb2::ENTRY_TAG => {
-                    list.push(read_entry(reader, cipher, EntryState::History, parent)?);
+                    list.push(read_entry(reader, cipher, recovery, EntryState::History, parent)?);
                 }
                 _ => {}
             },
@@ -766,35 +1039,82 @@ fn read_string<R: Read>(
     Ok((key, value))
 }
 
-fn read_times<N, R>(reader: &mut EventReader<R>, node: &mut N) -> Result<()>
+fn read_times<N, R>(
+    reader: &mut EventReader<R>,
+    node: &mut N,
+    recovery: &mut Recovery,
+) -> Result<()>
 where
     N: Times,
     R: Read,
 {
+    let mut created = false;
+    let mut accessed = false;
+    let mut modified = false;
+    let mut location_changed = false;
     loop {
         let event = reader.next()?;
         match event {
             XmlEvent::StartElement { name, .. } => match name.local_name.as_str() {
                 kdb2::CREATION_TIME_TAG => {
-                    node.set_creation_time(xml::read_datetime(reader)?);
+                    let value = recovery.value(
+                        kdb2::CREATION_TIME_TAG,
+                        xml::read_datetime(reader),
+                        node.creation_time(),
+                    )?;
+                    node.set_creation_time(value);
+                    created = true;
                 }
                 kdb2::EXPIRY_TIME_TAG => {
-                    node.set_expiry_time(xml::read_datetime(reader)?);
+                    let value = recovery.value(
+                        kdb2::EXPIRY_TIME_TAG,
+                        xml::read_datetime(reader),
+                        node.expiry_time(),
+                    )?;
+                    node.set_expiry_time(value);
                 }
                 kdb2::EXPIRES_TAG => {
-                    node.set_expires(xml::read_bool(reader)?);
+                    let value = recovery.value(
+                        kdb2::EXPIRES_TAG,
+                        xml::read_bool(reader),
+                        node.expires(),
+                    )?;
+                    node.set_expires(value);
                 }
                 kdb2::LAST_ACCESS_TIME_TAG => {
-                    node.set_last_accessed(xml::read_datetime(reader)?);
+                    let value = recovery.value(
+                        kdb2::LAST_ACCESS_TIME_TAG,
+                        xml::read_datetime(reader),
+                        node.last_accessed(),
+                    )?;
+                    node.set_last_accessed(value);
+                    accessed = true;
                 }
                 kdb2::LAST_MODIFICATION_TIME_TAG => {
-                    node.set_last_modified(xml::read_datetime(reader)?);
+                    let value = recovery.value(
+                        kdb2::LAST_MODIFICATION_TIME_TAG,
+                        xml::read_datetime(reader),
+                        node.last_modified(),
+                    )?;
+                    node.set_last_modified(value);
+                    modified = true;
                 }
                 kdb2::LOCATION_CHANGED_TAG => {
-                    node.set_location_changed(xml::read_datetime(reader)?);
+                    let value = recovery.value(
+                        kdb2::LOCATION_CHANGED_TAG,
+                        xml::read_datetime(reader),
+                        node.location_changed(),
+                    )?;
+                    node.set_location_changed(value);
+                    location_changed = true;
                 }
                 kdb2::USAGE_COUNT_TAG => {
-                    node.set_usage_count(xml::read_i32(reader)?);
+                    let value = recovery.value(
+                        kdb2::USAGE_COUNT_TAG,
+                        xml::read_i32(reader),
+                        node.usage_count(),
+                    )?;
+                    node.set_usage_count(value);
                 }
                 _ => {}
             },
@@ -809,6 +1129,23 @@ where
         }
     }
 
+    // Times that are missing (e.g. written by older or other tools) default
+    // to the creation time instead of the time of reading, so they don't
+    // change each time the file is opened and saved. A missing usage count
+    // stays zero and a missing expiry time means the node doesn't expire.
+    if created {
+        let creation_time = node.creation_time();
+        if !accessed {
+            node.set_last_accessed(creation_time);
+        }
+        if !modified {
+            node.set_last_modified(creation_time);
+        }
+        if !location_changed {
+            node.set_location_changed(creation_time);
+        }
+    }
+
     Ok(())
 }
 
@@ -834,3 +1171,95 @@ fn get_id_attr_value<R: Read>(
         None => xml::read_err(reader, "Attribute ID not found"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::format::kdb2_writer;
+    use crate::types::CompositeKey;
+    use crate::types::Database;
+    use crate::types::Icon;
+    use crate::types::ProtectedStreamKey;
+    use chrono::Duration;
+
+    #[test]
+    fn test_read_with_invalid_value_returns_error() {
+        let xml = invalid_icon_xml();
+        match read(&mut &xml[..], &stream_key(), false) {
+            Err(Error::XmlError(_)) => (),
+            _ => panic!("expected XML error"),
+        }
+    }
+
+    #[test]
+    fn test_read_lenient_with_invalid_value_uses_default() {
+        let xml = invalid_icon_xml();
+        let data = read(&mut &xml[..], &stream_key(), true).unwrap();
+        let root = data.root_group.unwrap();
+        assert_eq!(root.icon, Icon::Folder);
+        assert_eq!(root.name, "Root");
+        assert_eq!(data.recovered.len(), 1);
+        assert_eq!(data.recovered[0].element, kdb2::ICON_ID_TAG);
+    }
+
+    #[test]
+    fn test_read_lenient_with_valid_data_recovers_nothing() {
+        let db = Database::new(&CompositeKey::from_password("test"));
+        let mut xml = Vec::new();
+        kdb2_writer::write_plaintext_xml(&mut xml, &db).unwrap();
+        let data = read(&mut &xml[..], &stream_key(), true).unwrap();
+        assert_eq!(data.recovered, Vec::new());
+    }
+
+    #[test]
+    fn test_read_with_missing_times_uses_creation_time() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        let mut entry = Entry::new();
+        entry.usage_count = 7;
+        entry.creation_time -= Duration::days(30);
+        let created = entry.creation_time;
+        db.root_group.add_entry(entry);
+        let mut xml = Vec::new();
+        kdb2_writer::write_plaintext_xml(&mut xml, &db).unwrap();
+        let mut xml = String::from_utf8(xml).unwrap();
+        for tag in ["LastAccessTime", "LastModificationTime", "LocationChanged"] {
+            xml = remove_elements(&xml, tag);
+        }
+
+        let data = read(&mut xml.as_bytes(), &stream_key(), false).unwrap();
+        let entry = &data.root_group.unwrap().entries[0];
+        assert_eq!(entry.usage_count, 7);
+        assert_eq!(entry.creation_time, created);
+        assert_eq!(entry.last_accessed, created);
+        assert_eq!(entry.last_modified, created);
+        assert_eq!(entry.location_changed, created);
+    }
+
+    fn remove_elements(xml: &str, tag: &str) -> String {
+        let open = format!("<{}>", tag);
+        let close = format!("</{}>", tag);
+        let mut result = String::from(xml);
+        while let Some(start) = result.find(&open) {
+            let end = result[start..].find(&close).unwrap() + start + close.len();
+            result.replace_range(start..end, "");
+        }
+        result
+    }
+
+    fn invalid_icon_xml() -> Vec<u8> {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.root_group.name = String::from("Root");
+        db.root_group.icon = Icon::Key;
+        let mut xml = Vec::new();
+        kdb2_writer::write_plaintext_xml(&mut xml, &db).unwrap();
+        String::from_utf8(xml)
+            .unwrap()
+            .replacen("<IconID>0</IconID>", "<IconID>invalid</IconID>", 1)
+            .into_bytes()
+    }
+
+    fn stream_key() -> StreamKey {
+        StreamKey::new(&ProtectedStreamKey([0; 32]))
+    }
+}
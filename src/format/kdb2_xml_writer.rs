@@ -23,13 +23,18 @@ use crate::types::Entry;
 use crate::types::EntryState;
 use crate::types::Group;
 use crate::types::HeaderHash;
+use crate::types::ProtectedStreamKey;
 use crate::types::Result;
+use crate::types::SaveOptions;
 use crate::types::StreamKey;
 use crate::types::StringKey;
 use crate::types::StringValue;
 use crate::types::Times;
+use crate::types::Version;
+use chrono::{DateTime, Utc};
 use rust_xml::writer::{EmitterConfig, EventWriter, XmlEvent};
 use std::io::Write;
+use uuid::Uuid;
 
 /// Attempts to write the database's XML data to the writer.
 pub fn write<W: Write>(
@@ -37,20 +42,65 @@ pub fn write<W: Write>(
     db: &Database,
     hash: &HeaderHash,
     key: &StreamKey,
+    options: &SaveOptions,
 ) -> Result<()> {
     let mut cipher = salsa20::new_cipher(key);
     let config = EmitterConfig::new()
-        .perform_indent(true)
+        .perform_indent(options.indent)
         .indent_string("\t");
 
     {
         let mut writer = EventWriter::new_with_config(writer, config);
-        write_kee_pass_file_section(&mut writer, db, hash, &mut cipher)?;
+        write_kee_pass_file_section(&mut writer, db, hash, &mut cipher, options)?;
     }
 
     Ok(())
 }
 
+/// Attempts to write the database's XML data to the writer with all
+/// protected values written in cleartext.
+pub fn write_plaintext<W: Write>(writer: &mut W, db: &Database) -> Result<()> {
+    let mut db = db.clone();
+    for group in db.root_group.iter_mut() {
+        for entry in group.entries.iter_mut() {
+            unprotect_entry(entry);
+        }
+    }
+    let hash = HeaderHash(Vec::new());
+    let key = StreamKey::new(&ProtectedStreamKey([0u8; 32]));
+    write(writer, &db, &hash, &key, &SaveOptions::default())
+}
+
+fn unprotect_entry(entry: &mut Entry) {
+    for value in entry.strings.values_mut() {
+        if let StringValue::Protected(_) = *value {
+            *value = StringValue::Plain(value.reveal().into_owned());
+        }
+    }
+    for value in entry.binaries.values_mut() {
+        if let BinaryValue::Protected(ref sec) = *value {
+            *value = BinaryValue::Plain(sec.unsecure().to_vec());
+        }
+    }
+    for old in entry.history.iter_mut() {
+        unprotect_entry(old);
+    }
+}
+
+/// Returns the generator of the database, or the name of this library for
+/// databases without a generator or when the generator must be regenerated.
+fn generator<'a>(db: &'a Database, options: &SaveOptions) -> &'a str {
+    if db.generator.is_empty() || options.regenerate_generator {
+        common::GENERATOR_NAME
+    } else {
+        &db.generator
+    }
+}
+
+fn has_settings_changed(version: &Version) -> bool {
+    *version >= Version { major: 4, minor: 1 }
+}
+
 fn write_association_section<W: Write>(
     writer: &mut EventWriter<W>,
     assoc: &Association,
@@ -61,6 +111,7 @@ fn write_association_section<W: Write>(
     xml::write_end_tag(writer)
 }
 
+#[allow(deprecated)]
 fn write_auto_type_section<W: Write>(writer: &mut EventWriter<W>, entry: &Entry) -> Result<()> {
     xml::write_start_tag(writer, kdb2::AUTO_TYPE_TAG)?;
     xml::write_i32_tag(
@@ -98,8 +149,8 @@ fn write_binary_section<W: Write>(
             let tag = XmlEvent::start_element(kdb2::VALUE_TAG);
             let tag = tag.attr("Protected", "True");
             writer.write(tag)?;
-            let plain = sec.unsecure().to_vec();
-            let encrypted = salsa20::encrypt(cipher, &plain);
+            let mut encrypted = sec.unsecure().to_vec();
+            salsa20::encrypt_in_place(cipher, &mut encrypted, salsa20::CHUNK_SIZE);
             xml::write_binary(writer, encrypted.as_slice())?;
             xml::write_end_tag(writer)?;
         }
@@ -173,6 +224,20 @@ fn write_custom_icon_section<W: Write>(
     xml::write_end_tag(writer)
 }
 
+fn write_deleted_objects_section<W: Write>(
+    writer: &mut EventWriter<W>,
+    objects: &[(Uuid, DateTime<Utc>)],
+) -> Result<()> {
+    xml::write_start_tag(writer, kdb2::DELETED_OBJECTS_TAG)?;
+    for (uuid, time) in objects {
+        xml::write_start_tag(writer, kdb2::DELETED_OBJECT_TAG)?;
+        xml::write_uuid_tag(writer, kdb2::UUID_TAG, uuid)?;
+        xml::write_datetime_tag(writer, kdb2::DELETION_TIME_TAG, time)?;
+        xml::write_end_tag(writer)?;
+    }
+    xml::write_end_tag(writer)
+}
+
 fn write_entry_section<W: Write>(
     writer: &mut EventWriter<W>,
     cipher: &mut Salsa20,
@@ -253,9 +318,10 @@ fn write_kee_pass_file_section<W: Write>(
     db: &Database,
     hash: &HeaderHash,
     cipher: &mut Salsa20,
+    options: &SaveOptions,
 ) -> Result<()> {
     xml::write_start_tag(writer, kdb2::KEE_PASS_FILE_TAG)?;
-    write_meta_section(writer, db, hash)?;
+    write_meta_section(writer, db, hash, options)?;
     write_root_section(writer, db, cipher)?;
     xml::write_end_tag(writer)
 }
@@ -277,6 +343,7 @@ fn write_meta_section<W: Write>(
     writer: &mut EventWriter<W>,
     db: &Database,
     hash: &HeaderHash,
+    options: &SaveOptions,
 ) -> Result<()> {
     xml::write_start_tag(writer, kdb2::META_TAG)?;
     write_binaries_section(writer, &db.binaries)?;
@@ -299,7 +366,7 @@ fn write_meta_section<W: Write>(
         kdb2::ENTRY_TEMPLATES_GROUP_CHANGED_TAG,
         &db.entry_templates_group_changed,
     )?;
-    xml::write_string_tag(writer, kdb2::GENERATOR_TAG, &String::from(common::GENERATOR_NAME))?;
+    xml::write_string_tag(writer, kdb2::GENERATOR_TAG, &String::from(generator(db, options)))?;
     xml::write_binary_tag(writer, kdb2::HEADER_HASH_TAG, &hash.0)?;
     xml::write_i32_tag(writer, kdb2::HISTORY_MAX_ITEMS_TAG, db.history_max_items)?;
     xml::write_i32_tag(writer, kdb2::HISTORY_MAX_SIZE_TAG, db.history_max_size)?;
@@ -314,6 +381,11 @@ fn write_meta_section<W: Write>(
     xml::write_datetime_tag(writer, kdb2::RECYCLE_BIN_CHANGED_TAG, &db.recycle_bin_changed)?;
     xml::write_bool_tag(writer, kdb2::RECYCLE_BIN_ENABLED_TAG, db.recycle_bin_enabled)?;
     xml::write_uuid_tag(writer, kdb2::RECYCLE_BIN_UUID_TAG, &db.recycle_bin_uuid.0)?;
+    if let Some(ref time) = db.settings_changed {
+        if has_settings_changed(&db.version) {
+            xml::write_datetime_tag(writer, kdb2::SETTINGS_CHANGED_TAG, time)?;
+        }
+    }
     xml::write_end_tag(writer)
 }
 
@@ -324,6 +396,7 @@ fn write_root_section<W: Write>(
 ) -> Result<()> {
     xml::write_start_tag(writer, kdb2::ROOT_TAG)?;
     write_group_section(writer, cipher, &db.root_group)?;
+    write_deleted_objects_section(writer, &db.deleted_objects)?;
     xml::write_end_tag(writer)
 }
 
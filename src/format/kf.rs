@@ -8,12 +8,6 @@
 
 //! Module containing constants for the key file.
 
-/// The length of a binary key file.
-pub const BINARY_KEY_FILE_LEN: usize = 32;
-
-/// The length of a hexadecimal key file.
-pub const HEX_KEY_FILE_LEN: usize = 64;
-
 /// The version of the XML key file.
 pub const XML_KEY_FILE_VERSION: &'static str = "1.00";
 
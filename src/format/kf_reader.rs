@@ -19,10 +19,10 @@ use std::io::{Cursor, Read};
 pub fn read<R: Read>(reader: &mut R) -> Result<KeyFile> {
     let mut data = Vec::new();
     reader.read_to_end(&mut data)?;
-    match data.len() {
-        kf::BINARY_KEY_FILE_LEN => read_binary(data),
-        kf::HEX_KEY_FILE_LEN => read_hex(data),
-        _ => read_xml(&mut Cursor::new(data)),
+    match KeyFileType::detect(&data) {
+        KeyFileType::Binary => read_binary(data),
+        KeyFileType::Hex => read_hex(data),
+        KeyFileType::Xml => read_xml(&mut Cursor::new(data)),
     }
 }
 
@@ -6,13 +6,24 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#[cfg(feature = "database")]
 pub mod kdb2_reader;
+#[cfg(feature = "database")]
 pub mod kdb2_writer;
+#[cfg(any(feature = "database", feature = "keyfile"))]
 pub mod kf_reader;
+#[cfg(any(feature = "database", feature = "keyfile"))]
 pub mod kf_writer;
 
+#[cfg(feature = "database")]
 mod kdb2;
+#[cfg(feature = "database")]
+mod kdb2_block_reader;
+#[cfg(feature = "database")]
 mod kdb2_xml_reader;
+#[cfg(feature = "database")]
 mod kdb2_xml_writer;
+#[cfg(any(feature = "database", feature = "keyfile"))]
 mod kf;
+#[cfg(any(feature = "database", feature = "keyfile"))]
 mod xml;
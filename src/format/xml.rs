@@ -8,24 +8,39 @@
 
 //! Module containing functions for reading and writing XML.
 
+#[cfg(feature = "database")]
 use crate::compression::gzip;
+#[cfg(feature = "database")]
 use crate::crypto::salsa20::{self, Salsa20};
+#[cfg(feature = "database")]
 use crate::types::BinaryId;
+#[cfg(feature = "database")]
 use crate::types::BinaryKey;
+#[cfg(feature = "database")]
 use crate::types::BinaryValue;
+#[cfg(feature = "database")]
 use crate::types::Color;
+#[cfg(feature = "database")]
 use crate::types::CustomIconUuid;
 use crate::types::Error;
+#[cfg(feature = "database")]
 use crate::types::Icon;
+#[cfg(feature = "database")]
 use crate::types::Obfuscation;
 use crate::types::Result;
+#[cfg(feature = "database")]
 use crate::types::StringKey;
+#[cfg(feature = "database")]
 use crate::types::StringValue;
 use base64::{engine::general_purpose, Engine as _};
+#[cfg(feature = "database")]
 use chrono::{DateTime, Utc};
+#[cfg(feature = "database")]
 use secstr::SecStr;
 use std::io::{Read, Write};
+#[cfg(feature = "database")]
 use uuid::Uuid;
+#[cfg(feature = "database")]
 use xml::attribute::OwnedAttribute;
 use xml::common::Position;
 use xml::reader::{self, EventReader};
@@ -40,6 +55,7 @@ pub fn read_binary<R: Read>(reader: &mut EventReader<R>) -> Result<Vec<u8>> {
 }
 
 /// Attempts to read an optional binary key.
+#[cfg(feature = "database")]
 pub fn read_binary_key_opt<R: Read>(reader: &mut EventReader<R>) -> Result<Option<BinaryKey>> {
     match read_string_opt(reader)? {
         Some(string) => Ok(Some(BinaryKey(string))),
@@ -59,6 +75,7 @@ pub fn read_binary_opt<R: Read>(reader: &mut EventReader<R>) -> Result<Option<Ve
 }
 
 /// Attempts to read an optional binary value.
+#[cfg(feature = "database")]
 pub fn read_binary_value_opt<R: Read>(
     reader: &mut EventReader<R>,
     cipher: &mut Salsa20,
@@ -69,10 +86,10 @@ pub fn read_binary_value_opt<R: Read>(
     match ref_value {
         Some(string) => Ok(Some(BinaryValue::Ref(BinaryId(string)))),
         None => match read_binary_opt(reader)? {
-            Some(bytes) => {
+            Some(mut bytes) => {
                 if protected {
-                    let pbytes = salsa20::decrypt(cipher, &bytes);
-                    let secstr = SecStr::new(pbytes);
+                    salsa20::decrypt_in_place(cipher, &mut bytes, salsa20::CHUNK_SIZE);
+                    let secstr = SecStr::new(bytes);
                     Ok(Some(BinaryValue::Protected(secstr)))
                 } else {
                     Ok(Some(BinaryValue::Plain(bytes)))
@@ -90,6 +107,7 @@ pub fn read_binary_value_opt<R: Read>(
 }
 
 /// Attempts to read a boolean.
+#[cfg(feature = "database")]
 pub fn read_bool<R: Read>(reader: &mut EventReader<R>) -> Result<bool> {
     match read_bool_opt(reader)? {
         Some(b) => Ok(b),
@@ -98,6 +116,7 @@ pub fn read_bool<R: Read>(reader: &mut EventReader<R>) -> Result<bool> {
 }
 
 /// Attempts to read an optional boolean.
+#[cfg(feature = "database")]
 pub fn read_bool_opt<R: Read>(reader: &mut EventReader<R>) -> Result<Option<bool>> {
     match read_string_opt(reader)? {
         Some(string) => match string.to_lowercase().as_str() {
@@ -111,6 +130,7 @@ pub fn read_bool_opt<R: Read>(reader: &mut EventReader<R>) -> Result<Option<bool
 }
 
 /// Attempts to read an optional color.
+#[cfg(feature = "database")]
 pub fn read_color_opt<R: Read>(reader: &mut EventReader<R>) -> Result<Option<Color>> {
     match read_string_opt(reader)? {
         Some(string) => match Color::from_hex_string(&string) {
@@ -122,6 +142,7 @@ pub fn read_color_opt<R: Read>(reader: &mut EventReader<R>) -> Result<Option<Col
 }
 
 /// Attempts to read an optional custom icon UUID.
+#[cfg(feature = "database")]
 pub fn read_custom_icon_uuid_opt<R: Read>(
     reader: &mut EventReader<R>,
 ) -> Result<Option<CustomIconUuid>> {
@@ -132,6 +153,7 @@ pub fn read_custom_icon_uuid_opt<R: Read>(
 }
 
 /// Attempts to read a date and time.
+#[cfg(feature = "database")]
 pub fn read_datetime<R: Read>(reader: &mut EventReader<R>) -> Result<DateTime<Utc>> {
     match read_string_opt(reader)? {
         Some(string) => match string.parse::<DateTime<Utc>>() {
@@ -155,6 +177,7 @@ where
 }
 
 /// Attempts to read GZip compressed binary data.
+#[cfg(feature = "database")]
 pub fn read_gzip<R: Read>(reader: &mut EventReader<R>) -> Result<Vec<u8>> {
     match read_binary_opt(reader)? {
         Some(bytes) => {
@@ -166,6 +189,7 @@ pub fn read_gzip<R: Read>(reader: &mut EventReader<R>) -> Result<Vec<u8>> {
 }
 
 /// Attempts to read an i32.
+#[cfg(feature = "database")]
 pub fn read_i32<R: Read>(reader: &mut EventReader<R>) -> Result<i32> {
     match read_i32_opt(reader)? {
         Some(num) => Ok(num),
@@ -174,6 +198,7 @@ pub fn read_i32<R: Read>(reader: &mut EventReader<R>) -> Result<i32> {
 }
 
 /// Attempts to read an optional i32.
+#[cfg(feature = "database")]
 pub fn read_i32_opt<R: Read>(reader: &mut EventReader<R>) -> Result<Option<i32>> {
     match read_string_opt(reader)? {
         Some(string) => match string.parse::<i32>() {
@@ -185,6 +210,7 @@ pub fn read_i32_opt<R: Read>(reader: &mut EventReader<R>) -> Result<Option<i32>>
 }
 
 /// Attempts to read an icon.
+#[cfg(feature = "database")]
 pub fn read_icon<R: Read>(reader: &mut EventReader<R>) -> Result<Icon> {
     match read_i32_opt(reader)? {
         Some(num) => match Icon::from_i32(num) {
@@ -196,6 +222,7 @@ pub fn read_icon<R: Read>(reader: &mut EventReader<R>) -> Result<Icon> {
 }
 
 /// Attempts to read an obfuscation type.
+#[cfg(feature = "database")]
 pub fn read_obfuscation<R: Read>(reader: &mut EventReader<R>) -> Result<Obfuscation> {
     match read_i32_opt(reader)? {
         Some(num) => match Obfuscation::from_i32(num) {
@@ -215,6 +242,7 @@ pub fn read_string<R: Read>(reader: &mut EventReader<R>) -> Result<String> {
 }
 
 /// Attempts to read an optional string key
+#[cfg(feature = "database")]
 pub fn read_string_key_opt<R: Read>(reader: &mut EventReader<R>) -> Result<Option<StringKey>> {
     match read_string_opt(reader)? {
         Some(string) => Ok(Some(StringKey::from_string(&string))),
@@ -240,6 +268,7 @@ pub fn read_string_opt<R: Read>(reader: &mut EventReader<R>) -> Result<Option<St
 }
 
 /// Attempts to read an optional string value.
+#[cfg(feature = "database")]
 pub fn read_string_value_opt<R: Read>(
     reader: &mut EventReader<R>,
     cipher: &mut Salsa20,
@@ -268,6 +297,7 @@ pub fn read_string_value_opt<R: Read>(
 }
 
 /// Attempts to read a UUID.
+#[cfg(feature = "database")]
 pub fn read_uuid<R: Read>(reader: &mut EventReader<R>) -> Result<Uuid> {
     match read_uuid_opt(reader)? {
         Some(uuid) => Ok(uuid),
@@ -276,6 +306,7 @@ pub fn read_uuid<R: Read>(reader: &mut EventReader<R>) -> Result<Uuid> {
 }
 
 /// Attempts to read an optional UUID.
+#[cfg(feature = "database")]
 pub fn read_uuid_opt<R: Read>(reader: &mut EventReader<R>) -> Result<Option<Uuid>> {
     match read_binary_opt(reader)? {
         Some(bytes) => match Uuid::from_slice(bytes.as_slice()) {
@@ -287,6 +318,7 @@ pub fn read_uuid_opt<R: Read>(reader: &mut EventReader<R>) -> Result<Option<Uuid
 }
 
 /// Searches the specified attribute in the attributes and returns the value if found.
+#[cfg(feature = "database")]
 pub fn search_attr_value(attrs: &Vec<OwnedAttribute>, name: &str) -> Option<String> {
     for attr in attrs {
         if attr.name.local_name.to_lowercase() == name.to_lowercase() {
@@ -297,6 +329,7 @@ pub fn search_attr_value(attrs: &Vec<OwnedAttribute>, name: &str) -> Option<Stri
 }
 
 /// Attempts to write binary data.
+#[cfg(feature = "database")]
 pub fn write_binary<W: Write>(writer: &mut EventWriter<W>, data: &[u8]) -> Result<()> {
     write_string(writer, &general_purpose::STANDARD.encode(&data))
 }
@@ -311,6 +344,7 @@ pub fn write_binary_tag<W: Write>(
 }
 
 /// Attempts to write a tag that contains optional boolean data.
+#[cfg(feature = "database")]
 pub fn write_bool_opt_tag<W: Write>(
     writer: &mut EventWriter<W>,
     tag: &str,
@@ -324,11 +358,13 @@ pub fn write_bool_opt_tag<W: Write>(
 }
 
 /// Attempts to write a tag that contains boolean data.
+#[cfg(feature = "database")]
 pub fn write_bool_tag<W: Write>(writer: &mut EventWriter<W>, tag: &str, value: bool) -> Result<()> {
     write_bool_opt_tag(writer, tag, &Some(value))
 }
 
 /// Attempts to write a tag that contains an optional color.
+#[cfg(feature = "database")]
 pub fn write_color_tag<W: Write>(
     writer: &mut EventWriter<W>,
     tag: &str,
@@ -341,6 +377,7 @@ pub fn write_color_tag<W: Write>(
 }
 
 /// Attempts to write a tag that contains an optional custom icon UUID.
+#[cfg(feature = "database")]
 pub fn write_custom_icon_uuid_tag<W: Write>(
     writer: &mut EventWriter<W>,
     tag: &str,
@@ -353,6 +390,7 @@ pub fn write_custom_icon_uuid_tag<W: Write>(
 }
 
 /// Attempts to write a tag that contains a date and time.
+#[cfg(feature = "database")]
 pub fn write_datetime_tag<W: Write>(
     writer: &mut EventWriter<W>,
     tag: &str,
@@ -368,17 +406,20 @@ pub fn write_end_tag<W: Write>(writer: &mut EventWriter<W>) -> Result<()> {
 }
 
 /// Attempts to write GZip compressed data.
+#[cfg(feature = "database")]
 pub fn write_gzip<W: Write>(writer: &mut EventWriter<W>, data: &[u8]) -> Result<()> {
     let compressed = gzip::encode(data)?;
     write_binary(writer, &compressed)
 }
 
 /// Attempts to write a tag that contains an i32.
+#[cfg(feature = "database")]
 pub fn write_i32_tag<W: Write>(writer: &mut EventWriter<W>, tag: &str, value: i32) -> Result<()> {
     write_string_tag(writer, tag, &format!("{}", value))
 }
 
 /// Attempts to write a tag that contains no data.
+#[cfg(feature = "database")]
 pub fn write_null_tag<W: Write>(writer: &mut EventWriter<W>, tag: &str) -> Result<()> {
     write_start_tag(writer, tag)?;
     write_end_tag(writer)?;
@@ -410,10 +451,12 @@ pub fn write_string_tag<W: Write>(
 }
 
 /// Attempts to write a tag that contains a UUID.
+#[cfg(feature = "database")]
 pub fn write_uuid_tag<W: Write>(writer: &mut EventWriter<W>, tag: &str, uuid: &Uuid) -> Result<()> {
     write_binary_tag(writer, tag, uuid.as_bytes())
 }
 
+#[cfg(feature = "database")]
 fn get_protect_in_memory_attr_value<R: Read>(
     reader: &mut EventReader<R>,
     attrs: &Vec<OwnedAttribute>,
@@ -427,6 +470,7 @@ fn get_protect_in_memory_attr_value<R: Read>(
     }
 }
 
+#[cfg(feature = "database")]
 fn get_protected_attr_value<R: Read>(
     reader: &mut EventReader<R>,
     attrs: &Vec<OwnedAttribute>,
@@ -0,0 +1,71 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::rust_crypto::digest::Digest;
+use crate::rust_crypto::sha2::Sha256;
+use std::io::{Result, Write};
+
+/// A writer that counts and hashes the written data.
+pub struct HashWriter<W> {
+    inner: W,
+    hasher: Sha256,
+    size: u64,
+}
+
+impl<W: Write> HashWriter<W> {
+    /// Create a new hashing writer.
+    pub fn new(inner: W) -> HashWriter<W> {
+        HashWriter {
+            inner,
+            hasher: Sha256::new(),
+            size: 0,
+        }
+    }
+
+    /// Returns the number of bytes written and the SHA-256 of those bytes.
+    pub fn finish(mut self) -> (u64, [u8; 32]) {
+        let mut hash = [0u8; 32];
+        self.hasher.result(&mut hash);
+        (self.size, hash)
+    }
+}
+
+impl<W: Write> Write for HashWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let size = self.inner.write(buf)?;
+        self.hasher.input(&buf[..size]);
+        self.size += size as u64;
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::crypto::sha256;
+    use std::io::Write;
+
+    #[test]
+    fn test_finish_returns_size_and_hash_of_written_data() {
+        let mut out = Vec::new();
+        {
+            let mut target = HashWriter::new(&mut out);
+            target.write_all(&[0, 1, 2]).unwrap();
+            target.write_all(&[3]).unwrap();
+            let (size, hash) = target.finish();
+            assert_eq!(size, 4);
+            assert_eq!(hash, sha256::hash(&[&[0, 1, 2, 3]]));
+        }
+        assert_eq!(out, vec![0, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::io::{Error, ErrorKind, Read, Result};
+
+pub struct LimitReader<R> {
+    inner: R,
+    limit: u64,
+    count: u64,
+}
+
+impl<R: Read> LimitReader<R> {
+    /// Create a new reader that fails when more than limit bytes are read.
+    pub fn new(inner: R, limit: u64) -> LimitReader<R> {
+        LimitReader {
+            inner,
+            limit,
+            count: 0,
+        }
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns whether the limit has been exceeded.
+    pub fn is_exceeded(&self) -> bool {
+        self.count > self.limit
+    }
+}
+
+impl<R: Read> Read for LimitReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.is_exceeded() {
+            return Err(Error::new(ErrorKind::InvalidData, "read limit exceeded"));
+        }
+        let size = self.inner.read(buf)?;
+        self.count += size as u64;
+        if self.is_exceeded() {
+            return Err(Error::new(ErrorKind::InvalidData, "read limit exceeded"));
+        }
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_read_within_limit_returns_data() {
+        let mut reader = LimitReader::new(&b"data"[..], 4);
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).unwrap();
+        assert_eq!(data, b"data");
+        assert!(!reader.is_exceeded());
+    }
+
+    #[test]
+    fn test_read_beyond_limit_returns_error() {
+        let mut reader = LimitReader::new(&b"data"[..], 3);
+        let mut data = Vec::new();
+        assert!(reader.read_to_end(&mut data).is_err());
+        assert!(reader.is_exceeded());
+    }
+}
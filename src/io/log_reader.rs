@@ -7,21 +7,30 @@
 // except according to those terms.
 
 use super::log::Log;
-use std::io::{Read, Result};
+use std::io::{Error, Read, Result, Seek, SeekFrom};
 
 /// A reader that logs the read data.
 pub struct LogReader<R> {
     inner: R,
     is_logging: bool,
+    limit: usize,
     log_data: Vec<u8>,
 }
 
 impl<R: Read> LogReader<R> {
     /// Create a new logging reader.
+    #[cfg(test)]
     pub fn new(inner: R) -> LogReader<R> {
+        LogReader::with_limit(inner, usize::MAX)
+    }
+
+    /// Create a new logging reader that fails when more than limit bytes
+    /// would be logged.
+    pub fn with_limit(inner: R, limit: usize) -> LogReader<R> {
         LogReader {
             inner: inner,
             is_logging: true,
+            limit,
             log_data: Vec::new(),
         }
     }
@@ -47,11 +56,20 @@ impl<R> Log for LogReader<R> {
 
 impl<R: Read> Read for LogReader<R> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
-        let result = self.inner.read(buf);
+        let size = self.inner.read(buf)?;
         if self.is_logging {
-            self.log_data.extend_from_slice(&buf);
+            if self.log_data.len() + size > self.limit {
+                return Err(Error::other("log limit exceeded"));
+            }
+            self.log_data.extend_from_slice(&buf[..size]);
         }
-        result
+        Ok(size)
+    }
+}
+
+impl<R: Seek> Seek for LogReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
     }
 }
 
@@ -73,6 +91,27 @@ mod tests {
         assert_eq!(target.logged(), &Vec::<u8>::new());
     }
 
+    #[test]
+    fn test_read_beyond_limit_returns_error() {
+        let vec = vec![0, 1, 2, 3, 4, 5, 6, 7];
+        let mut target = LogReader::with_limit(Cursor::new(vec), 6);
+        let mut buffer = vec![0; 4];
+        target.read(&mut buffer).unwrap();
+        assert!(target.read(&mut buffer).is_err());
+
+        target.stop();
+        assert!(target.read(&mut buffer).is_ok());
+        assert_eq!(target.logged(), &vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_read_with_short_input_logs_read_bytes_only() {
+        let mut target = LogReader::new(Cursor::new(vec![0, 1]));
+        let mut buffer = vec![9; 4];
+        assert_eq!(target.read(&mut buffer).unwrap(), 2);
+        assert_eq!(target.logged(), &vec![0, 1]);
+    }
+
     #[test]
     fn test_logged_returns_correct_data() {
         let mut target = new_log_reader();
@@ -98,6 +137,25 @@ mod tests {
         assert_eq!(target.logged(), &vec![0, 1, 2, 3, 8, 9, 10, 11]);
     }
 
+    #[test]
+    fn test_read_with_partial_reads_logs_exact_data() {
+        let source = PartialReader(Cursor::new(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]));
+        let mut target = LogReader::new(source);
+        let mut buffer = vec![0; 7];
+        target.read_exact(&mut buffer).unwrap();
+        assert_eq!(buffer, vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(target.logged(), &vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    struct PartialReader(Cursor<Vec<u8>>);
+
+    impl Read for PartialReader {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            let size = buf.len().min(3);
+            self.0.read(&mut buf[..size])
+        }
+    }
+
     fn new_log_reader() -> LogReader<Cursor<Vec<u8>>> {
         let vec = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
         let source = Cursor::new(vec);
@@ -6,10 +6,14 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+pub use self::hash_writer::HashWriter;
+pub use self::limit_reader::LimitReader;
 pub use self::log::Log;
 pub use self::log_reader::LogReader;
 pub use self::log_writer::LogWriter;
 
+mod hash_writer;
+mod limit_reader;
 mod log;
 mod log_reader;
 mod log_writer;
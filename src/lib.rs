@@ -28,6 +28,8 @@
 //! Create a new database adding two groups and two entries:
 //!
 //! ```rust
+//! # #[cfg(feature = "database")]
+//! # {
 //! use kpdb::{CompositeKey, Database, Entry, Group};
 //!
 //! // Create a new database.
@@ -81,12 +83,15 @@
 //! assert_eq!(entry.password(), Some("mailpass"));
 //! assert_eq!(entry.url(), Some("https://mail.protonmail.com"));
 //! assert_eq!(entry.notes(), None);
+//! # }
 //! ```
 //!
 //! Open the existing KeePass database passwords.kdbx using the password
 //! "password", print it and save it to new.kdbx:
 //!
 //! ```rust,no_run
+//! # #[cfg(feature = "database")]
+//! # {
 //! use kpdb::{CompositeKey, Database};
 //! use std::fs::File;
 //!
@@ -98,12 +103,15 @@
 //!
 //! let mut file = File::create("new.kdbx").unwrap();
 //! db.save(&mut file).unwrap();
+//! # }
 //! ```
 //!
 //! Open the existing KeePass database passwords.kdbx using both the password
 //! "password" and the key file passwords.key, print it and save it to new.kdbx:
 //!
 //! ```rust,no_run
+//! # #[cfg(feature = "database")]
+//! # {
 //! use kpdb::{CompositeKey, Database, KeyFile};
 //! use std::fs::File;
 //!
@@ -118,6 +126,7 @@
 //!
 //! let mut file = File::create("new.kdbx").unwrap();
 //! db.save(&mut file).unwrap();
+//! # }
 //! ```
 //!
 //!
@@ -128,9 +137,15 @@
 //! - KeePass 1 databases.
 
 extern crate crypto as rust_crypto;
+#[cfg(any(feature = "database", feature = "keyfile"))]
 extern crate xml as rust_xml;
 
+pub use crate::capabilities::{supported_kdfs, supported_master_ciphers, supported_stream_ciphers};
 pub use crate::types::Association;
+#[cfg(feature = "database")]
+pub use crate::types::AutoType;
+#[cfg(feature = "database")]
+pub use crate::types::AutoTypeMut;
 pub use crate::types::BinariesMap;
 pub use crate::types::BinaryId;
 pub use crate::types::BinaryKey;
@@ -141,34 +156,81 @@ pub use crate::types::Compression;
 pub use crate::types::CustomDataMap;
 pub use crate::types::CustomIconUuid;
 pub use crate::types::CustomIconsMap;
+#[cfg(feature = "database")]
 pub use crate::types::Database;
+#[cfg(feature = "database")]
+pub use crate::types::DatabaseBuilder;
+#[cfg(feature = "database")]
+pub use crate::types::DatabaseStats;
 pub use crate::types::DbType;
+#[cfg(feature = "database")]
 pub use crate::types::Entry;
+pub use crate::types::EntryField;
 pub use crate::types::EntryUuid;
 pub use crate::types::Error;
+#[cfg(feature = "database")]
+pub use crate::types::FieldDiff;
+pub use crate::types::FieldEncoding;
+#[cfg(feature = "database")]
 pub use crate::types::Group;
 pub use crate::types::GroupUuid;
+pub use crate::types::IconCategory;
+pub use crate::types::Kdf;
+pub use crate::types::KdfParams;
+#[cfg(any(feature = "database", feature = "keyfile"))]
 pub use crate::types::KeyFile;
+#[cfg(any(feature = "database", feature = "keyfile"))]
 pub use crate::types::KeyFileType;
 pub use crate::types::MasterCipher;
+#[cfg(feature = "database")]
+pub use crate::types::MergePolicy;
+#[cfg(feature = "database")]
+pub use crate::types::OpenOptions;
+#[cfg(feature = "database")]
+pub use crate::types::PasswordIssue;
+pub use crate::types::ProtectedStreamKey;
+#[cfg(feature = "database")]
+pub use crate::types::ReadLimits;
+#[cfg(feature = "database")]
+pub use crate::types::RecoveredError;
 pub use crate::types::Result;
+#[cfg(feature = "database")]
+pub use crate::types::SaveOptions;
+#[cfg(feature = "database")]
+pub use crate::types::SaveReport;
+#[cfg(feature = "database")]
+pub use crate::types::SortField;
 pub use crate::types::StreamCipher;
+pub use crate::types::StreamKey;
 pub use crate::types::StringKey;
 pub use crate::types::StringValue;
 pub use crate::types::StringsMap;
+#[cfg(feature = "database")]
 pub use crate::types::Times;
 pub use crate::types::TransformRounds;
+pub use crate::types::TransformSeed;
+pub use crate::types::TransformedKey;
+#[cfg(feature = "database")]
+pub use crate::types::TreeVisitor;
+#[cfg(feature = "database")]
+pub use crate::types::ValidationIssue;
 pub use crate::types::Version;
+#[cfg(feature = "database")]
+pub use crate::types::WalkControl;
 pub use crate::types::{Color, ColorError};
 pub use crate::types::{Icon, IconError};
 pub use crate::types::{Obfuscation, ObfuscationError};
 
+mod capabilities;
 mod common;
+#[cfg(feature = "database")]
 mod compression;
 mod crypto;
 mod format;
+#[cfg(feature = "database")]
 mod io;
 mod types;
+#[cfg(feature = "database")]
 mod utils;
 
 #[cfg(test)]
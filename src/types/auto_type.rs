@@ -0,0 +1,181 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::association::Association;
+use super::obfuscation::Obfuscation;
+
+/// The auto-type settings of an entry.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AutoType {
+    /// Auto-type associations.
+    pub associations: Vec<Association>,
+
+    /// Default auto-type sequence.
+    pub def_sequence: String,
+
+    /// Whether auto-type is enabled.
+    pub enabled: bool,
+
+    /// The type of obfuscation to use for auto-typing.
+    pub obfuscation: Obfuscation,
+}
+
+impl AutoType {
+    /// Adds an association of the window with the keystroke sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::AutoType;
+    ///
+    /// let mut auto_type = AutoType::default();
+    /// auto_type
+    ///     .associate("Firefox - *", "{USERNAME}{TAB}{PASSWORD}{ENTER}")
+    ///     .associate("Chromium - *", "");
+    /// assert_eq!(auto_type.associations.len(), 2);
+    /// assert_eq!(auto_type.associations[1].window, "Chromium - *");
+    /// ```
+    pub fn associate<S, T>(&mut self, window: S, keystroke_sequence: T) -> &mut AutoType
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        self.associations.push(Association {
+            keystroke_sequence: keystroke_sequence.into(),
+            window: window.into(),
+        });
+        self
+    }
+}
+
+/// A mutable view of the auto-type settings of an entry.
+///
+/// Returned by `Entry::auto_type_mut`.
+#[derive(Debug, Eq, PartialEq)]
+pub struct AutoTypeMut<'a> {
+    /// Auto-type associations.
+    pub associations: &'a mut Vec<Association>,
+
+    /// Default auto-type sequence.
+    pub def_sequence: &'a mut String,
+
+    /// Whether auto-type is enabled.
+    pub enabled: &'a mut bool,
+
+    /// The type of obfuscation to use for auto-typing.
+    pub obfuscation: &'a mut Obfuscation,
+}
+
+impl<'a> AutoTypeMut<'a> {
+    /// Adds an association of the window with the keystroke sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Entry;
+    ///
+    /// let mut entry = Entry::new();
+    /// entry
+    ///     .auto_type_mut()
+    ///     .associate("Firefox - *", "{USERNAME}{TAB}{PASSWORD}{ENTER}")
+    ///     .associate("Chromium - *", "");
+    /// assert_eq!(entry.auto_type().associations.len(), 2);
+    /// ```
+    pub fn associate<S, T>(&mut self, window: S, keystroke_sequence: T) -> &mut AutoTypeMut<'a>
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        self.associations.push(Association {
+            keystroke_sequence: keystroke_sequence.into(),
+            window: window.into(),
+        });
+        self
+    }
+
+    /// Replaces all auto-type settings with the specified ones.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{AutoType, Entry};
+    ///
+    /// let mut auto_type = AutoType::default();
+    /// auto_type.enabled = false;
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.auto_type_mut().set(auto_type.clone());
+    /// assert_eq!(entry.auto_type(), auto_type);
+    /// ```
+    pub fn set(&mut self, auto_type: AutoType) {
+        *self.associations = auto_type.associations;
+        *self.def_sequence = auto_type.def_sequence;
+        *self.enabled = auto_type.enabled;
+        *self.obfuscation = auto_type.obfuscation;
+    }
+}
+
+impl Default for AutoType {
+    fn default() -> AutoType {
+        AutoType {
+            associations: Vec::new(),
+            def_sequence: String::new(),
+            enabled: true,
+            obfuscation: Obfuscation::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_default_returns_correct_instance() {
+        let auto_type = AutoType::default();
+        assert_eq!(auto_type.associations, Vec::new());
+        assert_eq!(auto_type.def_sequence, "");
+        assert!(auto_type.enabled);
+        assert_eq!(auto_type.obfuscation, Obfuscation::None);
+    }
+
+    #[test]
+    fn test_associate_appends_association() {
+        let mut auto_type = AutoType::default();
+        auto_type.associate("window", "{PASSWORD}");
+        let expected = Association {
+            keystroke_sequence: String::from("{PASSWORD}"),
+            window: String::from("window"),
+        };
+        assert_eq!(auto_type.associations, vec![expected]);
+    }
+
+    #[test]
+    fn test_auto_type_mut_set_replaces_all_settings() {
+        let mut associations = Vec::new();
+        let mut def_sequence = String::from("{PASSWORD}");
+        let mut enabled = true;
+        let mut obfuscation = Obfuscation::None;
+        let mut view = AutoTypeMut {
+            associations: &mut associations,
+            def_sequence: &mut def_sequence,
+            enabled: &mut enabled,
+            obfuscation: &mut obfuscation,
+        };
+        let mut auto_type = AutoType::default();
+        auto_type.associate("window", "{USERNAME}");
+        auto_type.enabled = false;
+        auto_type.obfuscation = Obfuscation::UseClipboard;
+        view.set(auto_type.clone());
+        assert_eq!(associations, auto_type.associations);
+        assert_eq!(def_sequence, "");
+        assert!(!enabled);
+        assert_eq!(obfuscation, Obfuscation::UseClipboard);
+    }
+}
@@ -7,7 +7,10 @@
 // except according to those terms.
 
 use super::binary_id::BinaryId;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// A type alias for the global map with binaries.
-pub type BinariesMap = HashMap<BinaryId, Vec<u8>>;
+///
+/// The map iterates in the order of its binary identifiers, so saving the same
+/// database produces the same output.
+pub type BinariesMap = BTreeMap<BinaryId, Vec<u8>>;
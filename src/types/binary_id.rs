@@ -9,3 +9,43 @@
 /// An identifier for binaries in the global binaries map.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub struct BinaryId(pub String);
+
+impl BinaryId {
+    /// Gets the numeric value of the identifier, if it's a number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::BinaryId;
+    ///
+    /// assert_eq!(BinaryId(String::from("3")).parse(), Some(3));
+    /// assert_eq!(BinaryId(String::from("a")).parse(), None);
+    /// ```
+    pub fn parse(&self) -> Option<u32> {
+        if self.0.is_empty() || !self.0.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        self.0.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_parse_with_number_returns_value() {
+        assert_eq!(BinaryId(String::from("0")).parse(), Some(0));
+        assert_eq!(BinaryId(String::from("42")).parse(), Some(42));
+    }
+
+    #[test]
+    fn test_parse_with_non_number_returns_none() {
+        assert_eq!(BinaryId(String::new()).parse(), None);
+        assert_eq!(BinaryId(String::from("+1")).parse(), None);
+        assert_eq!(BinaryId(String::from("-1")).parse(), None);
+        assert_eq!(BinaryId(String::from("id")).parse(), None);
+        assert_eq!(BinaryId(String::from("99999999999")).parse(), None);
+    }
+}
@@ -8,9 +8,10 @@
 
 use super::binary_id::BinaryId;
 use secstr::SecStr;
+use std::fmt;
 
 /// A value for entry's map with binaries.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub enum BinaryValue {
     /// Plain binary value.
     Plain(Vec<u8>),
@@ -21,3 +22,89 @@ pub enum BinaryValue {
     /// Reference to an item in the global binaries map.
     Ref(BinaryId),
 }
+
+impl BinaryValue {
+    /// Gets the bytes of a plain or protected binary value.
+    ///
+    /// Returns None for a reference, because resolving it requires the
+    /// database's global binaries map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{BinaryId, BinaryValue};
+    ///
+    /// let plain = BinaryValue::Plain(vec![1, 2, 3]);
+    /// assert_eq!(plain.as_bytes(), Some(vec![1, 2, 3]));
+    ///
+    /// let reference = BinaryValue::Ref(BinaryId(String::from("0")));
+    /// assert_eq!(reference.as_bytes(), None);
+    /// ```
+    pub fn as_bytes(&self) -> Option<Vec<u8>> {
+        match *self {
+            BinaryValue::Plain(ref bytes) => Some(bytes.clone()),
+            BinaryValue::Protected(ref secstr) => Some(secstr.unsecure().to_vec()),
+            BinaryValue::Ref(_) => None,
+        }
+    }
+
+    /// Gets whether this binary value is protected.
+    pub fn is_protected(&self) -> bool {
+        match *self {
+            BinaryValue::Protected(_) => true,
+            BinaryValue::Plain(_) | BinaryValue::Ref(_) => false,
+        }
+    }
+}
+
+impl fmt::Debug for BinaryValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BinaryValue::Plain(ref bytes) => f.debug_tuple("Plain").field(bytes).finish(),
+            BinaryValue::Protected(ref secstr) => f
+                .debug_tuple("Protected")
+                .field(&format_args!("<{} bytes>", secstr.unsecure().len()))
+                .finish(),
+            BinaryValue::Ref(ref id) => f.debug_tuple("Ref").field(id).finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::types::BinaryId;
+    use secstr::SecStr;
+
+    #[test]
+    fn test_as_bytes_with_plain_value_returns_bytes() {
+        let value = BinaryValue::Plain(vec![1, 2, 3]);
+        assert_eq!(value.as_bytes(), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_as_bytes_with_protected_value_returns_bytes() {
+        let value = BinaryValue::Protected(SecStr::new(vec![1, 2, 3]));
+        assert_eq!(value.as_bytes(), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_as_bytes_with_ref_value_returns_none() {
+        let value = BinaryValue::Ref(BinaryId(String::from("0")));
+        assert_eq!(value.as_bytes(), None);
+    }
+
+    #[test]
+    fn test_is_protected_returns_correct_value() {
+        assert!(!BinaryValue::Plain(vec![]).is_protected());
+        assert!(BinaryValue::Protected(SecStr::new(vec![])).is_protected());
+        assert!(!BinaryValue::Ref(BinaryId(String::from("0"))).is_protected());
+    }
+
+    #[test]
+    fn test_fmt_debug_with_protected_value_hides_value() {
+        let value = BinaryValue::Protected(SecStr::new(vec![1, 2, 3]));
+        assert_eq!(format!("{:?}", value), "Protected(<3 bytes>)");
+    }
+}
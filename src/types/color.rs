@@ -9,6 +9,7 @@
 use std::error;
 use std::fmt;
 use std::result::Result;
+use std::str::FromStr;
 
 const HEX_STRING_LENGTH: usize = 7;
 
@@ -26,6 +27,27 @@ pub struct Color {
 }
 
 impl Color {
+    /// Gets the WCAG contrast ratio between this color and the other color.
+    ///
+    /// The ratio ranges from 1 (no contrast) to 21 (black and white), WCAG
+    /// recommends a ratio of at least 4.5 for normal text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Color;
+    ///
+    /// let black = Color { red: 0, green: 0, blue: 0 };
+    /// let white = Color { red: 255, green: 255, blue: 255 };
+    /// assert_eq!(black.contrast_ratio(&white), 21.0);
+    /// ```
+    pub fn contrast_ratio(&self, other: &Color) -> f64 {
+        let a = self.luminance();
+        let b = other.luminance();
+        let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+        (lighter + 0.05) / (darker + 0.05)
+    }
+
     /// Attempts to create a color from an hex string.
     ///
     /// # Errors
@@ -65,6 +87,24 @@ impl Color {
         }
     }
 
+    /// Gets the relative luminance of the color as defined by WCAG.
+    ///
+    /// The luminance ranges from 0 (black) to 1 (white).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Color;
+    ///
+    /// let white = Color { red: 255, green: 255, blue: 255 };
+    /// assert_eq!(white.luminance(), 1.0);
+    /// ```
+    pub fn luminance(&self) -> f64 {
+        0.2126 * linear_channel(self.red)
+            + 0.7152 * linear_channel(self.green)
+            + 0.0722 * linear_channel(self.blue)
+    }
+
     /// Gets the hex string representation of the supplied color.
     ///
     /// # Examples
@@ -80,6 +120,20 @@ impl Color {
     }
 }
 
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_hex_string())
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorError;
+
+    fn from_str(s: &str) -> Result<Color, ColorError> {
+        Color::from_hex_string(s)
+    }
+}
+
 /// Error type for color conversion errors.
 #[derive(Debug, PartialEq)]
 pub enum ColorError {
@@ -139,31 +193,51 @@ impl error::Error for ColorError {
 }
 
 fn from_hex_string_blue(hex_str: &str) -> Result<u8, ColorError> {
-    match u8::from_str_radix(&hex_str[5..7], 16) {
+    match u8::from_str_radix(hex_str.get(5..7).unwrap_or(""), 16) {
         Ok(val) => Ok(val),
         Err(_) => Err(ColorError::InvalidBlueValue),
     }
 }
 
 fn from_hex_string_green(hex_str: &str) -> Result<u8, ColorError> {
-    match u8::from_str_radix(&hex_str[3..5], 16) {
+    match u8::from_str_radix(hex_str.get(3..5).unwrap_or(""), 16) {
         Ok(val) => Ok(val),
         Err(_) => Err(ColorError::InvalidGreenValue),
     }
 }
 
 fn from_hex_string_red(hex_str: &str) -> Result<u8, ColorError> {
-    match u8::from_str_radix(&hex_str[1..3], 16) {
+    match u8::from_str_radix(hex_str.get(1..3).unwrap_or(""), 16) {
         Ok(val) => Ok(val),
         Err(_) => Err(ColorError::InvalidRedValue),
     }
 }
 
+fn linear_channel(value: u8) -> f64 {
+    let value = value as f64 / 255.0;
+    if value <= 0.03928 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_contrast_ratio_returns_wcag_values() {
+        let black = Color::from_hex_string("#000000").unwrap();
+        let white = Color::from_hex_string("#ffffff").unwrap();
+        let gray = Color::from_hex_string("#767676").unwrap();
+        assert_eq!(black.contrast_ratio(&white), 21.0);
+        assert_eq!(white.contrast_ratio(&black), 21.0);
+        assert_eq!(gray.contrast_ratio(&gray), 1.0);
+        assert!((gray.contrast_ratio(&white) - 4.54).abs() < 0.01);
+    }
+
     #[test]
     fn test_from_hex_string_without_hash_sign_returns_error() {
         let expected = Err(ColorError::HexStringNoHashSign);
@@ -178,6 +252,13 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_from_hex_string_with_multibyte_chars_returns_error() {
+        let expected = Err(ColorError::InvalidRedValue);
+        let actual = Color::from_hex_string("#1é2345");
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_from_hex_string_with_too_long_hex_string_returns_error() {
         let expected = Err(ColorError::HexStringTooLong);
@@ -220,6 +301,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_luminance_returns_wcag_values() {
+        let black = Color::from_hex_string("#000000").unwrap();
+        let white = Color::from_hex_string("#ffffff").unwrap();
+        let red = Color::from_hex_string("#ff0000").unwrap();
+        let gray = Color::from_hex_string("#808080").unwrap();
+        assert_eq!(black.luminance(), 0.0);
+        assert_eq!(white.luminance(), 1.0);
+        assert!((red.luminance() - 0.2126).abs() < 1e-9);
+        assert!((gray.luminance() - 0.2159).abs() < 0.0001);
+    }
+
     #[test]
     fn test_to_hex_string_with_valid_color_returns_hex_string() {
         for tuple in get_test_tuples() {
@@ -250,4 +343,32 @@ mod tests {
             ("#ffffff", 255, 255, 255),
         ]
     }
+
+    #[test]
+    fn test_fmt_returns_hex_string() {
+        let color = Color {
+            red: 171,
+            green: 205,
+            blue: 239,
+        };
+        assert_eq!(format!("{}", color), "#abcdef");
+    }
+
+    #[test]
+    fn test_from_str_returns_correct_color() {
+        let expected = Ok(Color {
+            red: 171,
+            green: 205,
+            blue: 239,
+        });
+        let actual = "#abcdef".parse::<Color>();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_from_str_with_invalid_string_returns_error() {
+        let expected = Err(ColorError::HexStringNoHashSign);
+        let actual = "abcdefg".parse::<Color>();
+        assert_eq!(actual, expected);
+    }
 }
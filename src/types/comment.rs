@@ -6,6 +6,74 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::convert::Infallible;
+use std::str::{self, FromStr};
+
 /// The binary comment header from the database file.
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct Comment(pub Vec<u8>);
+
+impl Comment {
+    /// Gets the raw bytes of the comment.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Gets the comment as text if it's valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Comment;
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(Comment::from_str("text").unwrap().as_str(), Some("text"));
+    /// assert_eq!(Comment(vec![0xff]).as_str(), None);
+    /// ```
+    pub fn as_str(&self) -> Option<&str> {
+        str::from_utf8(&self.0).ok()
+    }
+}
+
+impl FromStr for Comment {
+    type Err = Infallible;
+
+    /// Create a new comment from the text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Comment;
+    /// use std::str::FromStr;
+    ///
+    /// let comment = Comment::from_str("Exported by kpdb").unwrap();
+    /// assert_eq!(comment.as_bytes(), b"Exported by kpdb");
+    /// ```
+    fn from_str(text: &str) -> Result<Comment, Infallible> {
+        Ok(Comment(text.as_bytes().to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_from_str_returns_correct_instance() {
+        let comment = Comment::from_str("héllo").unwrap();
+        assert_eq!(comment, Comment("héllo".as_bytes().to_vec()));
+    }
+
+    #[test]
+    fn test_as_bytes_returns_correct_bytes() {
+        let comment = Comment(vec![1, 2, 3]);
+        assert_eq!(comment.as_bytes(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_as_str_returns_correct_value() {
+        assert_eq!(Comment::from_str("héllo").unwrap().as_str(), Some("héllo"));
+        assert_eq!(Comment(vec![0xc3]).as_str(), None);
+    }
+}
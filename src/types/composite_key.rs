@@ -6,20 +6,27 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use super::error::Error;
+use super::result::Result;
+#[cfg(any(feature = "database", feature = "keyfile"))]
 use super::KeyFile;
 use crate::crypto::sha256;
 use secstr::SecStr;
+use std::fmt;
 
 /// Composition of the user's key data.
 ///
 /// This data type uses secstr's `SecStr` to protect the key data. To
 /// retrieve the protected data use the `unsecure` method.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct CompositeKey(SecStr);
 
 impl CompositeKey {
     /// Create a composite key from both a password and a key file.
     ///
+    /// An empty password is still part of the key, so the result differs
+    /// from `from_key_file` with the same key file.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
@@ -34,6 +41,7 @@ impl CompositeKey {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(any(feature = "database", feature = "keyfile"))]
     pub fn from_both<S: Into<String>>(password: S, key_file: KeyFile) -> CompositeKey {
         let password = sha256::hash(&[&password.into().into_bytes()]);
         let combined = sha256::hash(&[&password, &key_file.key.unsecure()]);
@@ -56,6 +64,7 @@ impl CompositeKey {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(any(feature = "database", feature = "keyfile"))]
     pub fn from_key_file(key_file: KeyFile) -> CompositeKey {
         let combined = sha256::hash(&[&key_file.key.unsecure()]);
         CompositeKey::secure(combined)
@@ -63,6 +72,10 @@ impl CompositeKey {
 
     /// Create a composite key from a password.
     ///
+    /// Any password is accepted, including an empty one which results in a
+    /// database that is effectively unprotected. Use `from_password_checked`
+    /// to reject such passwords.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -76,7 +89,45 @@ impl CompositeKey {
         CompositeKey::secure(combined)
     }
 
+    /// Attempts to create a composite key from a password.
+    ///
+    /// Returns an error when the password is empty or consists of whitespace
+    /// only.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::CompositeKey;
+    ///
+    /// assert!(CompositeKey::from_password_checked("secret").is_ok());
+    /// assert!(CompositeKey::from_password_checked(" \t").is_err());
+    /// ```
+    pub fn from_password_checked<S: Into<String>>(password: S) -> Result<CompositeKey> {
+        let password = password.into();
+        if password.trim().is_empty() {
+            return Err(Error::EmptyPassword);
+        }
+        Ok(CompositeKey::from_password(password))
+    }
+
+    /// Checks whether this composite key is empty, which is only the case
+    /// for a database opened with `Database::open_with_master_key`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::CompositeKey;
+    ///
+    /// let key = CompositeKey::from_password("");
+    /// assert!(!key.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.0.unsecure().is_empty()
+    }
+
     /// Gets the protected data from this composite key.
+    ///
+    /// Use `try_unsecure` to detect protected data with an invalid length.
     pub fn unsecure(&self) -> [u8; 32] {
         let unsecure = self.0.unsecure();
         let mut array = [0u8; 32];
@@ -86,18 +137,41 @@ impl CompositeKey {
         array
     }
 
+    /// Attempts to get the protected data from this composite key.
+    ///
+    /// Returns an error when the protected data isn't exactly 32 bytes.
+    pub fn try_unsecure(&self) -> Result<[u8; 32]> {
+        let unsecure = self.0.unsecure();
+        <[u8; 32]>::try_from(unsecure).map_err(|_| Error::InvalidKeyLength(unsecure.len()))
+    }
+
+    /// Create an empty composite key, which can't be used to save a
+    /// database.
+    #[cfg(feature = "database")]
+    pub(crate) fn empty() -> CompositeKey {
+        CompositeKey(SecStr::new(Vec::new()))
+    }
+
     fn secure(key: [u8; 32]) -> CompositeKey {
         CompositeKey(SecStr::new(key.to_vec()))
     }
 }
 
+impl fmt::Debug for CompositeKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CompositeKey(<{} bytes>)", self.0.unsecure().len())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    #[cfg(any(feature = "database", feature = "keyfile"))]
     use crate::types::{KeyFile, KeyFileType};
     use secstr::SecStr;
 
+    #[cfg(any(feature = "database", feature = "keyfile"))]
     #[test]
     fn test_from_both_returns_correct_instance() {
         let array = [
@@ -114,6 +188,7 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[cfg(any(feature = "database", feature = "keyfile"))]
     #[test]
     fn test_from_key_file_returns_correct_instance() {
         let array = [
@@ -141,6 +216,35 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_from_password_checked_with_empty_or_whitespace_returns_error() {
+        for password in ["", " ", "\t\n"].iter() {
+            match CompositeKey::from_password_checked(*password) {
+                Err(Error::EmptyPassword) => (),
+                _ => panic!("expected EmptyPassword error"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_password_checked_returns_same_key_as_from_password() {
+        let expected = CompositeKey::from_password(" secret ");
+        let actual = CompositeKey::from_password_checked(" secret ").unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(any(feature = "database", feature = "keyfile"))]
+    #[test]
+    fn test_from_both_with_empty_password_differs_from_key_file() {
+        let key = KeyFile {
+            key: SecStr::new(vec![0x70, 0x61, 0x73, 0x73]),
+            file_type: KeyFileType::Binary,
+        };
+        let both = CompositeKey::from_both("", key.clone());
+        assert!(both != CompositeKey::from_key_file(key));
+        assert!(both != CompositeKey::from_password(""));
+    }
+
     #[test]
     fn test_unsecure_inverses_secure() {
         let array = [
@@ -151,4 +255,28 @@ mod tests {
         let actual = CompositeKey::unsecure(&CompositeKey::secure(array));
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_fmt_debug_does_not_contain_key_bytes() {
+        let key = CompositeKey::from_password("secret");
+        let debug = format!("{:?}", key);
+        assert_eq!(debug, "CompositeKey(<32 bytes>)");
+        assert!(!debug.contains(&format!("{:?}", key.unsecure())));
+        assert!(!debug.contains(&hex::encode(key.unsecure())));
+    }
+
+    #[test]
+    fn test_try_unsecure_returns_correct_array() {
+        let target = CompositeKey::secure([7u8; 32]);
+        assert_eq!(target.try_unsecure().unwrap(), [7u8; 32]);
+    }
+
+    #[test]
+    fn test_try_unsecure_with_wrong_length_returns_error() {
+        let target = CompositeKey(SecStr::new(vec![7u8; 16]));
+        match target.try_unsecure() {
+            Err(Error::InvalidKeyLength(16)) => (),
+            _ => panic!("expected InvalidKeyLength error"),
+        }
+    }
 }
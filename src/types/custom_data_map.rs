@@ -6,7 +6,10 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// A type alias for a map with custom data.
-pub type CustomDataMap = HashMap<String, String>;
+///
+/// The map iterates in the order of its keys, so saving the same database
+/// produces the same output.
+pub type CustomDataMap = BTreeMap<String, String>;
@@ -9,7 +9,7 @@
 use uuid::Uuid;
 
 /// The identifier for a custom icon.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub struct CustomIconUuid(pub Uuid);
 
 impl CustomIconUuid {
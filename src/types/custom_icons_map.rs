@@ -7,7 +7,10 @@
 // except according to those terms.
 
 use super::custom_icon_uuid::CustomIconUuid;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// A type alias for a map with custom icons.
-pub type CustomIconsMap = HashMap<CustomIconUuid, Vec<u8>>;
+///
+/// The map iterates in the order of its icon identifiers, so saving the same
+/// database produces the same output.
+pub type CustomIconsMap = BTreeMap<CustomIconUuid, Vec<u8>>;
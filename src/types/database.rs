@@ -7,29 +7,60 @@
 // except according to those terms.
 
 use super::binaries_map::BinariesMap;
+use super::binary_id::BinaryId;
+use super::binary_key::BinaryKey;
+use super::binary_value::BinaryValue;
 use super::color::Color;
 use super::comment::Comment;
 use super::composite_key::CompositeKey;
 use super::compression::Compression;
 use super::custom_data_map::CustomDataMap;
+use super::custom_icon_uuid::CustomIconUuid;
 use super::custom_icons_map::CustomIconsMap;
+use super::database_stats::DatabaseStats;
 use super::db_type::DbType;
 use super::entry::Entry;
 use super::entry_uuid::EntryUuid;
 use super::error::Error;
 use super::group::Group;
 use super::group_uuid::GroupUuid;
+use super::header_hash::HeaderHash;
+use super::kdf_params::KdfParams;
 use super::master_cipher::MasterCipher;
+use super::meta_data::MetaData;
+use super::open_options::OpenOptions;
+use super::password_issue::PasswordIssue;
+use super::protected_stream_key::ProtectedStreamKey;
+use super::recovered_error::RecoveredError;
 use super::result::Result;
+use super::save_options::SaveOptions;
+use super::save_report::SaveReport;
+use super::sort_field::SortField;
 use super::stream_cipher::StreamCipher;
+use super::stream_key::StreamKey;
+use super::string_key::StringKey;
 use super::string_value::StringValue;
+use super::times::Times;
 use super::transform_rounds::TransformRounds;
+use super::transformed_key::TransformedKey;
+use super::tree_visitor::TreeVisitor;
+use super::validation_issue::ValidationIssue;
 use super::version::Version;
+use super::walk_control::WalkControl;
+use super::xml_data::XmlData;
 use crate::common;
 use crate::format::{kdb2_reader, kdb2_writer};
-use crate::io::{Log, LogReader, LogWriter};
-use chrono::{DateTime, Utc};
-use std::io::{Read, Write};
+use crate::io::{HashWriter, Log, LogReader, LogWriter};
+use crate::utils;
+use chrono::{DateTime, Duration, Utc};
+use secstr::SecStr;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::io::{Read, Seek, Write};
+use std::mem;
+use uuid::Uuid;
+
+const REFERENCE_START: &str = "{REF:";
 
 /// The KeePass database.
 #[derive(Clone, Debug, PartialEq)]
@@ -59,6 +90,9 @@ pub struct Database {
     pub version: Version,
 
     /// Map with binary data.
+    ///
+    /// This is the logical binary pool of the database, the version specific
+    /// writer decides where it's stored in the file.
     pub binaries: BinariesMap,
 
     /// Optional color.
@@ -76,6 +110,9 @@ pub struct Database {
     /// The date and time the default username was changed.
     pub def_username_changed: DateTime<Utc>,
 
+    /// Identifiers of deleted entries and groups with their deletion time.
+    pub deleted_objects: Vec<(Uuid, DateTime<Utc>)>,
+
     /// Description of this database.
     pub description: String,
 
@@ -88,7 +125,7 @@ pub struct Database {
     /// The identifier of the group containing entry templates.
     pub entry_templates_group_uuid: GroupUuid,
 
-    /// Name of the generator.
+    /// Name of the generator, saved as is (or "rust-kpdb" when empty).
     pub generator: String,
 
     /// Maximum number of history items.
@@ -145,6 +182,10 @@ pub struct Database {
 
     /// The root group.
     pub root_group: Group,
+
+    /// The date and time the database settings were changed, only stored in
+    /// KeePass 2 databases of version 4.1 and later.
+    pub settings_changed: Option<DateTime<Utc>>,
 }
 
 impl Database {
@@ -175,6 +216,7 @@ impl Database {
             custom_icons: CustomIconsMap::new(),
             def_username: String::new(),
             def_username_changed: now,
+            deleted_objects: Vec::new(),
             description: String::new(),
             description_changed: now,
             entry_templates_group_changed: now,
@@ -199,523 +241,4177 @@ impl Database {
             recycle_bin_enabled: common::RECYCLE_BIN_ENABLED_DEFAULT,
             recycle_bin_uuid: GroupUuid::nil(),
             root_group: Group::new(common::ROOT_GROUP_NAME),
+            settings_changed: None,
         }
     }
 
-    /// Returns a vector with entries that match (case insensitive) the supplied text.
+    /// Adds the custom icon data and returns its identifier.
+    ///
+    /// The identifier of the existing icon is returned when the same data
+    /// was already added.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use kpdb::{CompositeKey, Database, Entry, Group};
-    ///
-    /// let mut protonmail = Entry::new();
-    /// protonmail.set_title("ProtonMail");
-    /// protonmail.set_username("puser");
-    /// protonmail.set_password("ppass");
-    /// protonmail.set_url("https://mail.protonmail.com");
-    ///
-    /// let mut group = Group::new("Email");
-    /// group.add_entry(protonmail);
+    /// use kpdb::{CompositeKey, Database};
     ///
     /// let mut db = Database::new(&CompositeKey::from_password("test"));
-    /// db.root_group.add_group(group);
-    ///
-    /// let result = db.find_entries("Protonm");
-    /// assert_eq!(result.len(), 1);
-    ///
-    /// let result = db.find_entries("gmail");
-    /// assert_eq!(result.len(), 0);
+    /// let icon_uuid = db.add_custom_icon(vec![1, 2, 3]);
+    /// assert_eq!(db.add_custom_icon(vec![1, 2, 3]), icon_uuid);
+    /// assert_eq!(db.custom_icons.len(), 1);
     /// ```
-    pub fn find_entries<'a, S: Into<String>>(&'a self, text: S) -> Vec<&'a Entry> {
-        let mut list = Vec::new();
-        let text = &text.into().to_lowercase();
-        for group in self.root_group.iter() {
-            for entry in group.entries.iter() {
-                if entry_contains_string(entry, text) {
-                    list.push(entry);
-                }
-            }
+    pub fn add_custom_icon(&mut self, data: Vec<u8>) -> CustomIconUuid {
+        if let Some((uuid, _)) = self.custom_icons.iter().find(|(_, v)| **v == data) {
+            return *uuid;
         }
-        list
+        let uuid = CustomIconUuid::new_random();
+        self.custom_icons.insert(uuid, data);
+        uuid
     }
 
-    /// Returns a vector with mutable entries that match (case insensitive) the supplied text.
+    /// Returns an iterator over the history versions of all entries in the
+    /// database, paired with the current version of the entry.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use kpdb::{CompositeKey, Database, Entry, Group};
-    ///
-    /// let mut protonmail = Entry::new();
-    /// protonmail.set_title("ProtonMail");
-    /// protonmail.set_username("puser");
-    /// protonmail.set_password("ppass");
-    /// protonmail.set_url("https://mail.protonmail.com");
+    /// use kpdb::{CompositeKey, Database, Entry};
     ///
-    /// let mut group = Group::new("Email");
-    /// group.add_entry(protonmail);
+    /// let mut entry = Entry::new();
+    /// entry.set_password("old");
+    /// entry.history.push(entry.clone());
+    /// entry.set_password("new");
     ///
     /// let mut db = Database::new(&CompositeKey::from_password("test"));
-    /// db.root_group.add_group(group);
+    /// db.root_group.add_entry(entry);
     ///
-    /// let result = db.find_entries_mut("Protonm");
-    /// assert_eq!(result.len(), 1);
+    /// let passwords: Vec<_> = db
+    ///     .all_history()
+    ///     .map(|(current, old)| (current.password(), old.password()))
+    ///     .collect();
+    /// assert_eq!(passwords, vec![(Some("new"), Some("old"))]);
     /// ```
-    pub fn find_entries_mut<'a, S: Into<String>>(&'a mut self, text: S) -> Vec<&'a mut Entry> {
-        let mut list = Vec::new();
-        let text = &text.into().to_lowercase();
-        for group in self.root_group.iter_mut() {
-            for entry in group.entries.iter_mut() {
-                if entry_contains_string(entry, text) {
-                    list.push(entry);
-                }
-            }
-        }
-        list
+    pub fn all_history(&self) -> impl Iterator<Item = (&Entry, &Entry)> + '_ {
+        self.entries()
+            .flat_map(|entry| entry.history.iter().map(move |old| (entry, old)))
     }
 
-    /// Returns a vector with groups that match (case insensitive) the supplied name.
+    /// Returns whether the database contains an entry with the UUID.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use kpdb::{CompositeKey, Database, Group};
+    /// use kpdb::{CompositeKey, Database, Entry};
     ///
-    /// let group = Group::new("Email");
+    /// let entry = Entry::new();
+    /// let entry_uuid = entry.uuid;
     ///
     /// let mut db = Database::new(&CompositeKey::from_password("test"));
-    /// db.root_group.add_group(group);
-    ///
-    /// let result = db.find_groups("mail");
-    /// assert_eq!(result.len(), 1);
+    /// assert!(!db.contains_entry(entry_uuid));
     ///
-    /// let result = db.find_groups("unknown");
-    /// assert_eq!(result.len(), 0);
+    /// db.root_group.add_entry(entry);
+    /// assert!(db.contains_entry(entry_uuid));
     /// ```
-    pub fn find_groups<'a, S: Into<String>>(&'a self, name: S) -> Vec<&'a Group> {
-        let name = &name.into().to_lowercase();
+    pub fn contains_entry(&self, uuid: EntryUuid) -> bool {
         self.root_group
             .iter()
-            .filter(|g| g.name.to_lowercase().contains(name))
-            .collect::<Vec<&'a Group>>()
+            .any(|group| group.entries.iter().any(|entry| entry.uuid == uuid))
     }
 
-    /// Returns a vector with mutable groups that match (case insensitive) the supplied name.
+    /// Returns whether the database contains a group with the UUID.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use kpdb::{CompositeKey, Database, Group};
     ///
-    /// let group = Group::new("Email");
+    /// let group = Group::new("Group");
+    /// let group_uuid = group.uuid;
     ///
     /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// assert!(!db.contains_group(group_uuid));
+    ///
     /// db.root_group.add_group(group);
+    /// assert!(db.contains_group(group_uuid));
+    /// ```
+    pub fn contains_group(&self, uuid: GroupUuid) -> bool {
+        self.root_group.iter().any(|group| group.uuid == uuid)
+    }
+
+    /// Creates a new group inside the parent group and returns its
+    /// identifier.
     ///
-    /// let result = db.find_groups_mut("mail");
-    /// assert_eq!(result.len(), 1);
+    /// The parent of the new group is set and the new group inherits the
+    /// auto-type and search settings of the parent group (they are left as
+    /// `None`). Returns an error when the parent group doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database};
+    ///
+    /// # fn create_group_example() -> Result<()> {
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// let root_uuid = db.root_group.uuid;
+    ///
+    /// let group_uuid = db.create_group(root_uuid, "Email")?;
+    /// let group = db.get_group(group_uuid).unwrap();
+    /// assert_eq!(group.name, "Email");
+    /// assert_eq!(group.parent, root_uuid);
+    /// # Ok(())
+    /// # }
     /// ```
-    pub fn find_groups_mut<'a, S: Into<String>>(&'a mut self, name: S) -> Vec<&'a mut Group> {
-        let name = &name.into().to_lowercase();
-        self.root_group
-            .iter_mut()
-            .filter(|g| g.name.to_lowercase().contains(name))
-            .collect::<Vec<&'a mut Group>>()
+    pub fn create_group(&mut self, parent: GroupUuid, name: &str) -> Result<GroupUuid> {
+        match self.get_group_mut(parent) {
+            Some(parent_group) => {
+                let mut group = Group::new(name);
+                group.parent = parent;
+                let uuid = group.uuid;
+                parent_group.add_group(group);
+                Ok(uuid)
+            }
+            None => Err(Error::MissingGroup(parent)),
+        }
     }
 
-    /// Returns the entry that matches the UUID or None if not found.
+    /// Assigns new random identifiers to entries and groups whose identifier
+    /// is already used by another entry or group and returns the number of
+    /// changed identifiers.
+    ///
+    /// The first occurrence of an identifier keeps it. The history of an
+    /// entry and the parent of child nodes are updated accordingly.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use kpdb::{CompositeKey, Database, Entry, Group};
+    /// use kpdb::{CompositeKey, Database, Entry};
     ///
     /// let entry = Entry::new();
-    /// let entry_uuid = entry.uuid;
     ///
     /// let mut db = Database::new(&CompositeKey::from_password("test"));
-    /// assert_eq!(db.get_entry(entry_uuid), None);
-    ///
     /// db.root_group.add_entry(entry.clone());
-    /// assert_eq!(db.get_entry(entry_uuid), Some(&entry));
+    /// db.root_group.add_entry(entry.clone());
+    ///
+    /// assert_eq!(db.dedup_uuids(), 1);
+    /// assert_eq!(db.root_group.entries[0].uuid, entry.uuid);
+    /// assert!(db.root_group.entries[1].uuid != entry.uuid);
     /// ```
-    pub fn get_entry<'a>(&'a self, uuid: EntryUuid) -> Option<&'a Entry> {
-        for group in self.root_group.iter() {
-            for entry in group.entries.iter() {
-                if entry.uuid == uuid {
-                    return Some(entry);
+    pub fn dedup_uuids(&mut self) -> usize {
+        let mut count = 0;
+        let mut group_uuids = HashSet::new();
+        for group in self.root_group.iter_mut() {
+            if !group_uuids.insert(group.uuid) {
+                group.uuid = GroupUuid::new_random();
+                group_uuids.insert(group.uuid);
+                for entry in group.entries.iter_mut() {
+                    entry.parent = group.uuid;
                 }
+                for sub in group.groups.iter_mut() {
+                    sub.parent = group.uuid;
+                }
+                count += 1;
             }
         }
-        None
+
+        let mut entry_uuids = HashSet::new();
+        for entry in self.entries_mut() {
+            if !entry_uuids.insert(entry.uuid) {
+                entry.uuid = EntryUuid::new_random();
+                entry_uuids.insert(entry.uuid);
+                for old in entry.history.iter_mut() {
+                    old.uuid = entry.uuid;
+                }
+                count += 1;
+            }
+        }
+        count
     }
 
-    /// Returns the mutable entry that matches the UUID or None if not found.
+    /// Removes all entries and sub groups from the recycle bin and returns
+    /// the number of removed entries and groups.
+    ///
+    /// Binaries in the global binaries map that were only referenced by the
+    /// removed entries are removed as well. The identifiers of the removed
+    /// entries and groups are recorded in the deleted objects list. Nothing is
+    /// removed when the recycle bin doesn't exist.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use kpdb::{CompositeKey, Database, Entry, Group};
     ///
-    /// let mut entry = Entry::new();
-    /// let entry_uuid = entry.uuid;
+    /// let mut recycle_bin = Group::new("Recycle Bin");
+    /// recycle_bin.add_entry(Entry::new());
     ///
     /// let mut db = Database::new(&CompositeKey::from_password("test"));
-    /// assert_eq!(db.get_entry_mut(entry_uuid), None);
+    /// db.recycle_bin_uuid = recycle_bin.uuid;
+    /// db.root_group.add_group(recycle_bin);
     ///
-    /// db.root_group.add_entry(entry.clone());
-    /// assert_eq!(db.get_entry_mut(entry_uuid), Some(&mut entry));
+    /// assert_eq!(db.empty_recycle_bin(), 1);
+    /// assert_eq!(db.root_group.groups[0].entries.len(), 0);
     /// ```
-    pub fn get_entry_mut<'a>(&'a mut self, uuid: EntryUuid) -> Option<&'a mut Entry> {
-        for group in self.root_group.iter_mut() {
-            for entry in group.entries.iter_mut() {
-                if entry.uuid == uuid {
-                    return Some(entry);
+    pub fn empty_recycle_bin(&mut self) -> usize {
+        let uuid = self.recycle_bin_uuid;
+        if uuid == GroupUuid::nil() {
+            return 0;
+        }
+
+        let (entries, groups) = match self.get_group_mut(uuid) {
+            Some(group) => (group.entries.split_off(0), group.groups.split_off(0)),
+            None => return 0,
+        };
+
+        let now = Utc::now();
+        let mut count = entries.len();
+        let mut removed_refs = HashSet::new();
+        for entry in entries.iter() {
+            collect_binary_refs(entry, &mut removed_refs);
+            self.deleted_objects.push((entry.uuid.0, now));
+        }
+        for group in groups.iter() {
+            for sub in group.iter() {
+                count += 1 + sub.entries.len();
+                self.deleted_objects.push((sub.uuid.0, now));
+                for entry in sub.entries.iter() {
+                    collect_binary_refs(entry, &mut removed_refs);
+                    self.deleted_objects.push((entry.uuid.0, now));
                 }
             }
         }
-        None
+
+        let mut refs = HashSet::new();
+        for entry in self.entries() {
+            collect_binary_refs(entry, &mut refs);
+        }
+        for id in removed_refs.difference(&refs) {
+            self.binaries.remove(id);
+        }
+
+        self.recycle_bin_changed = now;
+        count
     }
 
-    /// Returns the group that matches the UUID or None if not found.
+    /// Converts the standard strings of all entries, including their
+    /// history, to protected or plain values to match the memory protection
+    /// settings of the database.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use kpdb::{CompositeKey, Database, Group};
+    /// use kpdb::{CompositeKey, Database, Entry, StringKey, StringValue};
     ///
-    /// let group = Group::new("Group");
-    /// let group_uuid = group.uuid;
+    /// let mut entry = Entry::new();
+    /// entry.set_username("puser");
     ///
     /// let mut db = Database::new(&CompositeKey::from_password("test"));
-    /// assert_eq!(db.get_group(group_uuid), None);
+    /// db.root_group.add_entry(entry);
+    /// db.protect_username = true;
+    /// db.enforce_memory_protection();
     ///
-    /// db.root_group.add_group(group.clone());
-    /// assert_eq!(db.get_group(group_uuid), Some(&group));
+    /// let value = &db.root_group.entries[0].strings[&StringKey::Username];
+    /// assert_eq!(*value, StringValue::new("puser", true));
     /// ```
-    pub fn get_group<'a>(&'a self, uuid: GroupUuid) -> Option<&'a Group> {
-        self.root_group.iter().find(|g| g.uuid == uuid)
+    pub fn enforce_memory_protection(&mut self) {
+        let flags = [
+            (StringKey::Notes, self.protect_notes),
+            (StringKey::Password, self.protect_password),
+            (StringKey::Title, self.protect_title),
+            (StringKey::Url, self.protect_url),
+            (StringKey::Username, self.protect_username),
+        ];
+        for entry in self.entries_mut() {
+            enforce_entry_protection(entry, &flags);
+        }
     }
 
-    /// Returns the mutable group that matches the UUID or None if not found.
+    /// Returns an iterator over all entries in the database.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use kpdb::{CompositeKey, Database, Group};
+    /// use kpdb::{CompositeKey, Database, Entry, Group};
     ///
-    /// let mut group = Group::new("Group");
-    /// let group_uuid = group.uuid;
+    /// let mut group = Group::new("Email");
+    /// group.add_entry(Entry::new());
     ///
     /// let mut db = Database::new(&CompositeKey::from_password("test"));
-    /// assert_eq!(db.get_group(group_uuid), None);
+    /// db.root_group.add_entry(Entry::new());
+    /// db.root_group.add_group(group);
     ///
-    /// db.root_group.add_group(group.clone());
-    /// assert_eq!(db.get_group_mut(group_uuid), Some(&mut group));
+    /// assert_eq!(db.entries().count(), 2);
     /// ```
-    pub fn get_group_mut<'a>(&'a mut self, uuid: GroupUuid) -> Option<&'a mut Group> {
-        self.root_group.iter_mut().find(|g| g.uuid == uuid)
+    pub fn entries<'a>(&'a self) -> impl Iterator<Item = &'a Entry> + 'a {
+        self.root_group.iter().flat_map(|g| g.entries.iter())
     }
 
-    /// Attempts to open an existing database.
+    /// Returns all entries with the same host as the URL.
+    ///
+    /// The hosts are compared case insensitively, the scheme, port and path
+    /// are ignored.
     ///
     /// # Examples
     ///
-    /// ```rust,no_run
-    /// # use kpdb::Result;
-    /// use kpdb::{CompositeKey, Database};
-    /// use std::fs::File;
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry};
     ///
-    /// # fn open_example() -> Result<()> {
-    /// let mut file = File::open("passwords.kdbx")?;
-    /// let key = CompositeKey::from_password("password");
-    /// let db = Database::open(&mut file, &key)?;
-    /// # Ok(())
-    /// # }
+    /// let mut entry = Entry::new();
+    /// entry.set_url("https://mail.google.com/mail/");
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(entry);
+    ///
+    /// assert_eq!(db.entries_for_url("https://MAIL.google.com/login").len(), 1);
+    /// assert_eq!(db.entries_for_url("https://google.com/").len(), 0);
     /// ```
-    pub fn open<R: Read>(reader: &mut R, key: &CompositeKey) -> Result<Database> {
-        let mut reader = LogReader::new(reader);
-        let mut buffer = [0u8; 4];
-
-        reader.read(&mut buffer)?;
-        if buffer != common::DB_SIGNATURE {
-            return Err(Error::InvalidDbSignature(buffer));
+    pub fn entries_for_url(&self, url: &str) -> Vec<&Entry> {
+        match utils::url_host(url) {
+            Some(host) => self
+                .entries()
+                .filter(|e| e.url_host().as_ref() == Some(&host))
+                .collect(),
+            None => Vec::new(),
         }
+    }
 
-        reader.read(&mut buffer)?;
-        if buffer == common::KDB1_SIGNATURE {
-            return Err(Error::UnhandledDbType(buffer));
-        } else if buffer == common::KDB2_SIGNATURE {
-            Database::open_kdb2(&mut reader, key)
-        } else {
-            return Err(Error::UnhandledDbType(buffer));
+    /// Returns the entries of the group with the specified UUID.
+    ///
+    /// When recursive is true the entries of all sub groups are included. An
+    /// empty vector is returned when the group doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry, Group};
+    ///
+    /// let mut work = Group::new("Work");
+    /// work.add_entry(Entry::new());
+    ///
+    /// let mut email = Group::new("Email");
+    /// email.add_entry(Entry::new());
+    /// email.add_group(work);
+    /// let uuid = email.uuid;
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(email);
+    ///
+    /// assert_eq!(db.entries_in_group(uuid, false).len(), 1);
+    /// assert_eq!(db.entries_in_group(uuid, true).len(), 2);
+    /// ```
+    pub fn entries_in_group(&self, group: GroupUuid, recursive: bool) -> Vec<&Entry> {
+        match self.get_group(group) {
+            Some(group) if recursive => group.iter().flat_map(|g| g.entries.iter()).collect(),
+            Some(group) => group.entries.iter().collect(),
+            None => Vec::new(),
         }
     }
 
-    /// Attempts to save the database.
+    /// Returns an iterator that allows modifying each entry in the database.
     ///
     /// # Examples
     ///
-    /// ```rust,no_run
-    /// # use kpdb::Result;
-    /// use kpdb::{CompositeKey, Database};
-    /// use std::fs::File;
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry};
     ///
-    /// # fn save_example() -> Result<()> {
-    /// let key = CompositeKey::from_password("password");
-    /// let db = Database::new(&key);
-    /// let mut file = File::create("new.kdbx")?;
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(Entry::new());
     ///
-    /// db.save(&mut file);
-    /// # Ok(())
-    /// # }
+    /// for entry in db.entries_mut() {
+    ///     entry.set_title("title");
+    /// }
+    /// assert_eq!(db.root_group.entries[0].title(), Some("title"));
     /// ```
-    pub fn save<W: Write>(&self, writer: &mut W) -> Result<()> {
-        let mut writer = LogWriter::new(writer);
-        match self.db_type {
-            DbType::Kdb1 => Err(Error::Unimplemented(String::from("KeePass v1 not supported"))),
-            DbType::Kdb2 => kdb2_writer::write(&mut writer, self),
-        }
+    pub fn entries_mut<'a>(&'a mut self) -> impl Iterator<Item = &'a mut Entry> + 'a {
+        self.root_group
+            .iter_mut()
+            .flat_map(|g| g.entries.iter_mut())
     }
 
-    fn open_kdb2<R: Log + Read>(reader: &mut R, key: &CompositeKey) -> Result<Database> {
-        let (meta_data, xml_data) = kdb2_reader::read(reader, key)?;
-        match xml_data.header_hash {
-            Some(header_hash) => {
-                if meta_data.header_hash != header_hash {
-                    return Err(Error::InvalidHeaderHash);
-                }
-            }
-            None => {}
+    /// Returns all entries, excluding the history, sorted by the specified
+    /// field.
+    ///
+    /// Entries that compare equal keep their order in the tree. See
+    /// `Entry::cmp_by` for how the fields are compared.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry, SortField};
+    ///
+    /// let mut bank = Entry::new();
+    /// bank.set_title("Bank");
+    /// let mut email = Entry::new();
+    /// email.set_title("email");
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(email);
+    /// db.root_group.add_entry(bank);
+    ///
+    /// let sorted = db.entries_sorted(SortField::Title, false);
+    /// assert_eq!(sorted[0].title(), Some("Bank"));
+    /// assert_eq!(sorted[1].title(), Some("email"));
+    /// ```
+    pub fn entries_sorted(&self, field: SortField, descending: bool) -> Vec<&Entry> {
+        let mut list: Vec<&Entry> = self.entries().collect();
+        if descending {
+            list.sort_by(|a, b| b.cmp_by(a, field));
+        } else {
+            list.sort_by(|a, b| a.cmp_by(b, field));
         }
+        list
+    }
 
-        let root_group = match xml_data.root_group {
-            Some(group) => group,
-            None => Group::new(common::ROOT_GROUP_NAME),
-        };
+    /// Returns an iterator over all entries in the database paired with the
+    /// path of their group.
+    ///
+    /// The path consists of the group names below the root group separated
+    /// by a forward slash, so entries of the root group have an empty path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry, Group};
+    ///
+    /// let mut group = Group::new("Email");
+    /// group.add_entry(Entry::new());
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(group);
+    ///
+    /// let (path, _) = db.entries_with_path().next().unwrap();
+    /// assert_eq!(path, "Email");
+    /// ```
+    pub fn entries_with_path<'a>(&'a self) -> impl Iterator<Item = (String, &'a Entry)> + 'a {
+        let mut list = Vec::new();
+        collect_entries_with_path(&self.root_group, String::new(), &mut list);
+        list.into_iter()
+    }
 
-        let db = Database {
-            comment: meta_data.comment,
-            composite_key: key.clone(),
-            compression: meta_data.compression,
-            db_type: DbType::Kdb2,
-            master_cipher: meta_data.master_cipher,
-            stream_cipher: meta_data.stream_cipher,
-            transform_rounds: meta_data.transform_rounds,
-            version: meta_data.version,
+    /// Returns the entry templates group or None if it isn't set.
+    ///
+    /// A nil identifier and an identifier of a group that doesn't exist both
+    /// result in None.
+    pub fn entry_templates_group_ref(&self) -> Option<&Group> {
+        referenced_group(self, self.entry_templates_group_uuid)
+    }
 
-            binaries: xml_data.binaries,
-            color: xml_data.color,
-            custom_data: xml_data.custom_data,
-            custom_icons: xml_data.custom_icons,
-            def_username: xml_data.def_username,
-            def_username_changed: xml_data.def_username_changed,
-            description: xml_data.description,
-            description_changed: xml_data.description_changed,
-            entry_templates_group_changed: xml_data.entry_templates_group_changed,
-            entry_templates_group_uuid: xml_data.entry_templates_group_uuid,
-            generator: xml_data.generator,
-            history_max_items: xml_data.history_max_items,
-            history_max_size: xml_data.history_max_size,
-            last_selected_group: xml_data.last_selected_group,
-            last_top_visible_group: xml_data.last_top_visible_group,
-            maintenance_history_days: xml_data.maintenance_history_days,
-            master_key_change_force: xml_data.master_key_change_force,
-            master_key_change_rec: xml_data.master_key_change_rec,
-            master_key_changed: xml_data.master_key_changed,
-            name: xml_data.name,
-            name_changed: xml_data.name_changed,
-            protect_notes: xml_data.protect_notes,
-            protect_password: xml_data.protect_password,
-            protect_title: xml_data.protect_title,
-            protect_url: xml_data.protect_url,
-            protect_username: xml_data.protect_username,
-            recycle_bin_changed: xml_data.recycle_bin_changed,
-            recycle_bin_enabled: xml_data.recycle_bin_enabled,
-            recycle_bin_uuid: xml_data.recycle_bin_uuid,
-            root_group: root_group,
+    /// Returns the template with the placeholders of the entry and all field
+    /// references replaced by their values.
+    ///
+    /// See `Entry::expand_placeholders` for the supported placeholders and
+    /// `Database::resolve_references` for the field references, references
+    /// in the values of the placeholders are resolved as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry};
+    ///
+    /// let mut shared = Entry::new();
+    /// shared.set_password("ppass");
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.set_username("puser");
+    /// entry.set_password(format!("{{REF:P@I:{}}}", shared.uuid.0.simple()));
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(shared);
+    ///
+    /// let text = db.expand(&entry, "{USERNAME}:{PASSWORD}");
+    /// assert_eq!(text, "puser:ppass");
+    /// ```
+    pub fn expand(&self, entry: &Entry, template: &str) -> String {
+        let text = entry.expand_placeholders(template);
+        let mut budget = common::MAX_REFERENCES;
+        resolve_text(self, &text, common::MAX_REFERENCE_DEPTH, &mut budget)
+    }
+
+    /// Attempts to write the data of the entry's binary with the specified
+    /// name to the writer.
+    ///
+    /// References are resolved using the global binaries map and protected
+    /// data is written in cleartext. Returns an error when the entry, the
+    /// named binary or the referenced data doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, Entry};
+    ///
+    /// # fn extract_attachment_example() -> Result<()> {
+    /// let entry = Entry::new();
+    /// let entry_uuid = entry.uuid;
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(entry);
+    /// db.set_entry_binary(entry_uuid, "file.txt", vec![1, 2, 3], false)?;
+    ///
+    /// let mut out = Vec::new();
+    /// db.extract_attachment(entry_uuid, "file.txt", &mut out)?;
+    /// assert_eq!(out, vec![1, 2, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn extract_attachment<W: Write>(
+        &self,
+        entry: EntryUuid,
+        name: &str,
+        out: &mut W,
+    ) -> Result<()> {
+        let item = match self.get_entry(entry) {
+            Some(item) => item,
+            None => return Err(Error::MissingEntry(entry)),
+        };
+        let key = BinaryKey(String::from(name));
+        let data = match item.binaries.get(&key) {
+            Some(BinaryValue::Plain(data)) => data.as_slice(),
+            Some(BinaryValue::Protected(sec)) => sec.unsecure(),
+            Some(BinaryValue::Ref(id)) => match self.binaries.get(id) {
+                Some(data) => data.as_slice(),
+                None => {
+                    return Err(Error::MissingBinary {
+                        entry,
+                        id: id.clone(),
+                    })
+                }
+            },
+            None => return Err(Error::MissingBinaryKey { entry, key }),
+        };
+        out.write_all(data)?;
+        Ok(())
+    }
+
+    /// Returns the entry and group identifiers that are used more than once.
+    ///
+    /// History entries are not taken into account because they share the
+    /// identifier of their entry.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry};
+    ///
+    /// let entry = Entry::new();
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(entry.clone());
+    /// db.root_group.add_entry(entry.clone());
+    ///
+    /// let (entry_uuids, group_uuids) = db.find_duplicate_uuids();
+    /// assert_eq!(entry_uuids, vec![entry.uuid]);
+    /// assert_eq!(group_uuids.len(), 0);
+    /// ```
+    pub fn find_duplicate_uuids(&self) -> (Vec<EntryUuid>, Vec<GroupUuid>) {
+        let mut seen = HashSet::new();
+        let mut entry_uuids = Vec::new();
+        for entry in self.entries() {
+            if !seen.insert(entry.uuid) && !entry_uuids.contains(&entry.uuid) {
+                entry_uuids.push(entry.uuid);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut group_uuids = Vec::new();
+        for group in self.root_group.iter() {
+            if !seen.insert(group.uuid) && !group_uuids.contains(&group.uuid) {
+                group_uuids.push(group.uuid);
+            }
+        }
+        (entry_uuids, group_uuids)
+    }
+
+    /// Returns a vector with entries that match (case insensitive) the supplied text.
+    ///
+    /// The title, username, URL, notes and other strings of an entry are
+    /// searched, both plain and protected values. The password is not
+    /// searched.
+    ///
+    /// With the `accent-insensitive` feature diacritics are ignored as well,
+    /// so "jose" matches "José".
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry, Group};
+    ///
+    /// let mut protonmail = Entry::new();
+    /// protonmail.set_title("ProtonMail");
+    /// protonmail.set_username("puser");
+    /// protonmail.set_password("ppass");
+    /// protonmail.set_url("https://mail.protonmail.com");
+    ///
+    /// let mut group = Group::new("Email");
+    /// group.add_entry(protonmail);
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(group);
+    ///
+    /// let result = db.find_entries("Protonm");
+    /// assert_eq!(result.len(), 1);
+    ///
+    /// let result = db.find_entries("gmail");
+    /// assert_eq!(result.len(), 0);
+    /// ```
+    pub fn find_entries<'a, S: Into<String>>(&'a self, text: S) -> Vec<&'a Entry> {
+        let text = &utils::fold(&text.into());
+        self.entries()
+            .filter(|e| entry_contains_string(e, text))
+            .collect::<Vec<&'a Entry>>()
+    }
+
+    /// Returns a vector with mutable entries that match (case insensitive) the supplied text.
+    ///
+    /// The title, username, URL, notes and other strings of an entry are
+    /// searched, both plain and protected values. The password is not
+    /// searched.
+    ///
+    /// With the `accent-insensitive` feature diacritics are ignored as well,
+    /// so "jose" matches "José".
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry, Group};
+    ///
+    /// let mut protonmail = Entry::new();
+    /// protonmail.set_title("ProtonMail");
+    /// protonmail.set_username("puser");
+    /// protonmail.set_password("ppass");
+    /// protonmail.set_url("https://mail.protonmail.com");
+    ///
+    /// let mut group = Group::new("Email");
+    /// group.add_entry(protonmail);
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(group);
+    ///
+    /// let result = db.find_entries_mut("Protonm");
+    /// assert_eq!(result.len(), 1);
+    /// ```
+    pub fn find_entries_mut<'a, S: Into<String>>(&'a mut self, text: S) -> Vec<&'a mut Entry> {
+        let text = &utils::fold(&text.into());
+        self.entries_mut()
+            .filter(|e| entry_contains_string(e, text))
+            .collect::<Vec<&'a mut Entry>>()
+    }
+
+    /// Returns a vector with groups that match (case insensitive) the supplied name.
+    ///
+    /// With the `accent-insensitive` feature diacritics are ignored as well,
+    /// so "jose" matches "José".
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Group};
+    ///
+    /// let group = Group::new("Email");
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(group);
+    ///
+    /// let result = db.find_groups("mail");
+    /// assert_eq!(result.len(), 1);
+    ///
+    /// let result = db.find_groups("unknown");
+    /// assert_eq!(result.len(), 0);
+    /// ```
+    pub fn find_groups<'a, S: Into<String>>(&'a self, name: S) -> Vec<&'a Group> {
+        let name = &utils::fold(&name.into());
+        self.root_group
+            .iter()
+            .filter(|g| utils::fold(&g.name).contains(name))
+            .collect::<Vec<&'a Group>>()
+    }
+
+    /// Returns a vector with mutable groups that match (case insensitive) the supplied name.
+    ///
+    /// With the `accent-insensitive` feature diacritics are ignored as well,
+    /// so "jose" matches "José".
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Group};
+    ///
+    /// let group = Group::new("Email");
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(group);
+    ///
+    /// let result = db.find_groups_mut("mail");
+    /// assert_eq!(result.len(), 1);
+    /// ```
+    pub fn find_groups_mut<'a, S: Into<String>>(&'a mut self, name: S) -> Vec<&'a mut Group> {
+        let name = &utils::fold(&name.into());
+        self.root_group
+            .iter_mut()
+            .filter(|g| utils::fold(&g.name).contains(name))
+            .collect::<Vec<&'a mut Group>>()
+    }
+
+    /// Calls the closure for each entry in the database, allowing bulk
+    /// modifications.
+    ///
+    /// The history of the entries isn't visited.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry, Group};
+    ///
+    /// let mut group = Group::new("Email");
+    /// group.add_entry(Entry::new());
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(Entry::new());
+    /// db.root_group.add_group(group);
+    ///
+    /// db.for_each_entry_mut(|entry| entry.tags = String::from("imported"));
+    /// assert!(db.entries().all(|entry| entry.tags == "imported"));
+    /// ```
+    pub fn for_each_entry_mut<F: FnMut(&mut Entry)>(&mut self, mut f: F) {
+        for_each_group_entry_mut(&mut self.root_group, &mut f);
+    }
+
+    /// Attempts to create a database from unencrypted KeePass XML data in
+    /// which all values are stored in cleartext.
+    ///
+    /// This reads the output of `to_keepass_xml_pretty(true)`. The memory
+    /// protection settings from the XML are applied to the entries, see
+    /// `enforce_memory_protection`. The composite key is used when the
+    /// database is saved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, Entry};
+    ///
+    /// # fn from_plaintext_xml_example() -> Result<()> {
+    /// let mut entry = Entry::new();
+    /// entry.set_password("secret");
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(entry);
+    /// let xml = db.to_keepass_xml_pretty(true)?;
+    ///
+    /// let copy = Database::from_plaintext_xml(&xml, &CompositeKey::from_password("test"))?;
+    /// assert_eq!(copy.root_group.entries[0].password(), Some("secret"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_plaintext_xml(xml: &str, key: &CompositeKey) -> Result<Database> {
+        let stream_key = StreamKey::new(&ProtectedStreamKey([0u8; 32]));
+        let mut db = Database::from_xml(xml, key, &stream_key)?;
+        db.enforce_memory_protection();
+        Ok(db)
+    }
+
+    /// Attempts to create a database from unencrypted KeePass XML data.
+    ///
+    /// Protected values in the XML are decrypted using the stream key, which
+    /// is derived from the protected stream key header of the database the
+    /// XML was taken from. The headers aren't part of the XML, so the
+    /// defaults of `Database::new` are used for them and a header hash in
+    /// the XML isn't verified. The composite key is used when the database
+    /// is saved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, ProtectedStreamKey, StreamKey};
+    /// use std::fs;
+    ///
+    /// # fn from_xml_example() -> Result<()> {
+    /// let xml = fs::read_to_string("passwords.xml")?;
+    /// let key = CompositeKey::from_password("password");
+    /// let stream_key = StreamKey::new(&ProtectedStreamKey([0u8; 32]));
+    /// let db = Database::from_xml(&xml, &key, &stream_key)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_xml(xml: &str, key: &CompositeKey, stream_key: &StreamKey) -> Result<Database> {
+        let mut xml_data = kdb2_reader::read_plaintext_xml(&mut xml.as_bytes(), stream_key)?;
+        let defaults = Database::new(key);
+        let meta_data = MetaData {
+            comment: defaults.comment,
+            compression: defaults.compression,
+            header_hash: HeaderHash(Vec::new()),
+            master_cipher: defaults.master_cipher,
+            stream_cipher: defaults.stream_cipher,
+            transform_rounds: defaults.transform_rounds,
+            version: defaults.version,
         };
+        xml_data.header_hash = None;
+        Database::from_kdb2(key, meta_data, xml_data)
+    }
+
+    /// Removes the custom icons that aren't used by any group or entry
+    /// (including the history of entries) and returns the number of removed
+    /// icons.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry};
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// let used = db.add_custom_icon(vec![1]);
+    /// db.add_custom_icon(vec![2]);
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.custom_icon_uuid = Some(used);
+    /// db.root_group.add_entry(entry);
+    ///
+    /// assert_eq!(db.gc_custom_icons(), 1);
+    /// assert!(db.custom_icons.contains_key(&used));
+    /// ```
+    pub fn gc_custom_icons(&mut self) -> usize {
+        let mut refs = HashSet::new();
+        for group in self.root_group.iter() {
+            refs.extend(group.custom_icon_uuid);
+            for entry in group.entries.iter() {
+                collect_custom_icon_refs(entry, &mut refs);
+            }
+        }
+        let count = self.custom_icons.len();
+        self.custom_icons.retain(|uuid, _| refs.contains(uuid));
+        count - self.custom_icons.len()
+    }
+
+    /// Gets the custom data value with the specified key if any.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database};
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// assert_eq!(db.get_custom_data("plugin"), None);
+    ///
+    /// db.set_custom_data("plugin", "value");
+    /// assert_eq!(db.get_custom_data("plugin"), Some("value"));
+    /// ```
+    pub fn get_custom_data(&self, key: &str) -> Option<&str> {
+        self.custom_data.get(key).map(|value| value.as_str())
+    }
+
+    /// Returns the entry that matches the UUID or None if not found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry, Group};
+    ///
+    /// let entry = Entry::new();
+    /// let entry_uuid = entry.uuid;
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// assert_eq!(db.get_entry(entry_uuid), None);
+    ///
+    /// db.root_group.add_entry(entry.clone());
+    /// assert_eq!(db.get_entry(entry_uuid), Some(&entry));
+    /// ```
+    pub fn get_entry<'a>(&'a self, uuid: EntryUuid) -> Option<&'a Entry> {
+        for group in self.root_group.iter() {
+            for entry in group.entries.iter() {
+                if entry.uuid == uuid {
+                    return Some(entry);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the entry that matches the path or None if not found.
+    ///
+    /// The path consists of the case insensitive group names below the root
+    /// group followed by the title of the entry, separated by a forward
+    /// slash. None is also returned when a path segment is ambiguous.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry, Group};
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.set_title("ProtonMail");
+    ///
+    /// let mut group = Group::new("Email");
+    /// group.add_entry(entry.clone());
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(group);
+    ///
+    /// assert_eq!(db.get_entry_by_path("email/protonmail"), Some(&entry));
+    /// assert_eq!(db.get_entry_by_path("Email/Gmail"), None);
+    /// ```
+    pub fn get_entry_by_path<'a>(&'a self, path: &str) -> Option<&'a Entry> {
+        let mut segments = path_segments(path);
+        let title = segments.pop()?.to_lowercase();
+        let group = get_group_by_segments(&self.root_group, &segments)?;
+        let mut matches = group
+            .entries
+            .iter()
+            .filter(|e| e.title().is_some_and(|t| t.to_lowercase() == title));
+        match (matches.next(), matches.next()) {
+            (Some(entry), None) => Some(entry),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable entry that matches the UUID or None if not found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry, Group};
+    ///
+    /// let mut entry = Entry::new();
+    /// let entry_uuid = entry.uuid;
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// assert_eq!(db.get_entry_mut(entry_uuid), None);
+    ///
+    /// db.root_group.add_entry(entry.clone());
+    /// assert_eq!(db.get_entry_mut(entry_uuid), Some(&mut entry));
+    /// ```
+    pub fn get_entry_mut<'a>(&'a mut self, uuid: EntryUuid) -> Option<&'a mut Entry> {
+        for group in self.root_group.iter_mut() {
+            for entry in group.entries.iter_mut() {
+                if entry.uuid == uuid {
+                    return Some(entry);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the group that matches the UUID or None if not found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Group};
+    ///
+    /// let group = Group::new("Group");
+    /// let group_uuid = group.uuid;
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// assert_eq!(db.get_group(group_uuid), None);
+    ///
+    /// db.root_group.add_group(group.clone());
+    /// assert_eq!(db.get_group(group_uuid), Some(&group));
+    /// ```
+    pub fn get_group<'a>(&'a self, uuid: GroupUuid) -> Option<&'a Group> {
+        self.root_group.iter().find(|g| g.uuid == uuid)
+    }
+
+    /// Returns the group that matches the path or None if not found.
+    ///
+    /// The path consists of the case insensitive group names below the root
+    /// group separated by a forward slash, so an empty path returns the root
+    /// group. None is also returned when a path segment is ambiguous.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Group};
+    ///
+    /// let mut group = Group::new("Email");
+    /// group.add_group(Group::new("Work"));
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(group);
+    ///
+    /// let result = db.get_group_by_path("email/work").unwrap();
+    /// assert_eq!(result.name, "Work");
+    /// ```
+    pub fn get_group_by_path<'a>(&'a self, path: &str) -> Option<&'a Group> {
+        get_group_by_segments(&self.root_group, &path_segments(path))
+    }
+
+    /// Returns the mutable group that matches the UUID or None if not found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Group};
+    ///
+    /// let mut group = Group::new("Group");
+    /// let group_uuid = group.uuid;
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// assert_eq!(db.get_group(group_uuid), None);
+    ///
+    /// db.root_group.add_group(group.clone());
+    /// assert_eq!(db.get_group_mut(group_uuid), Some(&mut group));
+    /// ```
+    pub fn get_group_mut<'a>(&'a mut self, uuid: GroupUuid) -> Option<&'a mut Group> {
+        self.root_group.iter_mut().find(|g| g.uuid == uuid)
+    }
+
+    /// Returns whether auto-type is enabled for the entries of the group.
+    ///
+    /// A group without an explicit auto-type setting inherits the setting of
+    /// its parent, the root group has auto-type enabled unless disabled.
+    /// False is returned when the group doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Group};
+    ///
+    /// let mut parent = Group::new("Parent");
+    /// parent.enable_auto_type = Some(false);
+    /// let child = Group::new("Child");
+    /// let child_uuid = child.uuid;
+    /// parent.add_group(child);
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(parent);
+    ///
+    /// assert!(db.is_auto_type_enabled(db.root_group.uuid));
+    /// assert!(!db.is_auto_type_enabled(child_uuid));
+    /// ```
+    pub fn is_auto_type_enabled(&self, uuid: GroupUuid) -> bool {
+        inherited_setting(&self.root_group, uuid, |g| g.enable_auto_type)
+    }
+
+    /// Returns whether the group with the UUID is the recycle bin.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Group};
+    ///
+    /// let group = Group::new("Recycle Bin");
+    /// let group_uuid = group.uuid;
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(group);
+    /// assert_eq!(db.is_recycle_bin(group_uuid), false);
+    ///
+    /// db.recycle_bin_uuid = group_uuid;
+    /// assert_eq!(db.is_recycle_bin(group_uuid), true);
+    /// ```
+    pub fn is_recycle_bin(&self, uuid: GroupUuid) -> bool {
+        self.recycle_bin_uuid != GroupUuid::nil() && self.recycle_bin_uuid == uuid
+    }
+
+    /// Returns whether the entries of the group should be included in
+    /// searches.
+    ///
+    /// A group without an explicit search setting inherits the setting of
+    /// its parent, the root group is searchable unless disabled. False is
+    /// returned when the group doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Group};
+    ///
+    /// let mut parent = Group::new("Parent");
+    /// parent.enable_searching = Some(false);
+    /// let child = Group::new("Child");
+    /// let child_uuid = child.uuid;
+    /// parent.add_group(child);
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(parent);
+    ///
+    /// assert_eq!(db.is_searchable(db.root_group.uuid), true);
+    /// assert_eq!(db.is_searchable(child_uuid), false);
+    /// ```
+    pub fn is_searchable(&self, uuid: GroupUuid) -> bool {
+        inherited_setting(&self.root_group, uuid, |g| g.enable_searching)
+    }
+
+    /// Returns the key derivation function and parameters that are used
+    /// when saving, for opened databases these were read from the file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, KdfParams};
+    ///
+    /// let db = Database::new(&CompositeKey::from_password("test"));
+    /// match db.kdf_params() {
+    ///     KdfParams::Aes { rounds } => assert_eq!(rounds.0, 10000),
+    /// }
+    /// ```
+    pub fn kdf_params(&self) -> KdfParams {
+        KdfParams::Aes {
+            rounds: self.transform_rounds.clone(),
+        }
+    }
+
+    /// Returns the last selected group or None if it isn't set.
+    ///
+    /// A nil identifier and an identifier of a group that doesn't exist both
+    /// result in None, so a GUI can fall back to the root group.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Group};
+    ///
+    /// let group = Group::new("Email");
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(group.clone());
+    /// assert_eq!(db.last_selected_group_ref(), None);
+    ///
+    /// db.last_selected_group = group.uuid;
+    /// assert_eq!(db.last_selected_group_ref(), Some(&group));
+    /// ```
+    pub fn last_selected_group_ref(&self) -> Option<&Group> {
+        referenced_group(self, self.last_selected_group)
+    }
+
+    /// Returns the last top visible group or None if it isn't set.
+    ///
+    /// A nil identifier and an identifier of a group that doesn't exist both
+    /// result in None.
+    pub fn last_top_visible_group_ref(&self) -> Option<&Group> {
+        referenced_group(self, self.last_top_visible_group)
+    }
+
+    /// Gets the smallest numeric identifier that isn't used yet in the
+    /// binaries map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{BinaryId, CompositeKey, Database};
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.binaries.insert(BinaryId(String::from("0")), vec![1, 2, 3]);
+    /// assert_eq!(db.next_binary_id(), BinaryId(String::from("1")));
+    /// ```
+    pub fn next_binary_id(&self) -> BinaryId {
+        let used: HashSet<u32> = self.binaries.keys().filter_map(BinaryId::parse).collect();
+        let id = (0..=u32::MAX).find(|n| !used.contains(n)).unwrap();
+        BinaryId(id.to_string())
+    }
+
+    /// Attempts to open an existing database.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database};
+    /// use std::fs::File;
+    ///
+    /// # fn open_example() -> Result<()> {
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let key = CompositeKey::from_password("password");
+    /// let db = Database::open(&mut file, &key)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open<R: Read>(reader: &mut R, key: &CompositeKey) -> Result<Database> {
+        Database::open_with_options(reader, key, &OpenOptions::default())
+    }
+
+    /// Attempts to open an existing database from a seekable source.
+    ///
+    /// The size of the encrypted data is determined from the stream length,
+    /// so a truncated file is rejected before the expensive key
+    /// transformation. The encrypted data is then read in chunks up to the
+    /// end of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database};
+    /// use std::fs::File;
+    ///
+    /// # fn open_seek_example() -> Result<()> {
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let key = CompositeKey::from_password("password");
+    /// let db = Database::open_seek(&mut file, &key)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open_seek<R: Read + Seek>(reader: &mut R, key: &CompositeKey) -> Result<Database> {
+        Database::open_seek_with_options(reader, key, &OpenOptions::default())
+    }
+
+    /// Attempts to open an existing database from a seekable source using
+    /// the specified options.
+    ///
+    /// See `Database::open_seek` for how the encrypted data is read and
+    /// `OpenOptions` for the available options.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, OpenOptions, ReadLimits};
+    /// use std::fs::File;
+    ///
+    /// # fn open_seek_with_options_example() -> Result<()> {
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let key = CompositeKey::from_password("password");
+    /// let limits = ReadLimits {
+    ///     max_headers: 32,
+    ///     ..ReadLimits::default()
+    /// };
+    /// let options = OpenOptions::new().with_read_limits(limits);
+    /// let db = Database::open_seek_with_options(&mut file, &key, &options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open_seek_with_options<R: Read + Seek>(
+        reader: &mut R,
+        key: &CompositeKey,
+        options: &OpenOptions,
+    ) -> Result<Database> {
+        let mut reader = LogReader::with_limit(reader, options.limits.max_header_size());
+        read_signature(&mut reader)?;
+        let (meta_data, xml_data) = kdb2_reader::read_seek(&mut reader, key, options)?;
+        Database::from_kdb2(key, meta_data, xml_data)
+    }
+
+    /// Attempts to open an existing database using the specified options.
+    ///
+    /// See `OpenOptions` for the available options and their defaults, which
+    /// are used by `open`. In lenient mode the skipped values are not
+    /// reported, use `open_with_recovery` to get them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, OpenOptions};
+    /// use std::fs::File;
+    ///
+    /// # fn open_with_options_example() -> Result<()> {
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let key = CompositeKey::from_password("password");
+    /// let options = OpenOptions::new().max_transform_rounds(10000000);
+    /// let db = Database::open_with_options(&mut file, &key, &options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open_with_options<R: Read>(
+        reader: &mut R,
+        key: &CompositeKey,
+        options: &OpenOptions,
+    ) -> Result<Database> {
+        let (db, _) = Database::open_with_recovery(reader, key, options)?;
+        Ok(db)
+    }
+
+    /// Attempts to open an existing database and returns the unreadable
+    /// values that were skipped.
+    ///
+    /// With `OpenOptions::lenient` set, an XML value that can't be parsed
+    /// (e.g. an invalid date or icon) is replaced by its default and recorded
+    /// instead of failing the whole open. Broken XML structure, an invalid
+    /// key and a corrupted file are still errors. Without lenient mode the
+    /// returned list is always empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, OpenOptions};
+    /// use std::fs::File;
+    ///
+    /// # fn open_with_recovery_example() -> Result<()> {
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let key = CompositeKey::from_password("password");
+    /// let options = OpenOptions::new().lenient(true);
+    /// let (db, recovered) = Database::open_with_recovery(&mut file, &key, &options)?;
+    /// for error in recovered {
+    ///     println!("skipped {}", error);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open_with_recovery<R: Read>(
+        reader: &mut R,
+        key: &CompositeKey,
+        options: &OpenOptions,
+    ) -> Result<(Database, Vec<RecoveredError>)> {
+        let mut reader = LogReader::with_limit(reader, options.limits.max_header_size());
+        read_signature(&mut reader)?;
+        Database::open_kdb2(&mut reader, key, options)
+    }
+
+    /// Attempts to open an existing database using only an escrowed
+    /// transformed key, without the password or key file.
+    ///
+    /// Anyone holding the transformed key can decrypt the file it was
+    /// derived from, so an escrowed key must be stored as carefully as the
+    /// password itself. The key is only valid until the database is saved
+    /// again, since every save generates a new transform seed.
+    ///
+    /// The composite key can't be recovered from a transformed key, so the
+    /// returned database has an empty composite key. Saving it fails with
+    /// `Error::MissingCompositeKey` until a new `composite_key` is set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database};
+    /// use std::fs::File;
+    ///
+    /// # fn open_with_master_key_example() -> Result<()> {
+    /// let key = CompositeKey::from_password("password");
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let escrowed = Database::transformed_key(&mut file, &key)?;
+    ///
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let mut db = Database::open_with_master_key(&mut file, &escrowed)?;
+    /// db.composite_key = CompositeKey::from_password("new password");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open_with_master_key<R: Read>(
+        reader: &mut R,
+        transformed_key: &TransformedKey,
+    ) -> Result<Database> {
+        let options = OpenOptions::default();
+        Database::open_with_master_key_with_options(reader, transformed_key, &options)
+    }
+
+    /// Attempts to open an existing database using only an escrowed
+    /// transformed key and the specified options.
+    ///
+    /// See `Database::open_with_master_key` for how the transformed key is
+    /// used and `OpenOptions` for the available options.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, OpenOptions, ReadLimits};
+    /// use std::fs::File;
+    ///
+    /// # fn open_with_master_key_with_options_example() -> Result<()> {
+    /// let key = CompositeKey::from_password("password");
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let escrowed = Database::transformed_key(&mut file, &key)?;
+    ///
+    /// let limits = ReadLimits {
+    ///     max_xml_size: 16 * 1024 * 1024,
+    ///     ..ReadLimits::default()
+    /// };
+    /// let options = OpenOptions::new().with_read_limits(limits);
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let db = Database::open_with_master_key_with_options(&mut file, &escrowed, &options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open_with_master_key_with_options<R: Read>(
+        reader: &mut R,
+        transformed_key: &TransformedKey,
+        options: &OpenOptions,
+    ) -> Result<Database> {
+        let mut reader = LogReader::with_limit(reader, options.limits.max_header_size());
+        read_signature(&mut reader)?;
+        let key = CompositeKey::empty();
+        Database::open_kdb2_with_transformed_key(&mut reader, &key, transformed_key, options)
+    }
+
+    /// Attempts to open an existing database using a previously transformed
+    /// key, which skips the expensive key derivation.
+    ///
+    /// The transformed key is only valid for a specific file: saving the
+    /// database generates a new transform seed, so the key must be obtained
+    /// again with [`Database::transformed_key`] after each save. An invalid
+    /// or stale transformed key results in an invalid key error.
+    ///
+    /// The composite key isn't checked against the transformed key, it's
+    /// only used when the database is saved. Use `open_with_master_key` when
+    /// only an escrowed transformed key is available.
+    ///
+    /// The transformed key is kept in protected memory that is zeroed when
+    /// it's dropped, so drop a cached key as soon as it's no longer needed
+    /// (e.g. when the application is locked permanently).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database};
+    /// use std::fs::File;
+    ///
+    /// # fn open_with_transformed_key_example() -> Result<()> {
+    /// let key = CompositeKey::from_password("password");
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let transformed_key = Database::transformed_key(&mut file, &key)?;
+    ///
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let db = Database::open_with_transformed_key(&mut file, &key, &transformed_key)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open_with_transformed_key<R: Read>(
+        reader: &mut R,
+        key: &CompositeKey,
+        transformed_key: &TransformedKey,
+    ) -> Result<Database> {
+        let options = OpenOptions::default();
+        Database::open_with_transformed_key_with_options(reader, key, transformed_key, &options)
+    }
+
+    /// Attempts to open an existing database using a previously transformed
+    /// key and the specified options.
+    ///
+    /// See `Database::open_with_transformed_key` for how the transformed key
+    /// is used and `OpenOptions` for the available options.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, OpenOptions};
+    /// use std::fs::File;
+    ///
+    /// # fn open_with_transformed_key_with_options_example() -> Result<()> {
+    /// let key = CompositeKey::from_password("password");
+    /// let options = OpenOptions::new().max_transform_rounds(10000000);
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let transformed_key = Database::transformed_key_with_options(&mut file, &key, &options)?;
+    ///
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let db = Database::open_with_transformed_key_with_options(
+    ///     &mut file,
+    ///     &key,
+    ///     &transformed_key,
+    ///     &options,
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open_with_transformed_key_with_options<R: Read>(
+        reader: &mut R,
+        key: &CompositeKey,
+        transformed_key: &TransformedKey,
+        options: &OpenOptions,
+    ) -> Result<Database> {
+        let mut reader = LogReader::with_limit(reader, options.limits.max_header_size());
+        read_signature(&mut reader)?;
+        Database::open_kdb2_with_transformed_key(&mut reader, key, transformed_key, options)
+    }
+
+    /// Returns the password problems of the entries outside the recycle bin.
+    ///
+    /// Entries that share a password are reported together, passwords with
+    /// an estimated entropy below 60 bits are reported as weak and entries
+    /// that were last modified more than max_age ago are reported as old.
+    /// Entries without a password are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::{Duration, Utc};
+    /// use kpdb::{CompositeKey, Database, Entry, PasswordIssue};
+    ///
+    /// let mut first = Entry::new();
+    /// first.set_password("Tr0ub4dour&3-correct-horse");
+    /// let mut second = first.clone();
+    /// second.uuid = kpdb::EntryUuid::new_random();
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(first.clone());
+    /// db.root_group.add_entry(second.clone());
+    ///
+    /// let issues = db.password_audit(Utc::now(), Duration::days(365));
+    /// assert_eq!(issues, vec![PasswordIssue::ReusedPassword(vec![first.uuid, second.uuid])]);
+    /// ```
+    pub fn password_audit(&self, now: DateTime<Utc>, max_age: Duration) -> Vec<PasswordIssue> {
+        let mut issues = Vec::new();
+        let mut reused: Vec<(&str, Vec<EntryUuid>)> = Vec::new();
+        let mut entries = Vec::new();
+        collect_entries_outside_recycle_bin(self, &self.root_group, &mut entries);
+        for entry in entries {
+            let password = match entry.password() {
+                Some(password) if !password.is_empty() => password,
+                _ => continue,
+            };
+            let entropy = utils::password_entropy(password);
+            if entropy < common::PASSWORD_ENTROPY_MIN {
+                issues.push(PasswordIssue::WeakPassword {
+                    entry: entry.uuid,
+                    entropy,
+                });
+            }
+            let age = now.signed_duration_since(entry.last_modified());
+            if age > max_age {
+                issues.push(PasswordIssue::OldPassword {
+                    entry: entry.uuid,
+                    days: age.num_days(),
+                });
+            }
+            match reused.iter_mut().find(|(p, _)| *p == password) {
+                Some((_, uuids)) => uuids.push(entry.uuid),
+                None => reused.push((password, vec![entry.uuid])),
+            }
+        }
+        issues.extend(
+            reused
+                .into_iter()
+                .filter(|(_, uuids)| uuids.len() > 1)
+                .map(|(_, uuids)| PasswordIssue::ReusedPassword(uuids)),
+        );
+        issues
+    }
+
+    /// Returns at most limit entries, the most recently created first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::Duration;
+    /// use kpdb::{CompositeKey, Database, Entry};
+    ///
+    /// let old = Entry::new();
+    /// let mut new = Entry::new();
+    /// new.creation_time = old.creation_time + Duration::days(1);
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(old.clone());
+    /// db.root_group.add_entry(new.clone());
+    ///
+    /// let recent = db.recent_entries(1);
+    /// assert_eq!(recent.len(), 1);
+    /// assert_eq!(recent[0].uuid, new.uuid);
+    /// ```
+    pub fn recent_entries(&self, limit: usize) -> Vec<&Entry> {
+        let mut list: Vec<&Entry> = self.entries().collect();
+        list.sort_by_key(|e| Reverse(e.creation_time));
+        list.truncate(limit);
+        list
+    }
+
+    /// Returns at most limit entries, the most recently modified first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::Duration;
+    /// use kpdb::{CompositeKey, Database, Entry};
+    ///
+    /// let mut old = Entry::new();
+    /// let new = Entry::new();
+    /// old.last_modified = new.last_modified - Duration::days(1);
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(old.clone());
+    /// db.root_group.add_entry(new.clone());
+    ///
+    /// let recent = db.recently_modified(2);
+    /// assert_eq!(recent[0].uuid, new.uuid);
+    /// assert_eq!(recent[1].uuid, old.uuid);
+    /// ```
+    pub fn recently_modified(&self, limit: usize) -> Vec<&Entry> {
+        let mut list: Vec<&Entry> = self.entries().collect();
+        list.sort_by_key(|e| Reverse(e.last_modified));
+        list.truncate(limit);
+        list
+    }
+
+    /// Returns the recycle bin group or None if there is no recycle bin.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Group};
+    ///
+    /// let group = Group::new("Recycle Bin");
+    /// let group_uuid = group.uuid;
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(group.clone());
+    /// assert_eq!(db.recycle_bin(), None);
+    ///
+    /// db.recycle_bin_uuid = group_uuid;
+    /// assert_eq!(db.recycle_bin(), Some(&group));
+    /// ```
+    pub fn recycle_bin(&self) -> Option<&Group> {
+        referenced_group(self, self.recycle_bin_uuid)
+    }
+
+    /// Returns a copy of this database without secrets.
+    ///
+    /// Every entry is redacted (see `Entry::redacted`) and the composite key
+    /// is replaced by an empty key, so the copy can't be saved until a new
+    /// `composite_key` is set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry};
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.set_password("secret");
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(entry);
+    ///
+    /// let redacted = db.redacted();
+    /// assert_eq!(redacted.root_group.entries[0].password(), Some("***"));
+    /// ```
+    pub fn redacted(&self) -> Database {
+        let mut db = self.clone();
+        db.composite_key = CompositeKey::empty();
+        for entry in db.entries_mut() {
+            *entry = entry.redacted();
+        }
+        db
+    }
+
+    /// Attempts to rename the group and updates its modification time.
+    ///
+    /// Returns an error when the group doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, Group};
+    ///
+    /// # fn rename_group_example() -> Result<()> {
+    /// let group = Group::new("Email");
+    /// let group_uuid = group.uuid;
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(group);
+    ///
+    /// db.rename_group(group_uuid, "Mail")?;
+    /// assert_eq!(db.root_group.groups[0].name, "Mail");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rename_group<S: Into<String>>(&mut self, uuid: GroupUuid, name: S) -> Result<()> {
+        match self.get_group_mut(uuid) {
+            Some(group) => {
+                group.name = name.into();
+                group.last_modified = Utc::now();
+                Ok(())
+            }
+            None => Err(Error::MissingGroup(uuid)),
+        }
+    }
+
+    /// Returns the value of the string with the supplied key where every
+    /// field reference is replaced by the value it references, or None if
+    /// the entry doesn't have the string.
+    ///
+    /// A field reference has the form `{REF:<wanted>@<search>:<text>}`,
+    /// where wanted and search are one of T (title), U (username), P
+    /// (password), A (URL), N (notes) or I (identifier). The referenced
+    /// entry is looked up by its identifier when searching in I, otherwise
+    /// it's the first entry whose searched field contains (case insensitive)
+    /// the text. References in referenced values are resolved as well, up to
+    /// a maximum depth to break cycles. References that can't be resolved are
+    /// left as is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry, StringKey};
+    ///
+    /// let mut shared = Entry::new();
+    /// shared.set_username("puser");
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.set_username(format!("{{REF:U@I:{}}}", shared.uuid.0.simple()));
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(shared);
+    ///
+    /// let username = db.resolve_references(&entry, &StringKey::Username);
+    /// assert_eq!(username, Some(String::from("puser")));
+    /// ```
+    pub fn resolve_references(&self, entry: &Entry, key: &StringKey) -> Option<String> {
+        let value = entry.strings.get(key)?;
+        let mut budget = common::MAX_REFERENCES;
+        let depth = common::MAX_REFERENCE_DEPTH;
+        Some(resolve_text(self, &value.reveal(), depth, &mut budget))
+    }
+
+    /// Attempts to save the database.
+    ///
+    /// The transform seed, master seed, master IV, protected stream key and
+    /// stream start bytes are generated for every save, so saving the same
+    /// database twice never produces the same file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database};
+    /// use std::fs::File;
+    ///
+    /// # fn save_example() -> Result<()> {
+    /// let key = CompositeKey::from_password("password");
+    /// let db = Database::new(&key);
+    /// let mut file = File::create("new.kdbx")?;
+    ///
+    /// db.save(&mut file);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn save<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.save_with_options(writer, &SaveOptions::default())
+    }
+
+    /// Attempts to save the database and reports what was written.
+    ///
+    /// The database is written the same way as with `save`. The returned
+    /// report contains the number of bytes written and the SHA-256 of those
+    /// bytes, which can be used to verify a temporary file before it
+    /// replaces the original.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database};
+    /// use std::fs::{self, File};
+    ///
+    /// # fn save_reported_example() -> Result<()> {
+    /// let key = CompositeKey::from_password("password");
+    /// let db = Database::new(&key);
+    /// let mut file = File::create("new.kdbx.tmp")?;
+    ///
+    /// let report = db.save_reported(&mut file)?;
+    /// file.sync_all()?;
+    /// if fs::metadata("new.kdbx.tmp")?.len() == report.bytes_written {
+    ///     fs::rename("new.kdbx.tmp", "new.kdbx")?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn save_reported<W: Write>(&self, writer: &mut W) -> Result<SaveReport> {
+        let mut writer = HashWriter::new(writer);
+        self.save(&mut writer)?;
+        let (bytes_written, sha256) = writer.finish();
+        Ok(SaveReport {
+            bytes_written,
+            sha256,
+        })
+    }
+
+    /// Attempts to save the database using the specified options.
+    ///
+    /// See `SaveOptions` for the available options and their defaults, which
+    /// are used by `save`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, SaveOptions};
+    /// use std::fs::File;
+    ///
+    /// # fn save_with_options_example() -> Result<()> {
+    /// let key = CompositeKey::from_password("password");
+    /// let db = Database::new(&key);
+    /// let mut file = File::create("new.kdbx")?;
+    ///
+    /// let options = SaveOptions::new().compression_level(9);
+    /// db.save_with_options(&mut file, &options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn save_with_options<W: Write>(&self, writer: &mut W, options: &SaveOptions) -> Result<()> {
+        let mut writer = LogWriter::new(writer);
+        match self.db_type {
+            DbType::Kdb1 => Err(Error::Unimplemented(String::from("KeePass v1 not supported"))),
+            DbType::Kdb2 => kdb2_writer::write_with_options(&mut writer, self, options),
+        }
+    }
+
+    /// Sets the master and stream encryption algorithms.
+    ///
+    /// Returns an error when an algorithm is not supported for writing, in
+    /// which case the database is not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use kpdb::Result;
+    /// # fn set_ciphers_example() -> Result<()> {
+    /// use kpdb::{CompositeKey, Database, MasterCipher, StreamCipher};
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.set_ciphers(MasterCipher::Aes256, StreamCipher::Salsa20)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_ciphers(&mut self, master: MasterCipher, stream: StreamCipher) -> Result<()> {
+        check_master_cipher(&master)?;
+        check_stream_cipher(&stream)?;
+        self.master_cipher = master;
+        self.stream_cipher = stream;
+        Ok(())
+    }
+
+    /// Attempts to attach the data to the entry under the supplied name and
+    /// updates its modification time.
+    ///
+    /// Unprotected data is stored in the global binaries map and referenced
+    /// by the entry, identical data shares a single item. Protected data is
+    /// stored in the entry itself. How the binaries are stored in the file is
+    /// decided by the writer of the database version. Returns an error when
+    /// the entry doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use kpdb::Result;
+    /// use kpdb::{BinaryKey, BinaryValue, CompositeKey, Database, Entry};
+    ///
+    /// # fn set_entry_binary_example() -> Result<()> {
+    /// let entry = Entry::new();
+    /// let entry_uuid = entry.uuid;
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(entry);
+    ///
+    /// db.set_entry_binary(entry_uuid, "file.txt", vec![1, 2, 3], false)?;
+    /// let key = BinaryKey(String::from("file.txt"));
+    /// match db.root_group.entries[0].binaries[&key] {
+    ///     BinaryValue::Ref(ref id) => assert_eq!(db.binaries[id], vec![1, 2, 3]),
+    ///     _ => panic!("expected reference"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_entry_binary(
+        &mut self,
+        entry: EntryUuid,
+        name: &str,
+        data: Vec<u8>,
+        protected: bool,
+    ) -> Result<()> {
+        if self.get_entry(entry).is_none() {
+            return Err(Error::MissingEntry(entry));
+        }
+        let value = if protected {
+            BinaryValue::Protected(SecStr::new(data))
+        } else {
+            BinaryValue::Ref(self.add_binary(data))
+        };
+        if let Some(entry) = self.get_entry_mut(entry) {
+            entry.binaries.insert(BinaryKey(String::from(name)), value);
+            entry.last_modified = Utc::now();
+        }
+        Ok(())
+    }
+
+    /// Attempts to set or clear the custom icon of the entry.
+    ///
+    /// Returns an error when the entry or the custom icon doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, Entry};
+    ///
+    /// # fn set_entry_custom_icon_example() -> Result<()> {
+    /// let entry = Entry::new();
+    /// let entry_uuid = entry.uuid;
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(entry);
+    ///
+    /// let icon_uuid = db.add_custom_icon(vec![1, 2, 3]);
+    /// db.set_entry_custom_icon(entry_uuid, Some(icon_uuid))?;
+    /// assert_eq!(db.root_group.entries[0].custom_icon_uuid, Some(icon_uuid));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_entry_custom_icon(
+        &mut self,
+        entry: EntryUuid,
+        icon: Option<CustomIconUuid>,
+    ) -> Result<()> {
+        if let Some(uuid) = icon {
+            if !self.custom_icons.contains_key(&uuid) {
+                return Err(Error::MissingCustomIcon(uuid));
+            }
+        }
+        match self.get_entry_mut(entry) {
+            Some(entry) => {
+                entry.custom_icon_uuid = icon;
+                Ok(())
+            }
+            None => Err(Error::MissingEntry(entry)),
+        }
+    }
+
+    /// Attempts to set the title of the entry and updates its modification
+    /// time.
+    ///
+    /// Returns an error when the entry doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, Entry};
+    ///
+    /// # fn set_entry_title_example() -> Result<()> {
+    /// let entry = Entry::new();
+    /// let entry_uuid = entry.uuid;
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(entry);
+    ///
+    /// db.set_entry_title(entry_uuid, "Email")?;
+    /// assert_eq!(db.root_group.entries[0].title(), Some("Email"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_entry_title<S: Into<String>>(&mut self, uuid: EntryUuid, title: S) -> Result<()> {
+        match self.get_entry_mut(uuid) {
+            Some(entry) => {
+                entry.set_title(title);
+                entry.last_modified = Utc::now();
+                Ok(())
+            }
+            None => Err(Error::MissingEntry(uuid)),
+        }
+    }
+
+    /// Attempts to set the comment header.
+    ///
+    /// Returns an error when the comment is larger than 65535 bytes, which
+    /// is the most the comment header can hold.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use kpdb::Result;
+    /// use kpdb::{Comment, CompositeKey, Database};
+    /// use std::str::FromStr;
+    ///
+    /// # fn set_comment_example() -> Result<()> {
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.set_comment(Some(Comment::from_str("comment").unwrap()))?;
+    /// assert_eq!(db.comment.unwrap().as_str(), Some("comment"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_comment(&mut self, comment: Option<Comment>) -> Result<()> {
+        if let Some(ref comment) = comment {
+            kdb2_writer::comment_size(comment)?;
+        }
+        self.comment = comment;
+        Ok(())
+    }
+
+    /// Sets the compression algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Compression, Database};
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.set_compression(Compression::None);
+    /// assert_eq!(db.compression, Compression::None);
+    /// ```
+    pub fn set_compression(&mut self, compression: Compression) {
+        self.compression = compression;
+    }
+
+    /// Sets the custom data value with the specified key.
+    pub fn set_custom_data(&mut self, key: &str, value: &str) {
+        self.custom_data
+            .insert(String::from(key), String::from(value));
+    }
+
+    /// Returns a summary of the contents of the database.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::Utc;
+    /// use kpdb::{CompositeKey, Database, Entry, Group};
+    ///
+    /// let mut group = Group::new("Group");
+    /// group.add_entry(Entry::new());
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(group);
+    ///
+    /// let stats = db.statistics(Utc::now());
+    /// assert_eq!(stats.groups, 1);
+    /// assert_eq!(stats.entries, 1);
+    /// assert_eq!(stats.empty_passwords, 1);
+    /// ```
+    pub fn statistics(&self, now: DateTime<Utc>) -> DatabaseStats {
+        let mut stats = DatabaseStats::default();
+        for entry in self.entries() {
+            stats.entries += 1;
+            if entry.is_expired(now) {
+                stats.expired_entries += 1;
+            }
+            if entry.password().is_none_or(str::is_empty) {
+                stats.empty_passwords += 1;
+            }
+        }
+        stats.groups = self.root_group.iter().count() - 1;
+        stats.binaries_size = self.binaries.values().map(|b| b.len()).sum();
+        stats.max_depth = group_depth(&self.root_group);
+        stats
+    }
+
+    /// Attempts to export the database as indented KeePass XML for inspection.
+    ///
+    /// Protected values are replaced by a placeholder and protected binaries
+    /// are left out, unless insecure_plaintext is true: then all protected
+    /// values are written in cleartext. Never store or share the cleartext
+    /// output without care, it contains every secret of the database.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, Entry};
+    ///
+    /// # fn to_keepass_xml_pretty_example() -> Result<()> {
+    /// let mut entry = Entry::new();
+    /// entry.set_password("secret");
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_entry(entry);
+    ///
+    /// let xml = db.to_keepass_xml_pretty(false)?;
+    /// assert!(!xml.contains("secret"));
+    ///
+    /// let xml = db.to_keepass_xml_pretty(true)?;
+    /// assert!(xml.contains("<Value>secret</Value>"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_keepass_xml_pretty(&self, insecure_plaintext: bool) -> Result<String> {
+        let mut data = Vec::new();
+        if insecure_plaintext {
+            kdb2_writer::write_plaintext_xml(&mut data, self)?;
+        } else {
+            kdb2_writer::write_plaintext_xml(&mut data, &self.redacted())?;
+        }
+        String::from_utf8(data).map_err(|err| Error::XmlError(format!("{}", err)))
+    }
+
+    /// Attempts to derive the transformed key for the database in the reader.
+    ///
+    /// This performs the expensive key derivation so the result can be
+    /// cached and passed to [`Database::open_with_transformed_key`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database};
+    /// use std::fs::File;
+    ///
+    /// # fn transformed_key_example() -> Result<()> {
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let key = CompositeKey::from_password("password");
+    /// let transformed_key = Database::transformed_key(&mut file, &key)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transformed_key<R: Read>(reader: &mut R, key: &CompositeKey) -> Result<TransformedKey> {
+        Database::transformed_key_with_options(reader, key, &OpenOptions::default())
+    }
+
+    /// Attempts to derive the transformed key for the database in the reader
+    /// using the specified options.
+    ///
+    /// See `OpenOptions` for the available options.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, OpenOptions};
+    /// use std::fs::File;
+    ///
+    /// # fn transformed_key_with_options_example() -> Result<()> {
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let key = CompositeKey::from_password("password");
+    /// let options = OpenOptions::new().max_transform_rounds(10000000);
+    /// let transformed_key = Database::transformed_key_with_options(&mut file, &key, &options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transformed_key_with_options<R: Read>(
+        reader: &mut R,
+        key: &CompositeKey,
+        options: &OpenOptions,
+    ) -> Result<TransformedKey> {
+        let mut reader = LogReader::with_limit(reader, options.limits.max_header_size());
+        read_signature(&mut reader)?;
+        kdb2_reader::transformed_key(&mut reader, key, options)
+    }
+
+    /// Removes the oldest history versions of all entries until the history
+    /// of each entry is within history_max_items and history_max_size and
+    /// returns the number of removed versions.
+    ///
+    /// A negative limit means that the history isn't limited by it. The size
+    /// of a version is estimated from its strings, binaries and tags, like
+    /// KeePass does.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry};
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// let mut entry = Entry::new();
+    /// for title in ["first", "second", "third"] {
+    ///     entry.set_title(title);
+    ///     entry.add_history_snapshot();
+    /// }
+    /// db.root_group.add_entry(entry);
+    /// db.history_max_items = 2;
+    ///
+    /// assert_eq!(db.trim_history(), 1);
+    /// let entry = db.entries().next().unwrap();
+    /// assert_eq!(entry.history[0].title(), Some("second"));
+    /// ```
+    pub fn trim_history(&mut self) -> usize {
+        let binaries = &self.binaries;
+        let max_items = self.history_max_items;
+        let max_size = self.history_max_size;
+        let mut count = 0;
+        for group in self.root_group.iter_mut() {
+            for entry in group.entries.iter_mut() {
+                let keep = history_to_keep(entry, binaries, max_items, max_size);
+                count += entry.history.len() - keep;
+                entry.history.drain(..entry.history.len() - keep);
+            }
+        }
+        count
+    }
+
+    /// Returns the structural problems of the database.
+    ///
+    /// Dangling binary references, duplicate identifiers, groups that repeat
+    /// the identifier of an ancestor, a missing recycle bin, entries and
+    /// groups whose parent is set but doesn't match their container and weak
+    /// transform rounds are reported. An empty vector is returned when no
+    /// problems are found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, GroupUuid, TransformRounds, ValidationIssue};
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// assert_eq!(db.validate(), vec![]);
+    ///
+    /// let uuid = GroupUuid::new_random();
+    /// db.recycle_bin_uuid = uuid;
+    /// db.transform_rounds = TransformRounds(0);
+    /// assert_eq!(
+    ///     db.validate(),
+    ///     vec![
+    ///         ValidationIssue::MissingRecycleBin(uuid),
+    ///         ValidationIssue::WeakTransformRounds(0),
+    ///     ]
+    /// );
+    /// ```
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        for entry in self.entries() {
+            collect_dangling_binaries(&self.binaries, entry, &mut issues);
+        }
+
+        let (entry_uuids, group_uuids) = self.find_duplicate_uuids();
+        issues.extend(
+            entry_uuids
+                .into_iter()
+                .map(ValidationIssue::DuplicateEntryUuid),
+        );
+        issues.extend(
+            group_uuids
+                .into_iter()
+                .map(ValidationIssue::DuplicateGroupUuid),
+        );
+
+        collect_group_cycles(&self.root_group, &mut Vec::new(), &mut issues);
+
+        if self.recycle_bin_enabled
+            && self.recycle_bin_uuid != GroupUuid::nil()
+            && self.get_group(self.recycle_bin_uuid).is_none()
+        {
+            issues.push(ValidationIssue::MissingRecycleBin(self.recycle_bin_uuid));
+        }
+
+        for group in self.root_group.iter() {
+            for entry in group.entries.iter() {
+                if entry.parent != GroupUuid::nil() && entry.parent != group.uuid {
+                    issues.push(ValidationIssue::EntryParentMismatch {
+                        entry: entry.uuid,
+                        parent: entry.parent,
+                        group: group.uuid,
+                    });
+                }
+            }
+            for sub in group.groups.iter() {
+                if sub.parent != GroupUuid::nil() && sub.parent != group.uuid {
+                    issues.push(ValidationIssue::GroupParentMismatch {
+                        uuid: sub.uuid,
+                        parent: sub.parent,
+                        group: group.uuid,
+                    });
+                }
+            }
+        }
+
+        if self.transform_rounds.0 < common::TRANSFORM_ROUNDS_MIN {
+            issues.push(ValidationIssue::WeakTransformRounds(self.transform_rounds.0));
+        }
+        issues
+    }
+
+    /// Checks whether the key can decrypt the database without reading the
+    /// whole database.
+    ///
+    /// Returns false when the key is invalid, this is much faster than
+    /// opening the database and checking for an `Error::InvalidKey`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database};
+    /// use std::fs::File;
+    ///
+    /// # fn verify_key_example() -> Result<()> {
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let key = CompositeKey::from_password("password");
+    /// if Database::verify_key(&mut file, &key)? {
+    ///     println!("The password is correct");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify_key<R: Read>(reader: &mut R, key: &CompositeKey) -> Result<bool> {
+        Database::verify_key_with_options(reader, key, &OpenOptions::default())
+    }
+
+    /// Checks whether the key can decrypt the database using the specified
+    /// options.
+    ///
+    /// See `OpenOptions` for the available options.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use kpdb::Result;
+    /// use kpdb::{CompositeKey, Database, OpenOptions};
+    /// use std::fs::File;
+    ///
+    /// # fn verify_key_with_options_example() -> Result<()> {
+    /// let mut file = File::open("passwords.kdbx")?;
+    /// let key = CompositeKey::from_password("password");
+    /// let options = OpenOptions::new().max_transform_rounds(10000000);
+    /// if Database::verify_key_with_options(&mut file, &key, &options)? {
+    ///     println!("The password is correct");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify_key_with_options<R: Read>(
+        reader: &mut R,
+        key: &CompositeKey,
+        options: &OpenOptions,
+    ) -> Result<bool> {
+        let mut reader = LogReader::with_limit(reader, options.limits.max_header_size());
+        read_signature(&mut reader)?;
+        kdb2_reader::verify_key(&mut reader, key, options)
+    }
+
+    /// Walks the groups and entries depth-first starting at the root group,
+    /// calling the visitor for each of them.
+    ///
+    /// The entries of a group are visited before its child groups. The
+    /// visitor can skip the contents of a group or stop the walk altogether
+    /// by returning the corresponding `WalkControl`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{CompositeKey, Database, Entry, Group, TreeVisitor, WalkControl};
+    ///
+    /// struct Printer(Vec<String>);
+    ///
+    /// impl TreeVisitor for Printer {
+    ///     fn enter_group(&mut self, group: &Group, depth: usize) -> WalkControl {
+    ///         self.0.push(format!("{}{}/", "  ".repeat(depth), group.name));
+    ///         WalkControl::Continue
+    ///     }
+    ///
+    ///     fn visit_entry(&mut self, entry: &Entry, depth: usize) -> WalkControl {
+    ///         let title = entry.title().unwrap_or_default();
+    ///         self.0.push(format!("{}{}", "  ".repeat(depth), title));
+    ///         WalkControl::Continue
+    ///     }
+    /// }
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.set_title("ProtonMail");
+    /// let mut group = Group::new("Email");
+    /// group.add_entry(entry);
+    ///
+    /// let mut db = Database::new(&CompositeKey::from_password("test"));
+    /// db.root_group.add_group(group);
+    ///
+    /// let mut printer = Printer(Vec::new());
+    /// db.walk(&mut printer);
+    /// assert_eq!(printer.0, vec!["Root/", "  Email/", "    ProtonMail"]);
+    /// ```
+    pub fn walk<V: TreeVisitor>(&self, visitor: &mut V) {
+        walk_group(&self.root_group, 0, visitor);
+    }
+
+    fn add_binary(&mut self, data: Vec<u8>) -> BinaryId {
+        if let Some((id, _)) = self.binaries.iter().find(|(_, v)| **v == data) {
+            return id.clone();
+        }
+        let id = self.next_binary_id();
+        self.binaries.insert(id.clone(), data);
+        id
+    }
+
+    fn open_kdb2<R: Log + Read>(
+        reader: &mut R,
+        key: &CompositeKey,
+        options: &OpenOptions,
+    ) -> Result<(Database, Vec<RecoveredError>)> {
+        let (meta_data, mut xml_data) = kdb2_reader::read(reader, key, options)?;
+        let recovered = mem::take(&mut xml_data.recovered);
+        let db = Database::from_kdb2(key, meta_data, xml_data)?;
+        Ok((db, recovered))
+    }
+
+    fn open_kdb2_with_transformed_key<R: Log + Read>(
+        reader: &mut R,
+        key: &CompositeKey,
+        transformed_key: &TransformedKey,
+        options: &OpenOptions,
+    ) -> Result<Database> {
+        let (meta_data, xml_data) =
+            kdb2_reader::read_with_transformed_key(reader, transformed_key, options)?;
+        Database::from_kdb2(key, meta_data, xml_data)
+    }
+
+    fn from_kdb2(key: &CompositeKey, meta_data: MetaData, xml_data: XmlData) -> Result<Database> {
+        check_header_hash(&meta_data, &xml_data)?;
+
+        let root_group = match xml_data.root_group {
+            Some(group) => group,
+            None => Group::new(common::ROOT_GROUP_NAME),
+        };
+
+        let db = Database {
+            comment: meta_data.comment,
+            composite_key: key.clone(),
+            compression: meta_data.compression,
+            db_type: DbType::Kdb2,
+            master_cipher: meta_data.master_cipher,
+            stream_cipher: meta_data.stream_cipher,
+            transform_rounds: meta_data.transform_rounds,
+            version: meta_data.version,
+
+            binaries: xml_data.binaries,
+            color: xml_data.color,
+            custom_data: xml_data.custom_data,
+            custom_icons: xml_data.custom_icons,
+            def_username: xml_data.def_username,
+            def_username_changed: xml_data.def_username_changed,
+            deleted_objects: xml_data.deleted_objects,
+            description: xml_data.description,
+            description_changed: xml_data.description_changed,
+            entry_templates_group_changed: xml_data.entry_templates_group_changed,
+            entry_templates_group_uuid: xml_data.entry_templates_group_uuid,
+            generator: xml_data.generator,
+            history_max_items: xml_data.history_max_items,
+            history_max_size: xml_data.history_max_size,
+            last_selected_group: xml_data.last_selected_group,
+            last_top_visible_group: xml_data.last_top_visible_group,
+            maintenance_history_days: xml_data.maintenance_history_days,
+            master_key_change_force: xml_data.master_key_change_force,
+            master_key_change_rec: xml_data.master_key_change_rec,
+            master_key_changed: xml_data.master_key_changed,
+            name: xml_data.name,
+            name_changed: xml_data.name_changed,
+            protect_notes: xml_data.protect_notes,
+            protect_password: xml_data.protect_password,
+            protect_title: xml_data.protect_title,
+            protect_url: xml_data.protect_url,
+            protect_username: xml_data.protect_username,
+            recycle_bin_changed: xml_data.recycle_bin_changed,
+            recycle_bin_enabled: xml_data.recycle_bin_enabled,
+            recycle_bin_uuid: xml_data.recycle_bin_uuid,
+            root_group: root_group,
+            settings_changed: xml_data.settings_changed,
+        };
+
+        Ok(db)
+    }
+}
+
+fn check_header_hash(meta_data: &MetaData, xml_data: &XmlData) -> Result<()> {
+    if meta_data.version.has_xml_header_hash() {
+        check_xml_header_hash(meta_data, xml_data)
+    } else {
+        check_header_hmac(meta_data)
+    }
+}
+
+fn check_xml_header_hash(meta_data: &MetaData, xml_data: &XmlData) -> Result<()> {
+    // Some writers omit the header hash. The stream start bytes, which have
+    // already been verified by the reader, then only prove that the key,
+    // seeds and IV are right. The other headers (compression, protected
+    // stream key and inner random stream) are unauthenticated.
+    match xml_data.header_hash {
+        Some(ref header_hash) if *header_hash != meta_data.header_hash => {
+            Err(Error::InvalidHeaderHash)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_header_hmac(meta_data: &MetaData) -> Result<()> {
+    // Version 4 protects the headers with an HMAC that is stored after the
+    // headers instead of the header hash in the XML data, a header hash
+    // element is ignored just like KeePass does. The HMAC isn't read yet, so
+    // the headers can't be verified and the database is rejected.
+    Err(Error::UnhandledVersion(meta_data.version.clone()))
+}
+
+fn check_master_cipher(cipher: &MasterCipher) -> Result<()> {
+    match *cipher {
+        MasterCipher::Aes256 => Ok(()),
+    }
+}
+
+fn check_stream_cipher(cipher: &StreamCipher) -> Result<()> {
+    match *cipher {
+        StreamCipher::Salsa20 => Ok(()),
+    }
+}
+
+fn collect_binary_refs(entry: &Entry, refs: &mut HashSet<BinaryId>) {
+    for value in entry.binaries.values() {
+        if let BinaryValue::Ref(ref id) = *value {
+            refs.insert(id.clone());
+        }
+    }
+    for old in entry.history.iter() {
+        collect_binary_refs(old, refs);
+    }
+}
+
+fn collect_custom_icon_refs(entry: &Entry, refs: &mut HashSet<CustomIconUuid>) {
+    refs.extend(entry.custom_icon_uuid);
+    for old in entry.history.iter() {
+        collect_custom_icon_refs(old, refs);
+    }
+}
+
+fn collect_dangling_binaries(
+    binaries: &BinariesMap,
+    entry: &Entry,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    for value in entry.binaries.values() {
+        if let BinaryValue::Ref(ref id) = *value {
+            if !binaries.contains_key(id) {
+                issues.push(ValidationIssue::DanglingBinary {
+                    entry: entry.uuid,
+                    id: id.clone(),
+                });
+            }
+        }
+    }
+    for old in entry.history.iter() {
+        collect_dangling_binaries(binaries, old, issues);
+    }
+}
+
+fn collect_entries_outside_recycle_bin<'a>(
+    db: &Database,
+    group: &'a Group,
+    entries: &mut Vec<&'a Entry>,
+) {
+    if db.is_recycle_bin(group.uuid) {
+        return;
+    }
+    entries.extend(group.entries.iter());
+    for child in &group.groups {
+        collect_entries_outside_recycle_bin(db, child, entries);
+    }
+}
+
+fn collect_entries_with_path<'a>(
+    group: &'a Group,
+    path: String,
+    list: &mut Vec<(String, &'a Entry)>,
+) {
+    for entry in group.entries.iter() {
+        list.push((path.clone(), entry));
+    }
+    for sub in group.groups.iter() {
+        let sub_path = if path.is_empty() {
+            sub.name.clone()
+        } else {
+            format!("{}/{}", path, sub.name)
+        };
+        collect_entries_with_path(sub, sub_path, list);
+    }
+}
+
+fn collect_group_cycles(
+    group: &Group,
+    ancestors: &mut Vec<GroupUuid>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    if ancestors.contains(&group.uuid) {
+        issues.push(ValidationIssue::GroupCycle(group.uuid));
+    }
+    ancestors.push(group.uuid);
+    for sub in group.groups.iter() {
+        collect_group_cycles(sub, ancestors, issues);
+    }
+    ancestors.pop();
+}
+
+fn enforce_entry_protection(entry: &mut Entry, flags: &[(StringKey, bool)]) {
+    for (key, protected) in flags.iter() {
+        if let Some(value) = entry.strings.get_mut(key) {
+            let is_protected = matches!(*value, StringValue::Protected(_));
+            if is_protected != *protected {
+                let text = value.reveal().into_owned();
+                *value = StringValue::new(text, *protected);
+            }
+        }
+    }
+    for old in entry.history.iter_mut() {
+        enforce_entry_protection(old, flags);
+    }
+}
+
+fn entry_contains_string(entry: &Entry, name: &str) -> bool {
+    for (key, value) in entry.strings.iter() {
+        if *key != StringKey::Password && utils::fold(&value.reveal()).contains(name) {
+            return true;
+        }
+    }
+    false
+}
+
+// Estimates the size of a history version from the lengths of its strings,
+// binaries and tags.
+fn entry_size(entry: &Entry, binaries: &BinariesMap) -> usize {
+    let strings: usize = entry
+        .strings
+        .iter()
+        .map(|(key, val)| key.to_string().len() + val.reveal().len())
+        .sum();
+    let binaries: usize = entry
+        .binaries
+        .iter()
+        .map(|(key, val)| {
+            key.0.len()
+                + match *val {
+                    BinaryValue::Plain(ref data) => data.len(),
+                    BinaryValue::Protected(ref sec) => sec.unsecure().len(),
+                    BinaryValue::Ref(ref id) => binaries.get(id).map_or(0, |data| data.len()),
+                }
+        })
+        .sum();
+    strings + binaries + entry.override_url.len() + entry.tags.len()
+}
+
+fn for_each_group_entry_mut<F: FnMut(&mut Entry)>(group: &mut Group, f: &mut F) {
+    for entry in group.entries.iter_mut() {
+        f(entry);
+    }
+    for sub in group.groups.iter_mut() {
+        for_each_group_entry_mut(sub, f);
+    }
+}
+
+fn get_group_by_segments<'a>(group: &'a Group, segments: &[&str]) -> Option<&'a Group> {
+    match segments.split_first() {
+        Some((first, rest)) => {
+            let name = first.to_lowercase();
+            let mut matches = group
+                .groups
+                .iter()
+                .filter(|g| g.name.to_lowercase() == name);
+            match (matches.next(), matches.next()) {
+                (Some(sub), None) => get_group_by_segments(sub, rest),
+                _ => None,
+            }
+        }
+        None => Some(group),
+    }
+}
+
+fn group_chain<'a>(group: &'a Group, uuid: GroupUuid, chain: &mut Vec<&'a Group>) -> bool {
+    chain.push(group);
+    if group.uuid == uuid || group.groups.iter().any(|g| group_chain(g, uuid, chain)) {
+        return true;
+    }
+    chain.pop();
+    false
+}
+
+fn group_depth(group: &Group) -> usize {
+    group
+        .groups
+        .iter()
+        .map(|g| group_depth(g) + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+// Returns the number of newest history versions that fit within the limits.
+fn history_to_keep(entry: &Entry, binaries: &BinariesMap, max_items: i32, max_size: i32) -> usize {
+    let mut keep = entry.history.len();
+    if max_items >= 0 {
+        keep = keep.min(max_items as usize);
+    }
+    if max_size >= 0 {
+        let mut size = 0;
+        for (count, old) in entry.history.iter().rev().take(keep).enumerate() {
+            size += entry_size(old, binaries);
+            if size > max_size as usize {
+                keep = count;
+                break;
+            }
+        }
+    }
+    keep
+}
+
+fn inherited_setting<F>(root: &Group, uuid: GroupUuid, setting: F) -> bool
+where
+    F: Fn(&Group) -> Option<bool>,
+{
+    let mut chain = Vec::new();
+    if !group_chain(root, uuid, &mut chain) {
+        return false;
+    }
+    chain.iter().rev().find_map(|g| setting(g)).unwrap_or(true)
+}
+
+fn read_signature<R: Read>(reader: &mut R) -> Result<()> {
+    let mut buffer = [0u8; 4];
+
+    reader.read_exact(&mut buffer)?;
+    if buffer != common::DB_SIGNATURE {
+        return Err(Error::InvalidDbSignature(buffer));
+    }
+
+    reader.read_exact(&mut buffer)?;
+    if buffer == common::KDB1_SIGNATURE {
+        Err(Error::UnhandledDbType(buffer))
+    } else if buffer == common::KDB2_SIGNATURE {
+        Ok(())
+    } else {
+        Err(Error::UnhandledDbType(buffer))
+    }
+}
+
+fn reference_key(code: &str) -> Option<StringKey> {
+    match code {
+        "T" | "t" => Some(StringKey::Title),
+        "U" | "u" => Some(StringKey::Username),
+        "P" | "p" => Some(StringKey::Password),
+        "A" | "a" => Some(StringKey::Url),
+        "N" | "n" => Some(StringKey::Notes),
+        _ => None,
+    }
+}
+
+fn referenced_group(db: &Database, uuid: GroupUuid) -> Option<&Group> {
+    if uuid == GroupUuid::nil() {
+        None
+    } else {
+        db.get_group(uuid)
+    }
+}
+
+fn resolve_reference(
+    db: &Database,
+    spec: &str,
+    depth: usize,
+    budget: &mut usize,
+) -> Option<String> {
+    let (wanted, search) = spec.split_once('@')?;
+    let (search_in, text) = search.split_once(':')?;
+    let target = match search_in {
+        "I" | "i" => db.get_entry(EntryUuid(Uuid::parse_str(text).ok()?))?,
+        _ => {
+            let key = reference_key(search_in)?;
+            let text = utils::fold(text);
+            db.entries().find(|e| match e.strings.get(&key) {
+                Some(value) => utils::fold(&value.reveal()).contains(&text),
+                None => false,
+            })?
+        }
+    };
+    let value = match wanted {
+        "I" | "i" => target.uuid.0.simple().to_string().to_uppercase(),
+        _ => target
+            .strings
+            .get(&reference_key(wanted)?)?
+            .reveal()
+            .into_owned(),
+    };
+    Some(resolve_text(db, &value, depth - 1, budget))
+}
+
+// The budget limits the total number of resolved references, so references
+// that each refer to several others can't expand exponentially within the
+// maximum depth.
+fn resolve_text(db: &Database, text: &str, depth: usize, budget: &mut usize) -> String {
+    if depth == 0 {
+        return String::from(text);
+    }
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(REFERENCE_START) {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        if *budget == 0 {
+            break;
+        }
+        *budget -= 1;
+        let spec = &rest[start + REFERENCE_START.len()..start + len];
+        result.push_str(&rest[..start]);
+        match resolve_reference(db, spec, depth, budget) {
+            Some(value) => result.push_str(&value),
+            None => result.push_str(&rest[start..=start + len]),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+fn path_segments(path: &str) -> Vec<&str> {
+    path.split('/').filter(|s| !s.is_empty()).collect()
+}
+
+fn walk_group<V: TreeVisitor>(group: &Group, depth: usize, visitor: &mut V) -> bool {
+    match visitor.enter_group(group, depth) {
+        WalkControl::Stop => return false,
+        WalkControl::SkipSubtree => {}
+        WalkControl::Continue => {
+            for entry in &group.entries {
+                match visitor.visit_entry(entry, depth + 1) {
+                    WalkControl::Stop => return false,
+                    WalkControl::SkipSubtree => break,
+                    WalkControl::Continue => {}
+                }
+            }
+            for child in &group.groups {
+                if !walk_group(child, depth + 1, visitor) {
+                    return false;
+                }
+            }
+        }
+    }
+    visitor.leave_group(group, depth) != WalkControl::Stop
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::crypto::sha256;
+    use crate::types::BinariesMap;
+    use crate::types::CompositeKey;
+    use crate::types::Compression;
+    use crate::types::CustomDataMap;
+    use crate::types::CustomIconsMap;
+    use crate::types::DbType;
+    use crate::types::GroupUuid;
+    use crate::types::HeaderHash;
+    use crate::types::MasterCipher;
+    use crate::types::StreamCipher;
+    use crate::types::StringValue;
+    use crate::types::TransformRounds;
+    use crate::types::Version;
+    use crate::utils::test::approx_equal_datetime;
+    use chrono::{Duration, Utc};
+    use secstr::SecStr;
+
+    #[test]
+    fn test_new_returns_correct_instance() {
+        let now = Utc::now();
+        let key = CompositeKey::from_password("5pZ5mgpTkLCDaM46IuH7yGafZFIICyvC");
+        let db = Database::new(&key);
+        assert_eq!(db.comment, None);
+        assert_eq!(db.composite_key, key);
+        assert_eq!(db.compression, Compression::GZip);
+        assert_eq!(db.db_type, DbType::Kdb2);
+        assert_eq!(db.master_cipher, MasterCipher::Aes256);
+        assert_eq!(db.stream_cipher, StreamCipher::Salsa20);
+        assert_eq!(db.transform_rounds, TransformRounds(10000));
+        assert_eq!(db.version, Version::new_kdb2());
+        assert_eq!(db.binaries, BinariesMap::new());
+        assert_eq!(db.color, None);
+        assert_eq!(db.custom_data, CustomDataMap::new());
+        assert_eq!(db.custom_icons, CustomIconsMap::new());
+        assert_eq!(db.def_username, "");
+        assert!(approx_equal_datetime(db.def_username_changed, now));
+        assert_eq!(db.deleted_objects, Vec::new());
+        assert_eq!(db.description, "");
+        assert!(approx_equal_datetime(db.description_changed, now));
+        assert!(approx_equal_datetime(db.entry_templates_group_changed, now));
+        assert_eq!(db.entry_templates_group_uuid, GroupUuid::nil());
+        assert_eq!(db.generator, "rust-kpdb");
+        assert_eq!(db.history_max_items, 10);
+        assert_eq!(db.history_max_size, 6291456);
+        assert_eq!(db.last_selected_group, GroupUuid::nil());
+        assert_eq!(db.last_top_visible_group, GroupUuid::nil());
+        assert_eq!(db.maintenance_history_days, 365);
+        assert_eq!(db.master_key_change_force, -1);
+        assert_eq!(db.master_key_change_rec, -1);
+        assert!(approx_equal_datetime(db.master_key_changed, now));
+        assert_eq!(db.name, "");
+        assert!(approx_equal_datetime(db.name_changed, now));
+        assert_eq!(db.protect_notes, false);
+        assert_eq!(db.protect_password, true);
+        assert_eq!(db.protect_title, false);
+        assert_eq!(db.protect_url, false);
+        assert_eq!(db.protect_username, false);
+        assert!(approx_equal_datetime(db.recycle_bin_changed, now));
+        assert_eq!(db.recycle_bin_enabled, true);
+        assert_eq!(db.recycle_bin_uuid, GroupUuid::nil());
+        assert!(db.root_group.uuid != GroupUuid::nil());
+        assert_eq!(db.settings_changed, None);
+    }
+
+    #[test]
+    fn test_find_duplicate_uuids_returns_duplicates() {
+        let mut db = db_with_groups_and_entries();
+        assert_eq!(db.find_duplicate_uuids(), (Vec::new(), Vec::new()));
+
+        let entry = db.root_group.groups[0].entries[0].clone();
+        let group = Group::new("Duplicate");
+        db.root_group.add_entry(entry.clone());
+        db.root_group.add_entry(entry.clone());
+        db.root_group.add_group(group.clone());
+        db.root_group.groups[1].add_group(group.clone());
+
+        assert_eq!(db.find_duplicate_uuids(), (vec![entry.uuid], vec![group.uuid]));
+    }
+
+    #[test]
+    fn test_add_custom_icon_deduplicates_data() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        let a = db.add_custom_icon(vec![1, 2, 3]);
+        let b = db.add_custom_icon(vec![4, 5, 6]);
+        assert!(a != b);
+        assert_eq!(db.add_custom_icon(vec![1, 2, 3]), a);
+        assert_eq!(db.custom_icons.len(), 2);
+        assert_eq!(db.custom_icons.get(&b), Some(&vec![4, 5, 6]));
+    }
+
+    #[test]
+    fn test_dedup_uuids_repairs_duplicates() {
+        let mut db = db_with_groups_and_entries();
+        assert_eq!(db.dedup_uuids(), 0);
+
+        let mut entry = db.root_group.groups[0].entries[0].clone();
+        entry.history.push(entry.clone());
+        let mut group = Group::new("Duplicate");
+        group.add_entry(Entry::new());
+        db.root_group.groups[1].add_entry(entry.clone());
+        db.root_group.add_group(group.clone());
+        db.root_group.groups[1].add_group(group.clone());
+
+        assert_eq!(db.dedup_uuids(), 3);
+        assert_eq!(db.find_duplicate_uuids(), (Vec::new(), Vec::new()));
+        assert_eq!(db.root_group.groups[0].entries[0].uuid, entry.uuid);
+        assert_eq!(db.root_group.groups[2].uuid, group.uuid);
+
+        let copy = &db.root_group.groups[1].entries[1];
+        assert!(copy.uuid != entry.uuid);
+        assert_eq!(copy.history[0].uuid, copy.uuid);
+
+        let copy = &db.root_group.groups[1].groups[0];
+        assert!(copy.uuid != group.uuid);
+        assert_eq!(copy.entries[0].parent, copy.uuid);
+    }
+
+    #[test]
+    fn test_deleted_objects_are_saved() {
+        let key = CompositeKey::from_password("test");
+        let mut db = Database::new(&key);
+        let time = "2017-01-02T03:04:05Z".parse::<DateTime<Utc>>().unwrap();
+        let uuid = EntryUuid::new_random().0;
+        db.deleted_objects.push((uuid, time));
+
+        let mut buffer = Vec::new();
+        db.save(&mut buffer).unwrap();
+        let db = Database::open(&mut &buffer[..], &key).unwrap();
+        assert_eq!(db.deleted_objects, vec![(uuid, time)]);
+    }
+
+    #[test]
+    fn test_empty_recycle_bin_removes_entries_and_groups() {
+        let mut db = db_with_groups_and_entries();
+        let binary_key = BinaryKey(String::from("file"));
+        let shared_id = BinaryId(String::from("0"));
+        let trashed_id = BinaryId(String::from("1"));
+        db.binaries.insert(shared_id.clone(), vec![0]);
+        db.binaries.insert(trashed_id.clone(), vec![1]);
+        db.root_group.groups[0].entries[0]
+            .binaries
+            .insert(binary_key.clone(), BinaryValue::Ref(shared_id.clone()));
+
+        let mut shared = Entry::new();
+        shared
+            .binaries
+            .insert(binary_key.clone(), BinaryValue::Ref(shared_id.clone()));
+        let mut trashed = Entry::new();
+        trashed
+            .binaries
+            .insert(binary_key.clone(), BinaryValue::Ref(trashed_id.clone()));
+        let mut sub_group = Group::new("Sub");
+        sub_group.add_entry(trashed);
+
+        let mut recycle_bin = Group::new("Recycle Bin");
+        recycle_bin.add_entry(shared);
+        recycle_bin.add_group(sub_group);
+        db.recycle_bin_uuid = recycle_bin.uuid;
+        db.root_group.add_group(recycle_bin);
+
+        assert_eq!(db.empty_recycle_bin(), 3);
+        assert_eq!(db.deleted_objects.len(), 3);
+        assert_eq!(db.root_group.groups[2].entries.len(), 0);
+        assert_eq!(db.root_group.groups[2].groups.len(), 0);
+        assert!(db.binaries.contains_key(&shared_id));
+        assert!(!db.binaries.contains_key(&trashed_id));
+    }
+
+    #[test]
+    fn test_empty_recycle_bin_without_recycle_bin_returns_zero() {
+        let mut db = db_with_groups_and_entries();
+        assert_eq!(db.empty_recycle_bin(), 0);
+
+        db.recycle_bin_uuid = GroupUuid::new_random();
+        assert_eq!(db.empty_recycle_bin(), 0);
+        assert_eq!(db.entries().count(), 3);
+    }
+
+    #[test]
+    fn test_entries_returns_all_entries() {
+        let db = db_with_groups_and_entries();
+        assert_eq!(db.entries().count(), 3);
+    }
+
+    #[test]
+    fn test_entries_for_url_returns_entries_with_same_host() {
+        let db = db_with_groups_and_entries();
+        let result = db.entries_for_url("http://Mail.ProtonMail.com:80/inbox");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].title(), Some("ProtonMail"));
+        assert!(db.entries_for_url("https://protonmail.com").is_empty());
+        assert!(db.entries_for_url("").is_empty());
+    }
+
+    #[test]
+    fn test_entries_in_group_returns_correct_entries() {
+        let mut db = db_with_groups_and_entries();
+        let mut sub_group = Group::new("Sub");
+        sub_group.add_entry(Entry::new());
+        db.root_group.groups[0].add_group(sub_group);
+        let uuid = db.root_group.groups[0].uuid;
+
+        assert_eq!(db.entries_in_group(uuid, false).len(), 2);
+        assert_eq!(db.entries_in_group(uuid, true).len(), 3);
+        assert_eq!(db.entries_in_group(db.root_group.uuid, false).len(), 0);
+        assert_eq!(db.entries_in_group(db.root_group.uuid, true).len(), 4);
+    }
+
+    #[test]
+    fn test_entries_in_group_with_unknown_group_returns_empty_vec() {
+        let db = db_with_groups_and_entries();
+        assert!(db
+            .entries_in_group(GroupUuid::new_random(), true)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_entries_mut_returns_all_entries() {
+        let mut db = db_with_groups_and_entries();
+        for entry in db.entries_mut() {
+            entry.set_notes("notes");
+        }
+        assert!(db.entries().all(|e| e.notes() == Some("notes")));
+    }
+
+    #[test]
+    fn test_entries_with_path_returns_correct_paths() {
+        let mut db = db_with_groups_and_entries();
+        let mut sub_group = Group::new("Sub");
+        sub_group.add_entry(Entry::new());
+        db.root_group.groups[0].add_group(sub_group);
+        db.root_group.add_entry(Entry::new());
+
+        let paths: Vec<String> = db.entries_with_path().map(|(p, _)| p).collect();
+        assert_eq!(paths, vec!["", "Email", "Email", "Email/Sub", "VPN"]);
+    }
+
+    #[test]
+    fn test_find_entries_returns_correct_entries() {
+        let db = db_with_groups_and_entries();
+        let result = db.find_entries("Proton");
+        assert_eq!(result.len(), 2);
+
+        let result = db.find_entries("Unknown");
+        assert_eq!(result.len(), 0);
+    }
+
+    #[test]
+    fn test_find_entries_matches_protected_values() {
+        let mut db = db_with_groups_and_entries();
+        let mut entry = Entry::new();
+        entry
+            .strings
+            .insert(StringKey::Title, StringValue::new("Secret Title", true));
+        db.root_group.add_entry(entry);
+
+        let result = db.find_entries("secret");
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn test_find_entries_does_not_match_passwords() {
+        let db = db_with_groups_and_entries();
+        let result = db.find_entries("gpass");
+        assert_eq!(result.len(), 0);
+    }
+
+    #[test]
+    fn test_find_entries_mut_returns_correct_entries() {
+        let mut db = db_with_groups_and_entries();
+        {
+            let result = db.find_entries_mut("Proton");
+            assert_eq!(result.len(), 2);
+        }
+        {
+            let result = db.find_entries_mut("Unknown");
+            assert_eq!(result.len(), 0);
+        }
+    }
+
+    #[test]
+    fn test_find_groups_returns_correct_groups() {
+        let db = db_with_groups_and_entries();
+        let result = db.find_groups("mail");
+        assert_eq!(result.len(), 1);
+
+        let result = db.find_groups("Unknown");
+        assert_eq!(result.len(), 0);
+    }
+
+    #[test]
+    fn test_find_groups_mut_returns_correct_groups() {
+        let mut db = db_with_groups_and_entries();
+        {
+            let result = db.find_groups_mut("mail");
+            assert_eq!(result.len(), 1);
+        }
+        {
+            let result = db.find_groups_mut("Unknown");
+            assert_eq!(result.len(), 0);
+        }
+    }
+
+    #[test]
+    fn test_for_each_entry_mut_visits_all_entries() {
+        let mut db = db_with_groups_and_entries();
+        let mut count = 0;
+        db.for_each_entry_mut(|entry| {
+            entry.usage_count += 1;
+            count += 1;
+        });
+        assert_eq!(count, db.entries().count());
+        assert!(db.entries().all(|entry| entry.usage_count == 1));
+    }
+
+    #[test]
+    fn test_gc_custom_icons_removes_unused_icons() {
+        let mut db = db_with_groups_and_entries();
+        let group_icon = db.add_custom_icon(vec![1]);
+        let entry_icon = db.add_custom_icon(vec![2]);
+        let history_icon = db.add_custom_icon(vec![3]);
+        db.add_custom_icon(vec![4]);
+        db.add_custom_icon(vec![5]);
+
+        db.root_group.groups[1].custom_icon_uuid = Some(group_icon);
+        let mut old = db.root_group.groups[0].entries[0].clone();
+        old.custom_icon_uuid = Some(history_icon);
+        db.root_group.groups[0].entries[0].history.push(old);
+        db.root_group.groups[0].entries[1].custom_icon_uuid = Some(entry_icon);
+
+        assert_eq!(db.gc_custom_icons(), 2);
+        assert_eq!(db.custom_icons.len(), 3);
+        assert!(db.custom_icons.contains_key(&group_icon));
+        assert!(db.custom_icons.contains_key(&entry_icon));
+        assert!(db.custom_icons.contains_key(&history_icon));
+        assert_eq!(db.gc_custom_icons(), 0);
+    }
+
+    #[test]
+    fn test_get_custom_data_returns_correct_value() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.custom_data
+            .insert(String::from("key"), String::from("value"));
+        assert_eq!(db.get_custom_data("key"), Some("value"));
+        assert_eq!(db.get_custom_data("other"), None);
+    }
+
+    #[test]
+    fn test_contains_entry_and_group_find_nested_items() {
+        let entry = Entry::new();
+        let entry_uuid = entry.uuid;
+
+        let mut group = Group::new("Group");
+        let group_uuid = group.uuid;
+        group.add_entry(entry);
+
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.root_group.add_group(group);
+        assert!(db.contains_entry(entry_uuid));
+        assert!(db.contains_group(group_uuid));
+        assert!(db.contains_group(db.root_group.uuid));
+        assert!(!db.contains_entry(EntryUuid::new_random()));
+        assert!(!db.contains_group(GroupUuid::new_random()));
+    }
+
+    #[test]
+    fn test_get_entry_returns_correct_entry() {
+        let entry = Entry::new();
+        let entry_uuid = entry.uuid;
+
+        let mut group = Group::new("Group");
+        group.add_entry(entry.clone());
+
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        assert_eq!(db.get_entry(entry_uuid), None);
+
+        db.root_group.add_group(group);
+        assert_eq!(db.get_entry(entry_uuid), Some(&entry));
+    }
+
+    #[test]
+    fn test_get_entry_by_path_returns_correct_entry() {
+        let db = db_with_groups_and_entries();
+        let expected = &db.root_group.groups[0].entries[1];
+        assert_eq!(db.get_entry_by_path("Email/ProtonMail"), Some(expected));
+        assert_eq!(db.get_entry_by_path("/email/protonmail/"), Some(expected));
+        assert_eq!(db.get_entry_by_path("Email"), None);
+        assert_eq!(db.get_entry_by_path("VPN/ProtonMail"), None);
+        assert_eq!(db.get_entry_by_path(""), None);
+    }
+
+    #[test]
+    fn test_get_entry_by_path_with_ambiguous_title_returns_none() {
+        let mut db = db_with_groups_and_entries();
+        let mut entry = Entry::new();
+        entry.set_title("gmail");
+        db.root_group.groups[0].add_entry(entry);
+        assert_eq!(db.get_entry_by_path("Email/Gmail"), None);
+    }
+
+    #[test]
+    fn test_get_group_by_path_returns_correct_group() {
+        let mut db = db_with_groups_and_entries();
+        db.root_group.groups[1].add_group(Group::new("Work"));
+        let expected = &db.root_group.groups[1].groups[0];
+        assert_eq!(db.get_group_by_path("VPN/Work"), Some(expected));
+        assert_eq!(db.get_group_by_path("vpn/work"), Some(expected));
+        assert_eq!(db.get_group_by_path(""), Some(&db.root_group));
+        assert_eq!(db.get_group_by_path("Email/Work"), None);
+    }
+
+    #[test]
+    fn test_get_group_by_path_with_ambiguous_name_returns_none() {
+        let mut db = db_with_groups_and_entries();
+        db.root_group.add_group(Group::new("email"));
+        assert_eq!(db.get_group_by_path("Email"), None);
+    }
+
+    #[test]
+    fn test_get_entry_mut_returns_correct_entry() {
+        let mut entry = Entry::new();
+        let entry_uuid = entry.uuid;
+
+        let mut group = Group::new("Group");
+        group.add_entry(entry.clone());
+
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        assert_eq!(db.get_entry_mut(entry_uuid), None);
+
+        db.root_group.add_group(group);
+        assert_eq!(db.get_entry_mut(entry_uuid), Some(&mut entry));
+    }
+
+    #[test]
+    fn test_get_group_returns_correct_group() {
+        let group = Group::new("Group");
+        let group_uuid = group.uuid;
+
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        assert_eq!(db.get_group(group_uuid), None);
+
+        db.root_group.add_group(group.clone());
+        assert_eq!(db.get_group(group_uuid), Some(&group));
+    }
+
+    #[test]
+    fn test_get_group_mut_returns_correct_group() {
+        let mut group = Group::new("Group");
+        let group_uuid = group.uuid;
+
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        assert_eq!(db.get_group_mut(group_uuid), None);
+
+        db.root_group.add_group(group.clone());
+        assert_eq!(db.get_group_mut(group_uuid), Some(&mut group));
+    }
+
+    #[test]
+    fn test_is_recycle_bin_with_nil_uuid_returns_false() {
+        let db = Database::new(&CompositeKey::from_password("test"));
+        assert!(!db.is_recycle_bin(GroupUuid::nil()));
+        assert!(!db.is_recycle_bin(db.root_group.uuid));
+    }
+
+    #[test]
+    fn test_is_auto_type_enabled_resolves_inherited_setting() {
+        let mut db = db_with_groups_and_entries();
+        let mut sub_group = Group::new("Sub");
+        sub_group.enable_auto_type = Some(true);
+        let sub_uuid = sub_group.uuid;
+        db.root_group.groups[0].add_group(sub_group);
+        db.root_group.groups[0].enable_auto_type = Some(false);
+        let email_uuid = db.root_group.groups[0].uuid;
+        let vpn_uuid = db.root_group.groups[1].uuid;
+
+        assert!(db.is_auto_type_enabled(db.root_group.uuid));
+        assert!(!db.is_auto_type_enabled(email_uuid));
+        assert!(db.is_auto_type_enabled(sub_uuid));
+        assert!(db.is_auto_type_enabled(vpn_uuid));
+        assert!(!db.is_auto_type_enabled(GroupUuid::new_random()));
+    }
+
+    #[test]
+    fn test_is_searchable_resolves_inherited_setting() {
+        let mut db = db_with_groups_and_entries();
+        let mut sub_group = Group::new("Sub");
+        sub_group.enable_searching = Some(true);
+        let sub_uuid = sub_group.uuid;
+        db.root_group.groups[0].add_group(sub_group);
+        db.root_group.groups[0].enable_searching = Some(false);
+        let email_uuid = db.root_group.groups[0].uuid;
+        let vpn_uuid = db.root_group.groups[1].uuid;
+
+        assert!(db.is_searchable(db.root_group.uuid));
+        assert!(!db.is_searchable(email_uuid));
+        assert!(db.is_searchable(sub_uuid));
+        assert!(db.is_searchable(vpn_uuid));
+
+        db.root_group.enable_searching = Some(false);
+        assert!(!db.is_searchable(vpn_uuid));
+        assert!(db.is_searchable(sub_uuid));
+    }
+
+    #[test]
+    fn test_is_searchable_with_unknown_group_returns_false() {
+        let db = db_with_groups_and_entries();
+        assert!(!db.is_searchable(GroupUuid::new_random()));
+    }
+
+    #[test]
+    fn test_kdf_params_returns_params_of_opened_database() {
+        let key = CompositeKey::from_password("test");
+        let mut db = Database::new(&key);
+        db.transform_rounds = TransformRounds(1234);
+
+        let mut buffer = Vec::new();
+        db.save(&mut buffer).unwrap();
+        let db = Database::open(&mut &buffer[..], &key).unwrap();
+        assert_eq!(
+            db.kdf_params(),
+            KdfParams::Aes {
+                rounds: TransformRounds(1234)
+            }
+        );
+    }
+
+    #[test]
+    fn test_last_selected_group_ref_resolves_group() {
+        let mut db = db_with_groups_and_entries();
+        assert_eq!(db.last_selected_group_ref(), None);
+        db.last_selected_group = db.root_group.groups[1].uuid;
+        assert_eq!(db.last_selected_group_ref().unwrap().name, "VPN");
+        db.last_selected_group = GroupUuid::new_random();
+        assert_eq!(db.last_selected_group_ref(), None);
+    }
+
+    #[test]
+    fn test_group_refs_with_nil_uuid_return_none() {
+        let mut db = db_with_groups_and_entries();
+        db.root_group.uuid = GroupUuid::nil();
+        assert_eq!(db.entry_templates_group_ref(), None);
+        assert_eq!(db.last_selected_group_ref(), None);
+        assert_eq!(db.last_top_visible_group_ref(), None);
+        db.last_top_visible_group = db.root_group.groups[0].uuid;
+        assert_eq!(db.last_top_visible_group_ref().unwrap().name, "Email");
+        db.entry_templates_group_uuid = db.root_group.groups[0].uuid;
+        assert_eq!(db.entry_templates_group_ref().unwrap().name, "Email");
+    }
+
+    #[test]
+    fn test_from_plaintext_xml_reads_exported_database() {
+        let mut db = db_with_groups_and_entries();
+        db.name = String::from("Passwords");
+        let xml = db.to_keepass_xml_pretty(true).unwrap();
+
+        let key = CompositeKey::from_password("other");
+        let copy = Database::from_plaintext_xml(&xml, &key).unwrap();
+        assert_eq!(copy.composite_key, key);
+        assert_eq!(copy.name, "Passwords");
+        assert!(copy.root_group.content_eq(&db.root_group));
+        let entry = &copy.root_group.groups[0].entries[0];
+        assert_eq!(entry.strings[&StringKey::Password], StringValue::new("gpass", true));
+    }
+
+    #[test]
+    fn test_from_xml_with_invalid_xml_returns_error() {
+        let stream_key = StreamKey::new(&ProtectedStreamKey([0u8; 32]));
+        let key = CompositeKey::from_password("test");
+        match Database::from_xml("<Other/>", &key, &stream_key) {
+            Err(Error::XmlError(_)) => (),
+            _ => panic!("expected XML error"),
+        }
+    }
+
+    #[test]
+    fn test_recycle_bin_with_missing_group_returns_none() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.recycle_bin_uuid = GroupUuid::new_random();
+        assert_eq!(db.recycle_bin(), None);
+    }
+
+    #[test]
+    fn test_redacted_removes_secrets() {
+        let db = db_with_groups_and_entries();
+        let redacted = db.redacted();
+        assert!(redacted.composite_key.is_empty());
+        assert_eq!(redacted.entries().count(), 3);
+        for entry in redacted.entries() {
+            assert_eq!(entry.password(), Some("***"));
+        }
+        assert_eq!(db.root_group.groups[0].entries[0].password(), Some("gpass"));
+    }
+
+    #[test]
+    fn test_set_entry_custom_icon_sets_and_clears_icon() {
+        let mut db = db_with_groups_and_entries();
+        let entry_uuid = db.root_group.groups[0].entries[1].uuid;
+        let icon_uuid = db.add_custom_icon(vec![1, 2, 3]);
+
+        db.set_entry_custom_icon(entry_uuid, Some(icon_uuid))
+            .unwrap();
+        assert_eq!(db.root_group.groups[0].entries[1].custom_icon_uuid, Some(icon_uuid));
+
+        db.set_entry_custom_icon(entry_uuid, None).unwrap();
+        assert_eq!(db.root_group.groups[0].entries[1].custom_icon_uuid, None);
+    }
+
+    #[test]
+    fn test_set_entry_custom_icon_with_unknown_icon_returns_error() {
+        let mut db = db_with_groups_and_entries();
+        let entry_uuid = db.root_group.groups[0].entries[1].uuid;
+        let icon_uuid = CustomIconUuid::new_random();
+        match db.set_entry_custom_icon(entry_uuid, Some(icon_uuid)) {
+            Err(Error::MissingCustomIcon(uuid)) => assert_eq!(uuid, icon_uuid),
+            _ => panic!("expected MissingCustomIcon error"),
+        }
+        assert_eq!(db.root_group.groups[0].entries[1].custom_icon_uuid, None);
+    }
+
+    #[test]
+    fn test_set_entry_custom_icon_with_unknown_entry_returns_error() {
+        let mut db = db_with_groups_and_entries();
+        let entry_uuid = EntryUuid::new_random();
+        match db.set_entry_custom_icon(entry_uuid, None) {
+            Err(Error::MissingEntry(uuid)) => assert_eq!(uuid, entry_uuid),
+            _ => panic!("expected MissingEntry error"),
+        }
+    }
+
+    #[test]
+    fn test_create_group_adds_group_to_parent() {
+        let mut db = db_with_groups_and_entries();
+        let parent = db.root_group.groups[0].uuid;
+
+        let uuid = db.create_group(parent, "Child").unwrap();
+        let group = db.root_group.groups[0].groups.last().unwrap();
+        assert_eq!(group.uuid, uuid);
+        assert_eq!(group.name, "Child");
+        assert_eq!(group.parent, parent);
+        assert_eq!(group.enable_auto_type, None);
+        assert_eq!(group.enable_searching, None);
+
+        match db.create_group(GroupUuid::new_random(), "Other") {
+            Err(Error::MissingGroup(_)) => (),
+            _ => panic!("expected missing group error"),
+        }
+    }
+
+    #[test]
+    fn test_rename_group_renames_group_and_updates_time() {
+        let mut db = db_with_groups_and_entries();
+        let uuid = db.root_group.groups[0].uuid;
+        db.root_group.groups[0].last_modified = Utc::now() - Duration::days(1);
+
+        db.rename_group(uuid, "Renamed").unwrap();
+        assert_eq!(db.root_group.groups[0].name, "Renamed");
+        assert!(approx_equal_datetime(db.root_group.groups[0].last_modified, Utc::now()));
+
+        match db.rename_group(GroupUuid::new_random(), "Other") {
+            Err(Error::MissingGroup(_)) => (),
+            _ => panic!("expected missing group error"),
+        }
+    }
+
+    #[test]
+    fn test_set_entry_title_sets_title_and_updates_time() {
+        let mut db = db_with_groups_and_entries();
+        let uuid = db.root_group.groups[0].entries[0].uuid;
+        db.root_group.groups[0].entries[0].last_modified = Utc::now() - Duration::days(1);
+
+        db.set_entry_title(uuid, "Renamed").unwrap();
+        let entry = db.get_entry(uuid).unwrap();
+        assert_eq!(entry.title(), Some("Renamed"));
+        assert!(approx_equal_datetime(entry.last_modified, Utc::now()));
+
+        match db.set_entry_title(EntryUuid::new_random(), "Other") {
+            Err(Error::MissingEntry(_)) => (),
+            _ => panic!("expected missing entry error"),
+        }
+    }
+
+    #[test]
+    fn test_set_ciphers_sets_ciphers() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        let result = db.set_ciphers(MasterCipher::Aes256, StreamCipher::Salsa20);
+        assert!(result.is_ok());
+        assert_eq!(db.master_cipher, MasterCipher::Aes256);
+        assert_eq!(db.stream_cipher, StreamCipher::Salsa20);
+    }
+
+    #[test]
+    fn test_set_comment_sets_comment_that_is_saved() {
+        let key = CompositeKey::from_password("test");
+        let mut db = Database::new(&key);
+        db.set_comment(Some(Comment(vec![1, 2, 3]))).unwrap();
+        assert_eq!(db.comment, Some(Comment(vec![1, 2, 3])));
+
+        let mut buffer = Vec::new();
+        db.save(&mut buffer).unwrap();
+        let db = Database::open(&mut &buffer[..], &key).unwrap();
+        assert_eq!(db.comment, Some(Comment(vec![1, 2, 3])));
+    }
+
+    #[test]
+    fn test_set_comment_with_too_large_comment_returns_error() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        match db.set_comment(Some(Comment(vec![0; 65536]))) {
+            Err(Error::CommentTooLarge(65536)) => (),
+            _ => panic!("expected comment too large error"),
+        }
+        assert_eq!(db.comment, None);
+
+        db.set_comment(Some(Comment(vec![0; 65535]))).unwrap();
+        assert_eq!(db.comment, Some(Comment(vec![0; 65535])));
+    }
+
+    #[test]
+    fn test_set_compression_sets_compression() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.set_compression(Compression::None);
+        assert_eq!(db.compression, Compression::None);
+    }
+
+    #[test]
+    fn test_to_keepass_xml_pretty_is_independent_of_insertion_order() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        let mut other = db.clone();
+        for key in ["a", "b", "c", "d"] {
+            db.set_custom_data(key, key);
+        }
+        for key in ["d", "c", "b", "a"] {
+            other.set_custom_data(key, key);
+        }
+        assert_eq!(
+            db.to_keepass_xml_pretty(false).unwrap(),
+            other.to_keepass_xml_pretty(false).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_to_keepass_xml_pretty_writes_settings_changed_for_version_4_1() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.settings_changed = Some(Utc::now());
+        let xml = db.to_keepass_xml_pretty(false).unwrap();
+        assert!(!xml.contains("<SettingsChanged>"));
+
+        db.version = Version { major: 4, minor: 1 };
+        let xml = db.to_keepass_xml_pretty(false).unwrap();
+        assert!(xml.contains("<SettingsChanged>"));
+    }
+
+    #[test]
+    fn test_to_keepass_xml_pretty_omits_normalized_empty_strings() {
+        let mut entry = Entry::new();
+        entry.set_url("");
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.root_group.add_entry(entry);
+        assert!(db
+            .to_keepass_xml_pretty(false)
+            .unwrap()
+            .contains("<Key>URL</Key>"));
+
+        db.root_group.entries[0].normalize_strings(false);
+        assert!(!db
+            .to_keepass_xml_pretty(false)
+            .unwrap()
+            .contains("<Key>URL</Key>"));
+    }
+
+    #[test]
+    fn test_to_keepass_xml_pretty_hides_protected_values_by_default() {
+        let mut db = db_with_groups_and_entries();
+        let mut old = db.root_group.groups[0].entries[0].clone();
+        old.set_password("old-secret");
+        db.root_group.groups[0].entries[0].history.push(old);
+        let xml = db.to_keepass_xml_pretty(false).unwrap();
+        assert!(xml.contains("<Value>Gmail</Value>"));
+        assert!(xml.contains("<Value>***</Value>"));
+        assert!(!xml.contains("gpass"));
+        assert!(!xml.contains("old-secret"));
+        assert!(!xml.contains("Protected"));
+    }
+
+    #[test]
+    fn test_to_keepass_xml_pretty_with_insecure_plaintext_shows_protected_values() {
+        let mut db = db_with_groups_and_entries();
+        let mut old = db.root_group.groups[0].entries[0].clone();
+        old.set_password("old-secret");
+        db.root_group.groups[0].entries[0].history.push(old);
+        db.root_group.groups[0].entries[0].binaries.insert(
+            BinaryKey(String::from("key")),
+            BinaryValue::Protected(SecStr::new(b"bin".to_vec())),
+        );
+        let xml = db.to_keepass_xml_pretty(true).unwrap();
+        assert!(xml.contains("<Value>gpass</Value>"));
+        assert!(xml.contains("<Value>old-secret</Value>"));
+        assert!(xml.contains("<Value>Ymlu</Value>"));
+        assert!(!xml.contains("Protected"));
+    }
+
+    #[test]
+    fn test_set_custom_data_replaces_value() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.set_custom_data("key", "old");
+        db.set_custom_data("key", "new");
+        assert_eq!(db.custom_data.len(), 1);
+        assert_eq!(db.custom_data.get("key"), Some(&String::from("new")));
+    }
 
-        Ok(db)
+    #[test]
+    fn test_statistics_returns_correct_stats() {
+        let now = Utc::now();
+        let mut db = db_with_groups_and_entries();
+        db.root_group.groups[1].add_group(Group::new("Nested"));
+        db.root_group.groups[0].entries[0].expires = true;
+        db.root_group.groups[0].entries[0].expiry_time = now - Duration::days(1);
+        db.root_group.groups[0].entries[1].set_password("");
+        db.root_group.add_entry(Entry::new());
+        db.binaries
+            .insert(BinaryId(String::from("0")), vec![1, 2, 3]);
+        db.binaries.insert(BinaryId(String::from("1")), vec![4, 5]);
+
+        let stats = db.statistics(now);
+        assert_eq!(stats.binaries_size, 5);
+        assert_eq!(stats.empty_passwords, 2);
+        assert_eq!(stats.entries, 4);
+        assert_eq!(stats.expired_entries, 1);
+        assert_eq!(stats.groups, 3);
+        assert_eq!(stats.max_depth, 2);
     }
-}
 
-fn entry_contains_string(entry: &Entry, name: &String) -> bool {
-    for value in entry.strings.values() {
-        match *value {
-            StringValue::Plain(ref string) => {
-                if string.to_lowercase().contains(name) {
-                    return true;
-                }
-            }
-            StringValue::Protected(_) => {}
+    #[test]
+    fn test_statistics_with_empty_database_returns_zero_stats() {
+        let db = Database::new(&CompositeKey::from_password("test"));
+        assert_eq!(db.statistics(Utc::now()), DatabaseStats::default());
+    }
+
+    #[test]
+    fn test_all_history_returns_history_of_nested_entries() {
+        let mut db = db_with_groups_and_entries();
+        for entry in db.entries_mut() {
+            let old = entry.clone();
+            entry.history.push(old.clone());
+            entry.history.push(old);
         }
+        let pairs: Vec<(&Entry, &Entry)> = db.all_history().collect();
+        assert_eq!(pairs.len(), 2 * db.entries().count());
+        assert!(pairs.iter().all(|(current, old)| current.uuid == old.uuid));
     }
-    false
-}
 
-#[cfg(test)]
-mod tests {
+    #[test]
+    fn test_enforce_memory_protection_converts_standard_strings() {
+        let mut entry = Entry::new();
+        entry.set_username("puser");
+        entry.set_password("ppass");
+        entry.set_other(StringKey::Other(String::from("PIN")), "1234");
+        entry.history.push(entry.clone());
 
-    use super::*;
-    use crate::types::BinariesMap;
-    use crate::types::CompositeKey;
-    use crate::types::Compression;
-    use crate::types::CustomDataMap;
-    use crate::types::CustomIconsMap;
-    use crate::types::DbType;
-    use crate::types::GroupUuid;
-    use crate::types::MasterCipher;
-    use crate::types::StreamCipher;
-    use crate::types::TransformRounds;
-    use crate::types::Version;
-    use crate::utils::test::approx_equal_datetime;
-    use chrono::Utc;
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.root_group.add_entry(entry);
+        db.protect_username = true;
+        db.protect_password = false;
+        db.enforce_memory_protection();
+
+        let entry = &db.root_group.entries[0];
+        for version in [entry, &entry.history[0]].iter() {
+            let strings = &version.strings;
+            assert_eq!(strings[&StringKey::Username], StringValue::new("puser", true));
+            assert_eq!(strings[&StringKey::Password], StringValue::new("ppass", false));
+            let pin = &strings[&StringKey::Other(String::from("PIN"))];
+            assert_eq!(*pin, StringValue::new("1234", false));
+        }
+
+        db.protect_username = false;
+        db.enforce_memory_protection();
+        let value = &db.root_group.entries[0].strings[&StringKey::Username];
+        assert_eq!(*value, StringValue::new("puser", false));
+    }
 
     #[test]
-    fn test_new_returns_correct_instance() {
+    fn test_recent_entries_includes_nested_entries_and_respects_limit() {
         let now = Utc::now();
-        let key = CompositeKey::from_password("5pZ5mgpTkLCDaM46IuH7yGafZFIICyvC");
-        let db = Database::new(&key);
-        assert_eq!(db.comment, None);
-        assert_eq!(db.composite_key, key);
-        assert_eq!(db.compression, Compression::GZip);
-        assert_eq!(db.db_type, DbType::Kdb2);
-        assert_eq!(db.master_cipher, MasterCipher::Aes256);
-        assert_eq!(db.stream_cipher, StreamCipher::Salsa20);
-        assert_eq!(db.transform_rounds, TransformRounds(10000));
-        assert_eq!(db.version, Version::new_kdb2());
-        assert_eq!(db.binaries, BinariesMap::new());
-        assert_eq!(db.color, None);
-        assert_eq!(db.custom_data, CustomDataMap::new());
-        assert_eq!(db.custom_icons, CustomIconsMap::new());
-        assert_eq!(db.def_username, "");
-        assert!(approx_equal_datetime(db.def_username_changed, now));
-        assert_eq!(db.description, "");
-        assert!(approx_equal_datetime(db.description_changed, now));
-        assert!(approx_equal_datetime(db.entry_templates_group_changed, now));
-        assert_eq!(db.entry_templates_group_uuid, GroupUuid::nil());
-        assert_eq!(db.generator, "rust-kpdb");
-        assert_eq!(db.history_max_items, 10);
-        assert_eq!(db.history_max_size, 6291456);
-        assert_eq!(db.last_selected_group, GroupUuid::nil());
-        assert_eq!(db.last_top_visible_group, GroupUuid::nil());
-        assert_eq!(db.maintenance_history_days, 365);
-        assert_eq!(db.master_key_change_force, -1);
-        assert_eq!(db.master_key_change_rec, -1);
-        assert!(approx_equal_datetime(db.master_key_changed, now));
-        assert_eq!(db.name, "");
-        assert!(approx_equal_datetime(db.name_changed, now));
-        assert_eq!(db.protect_notes, false);
-        assert_eq!(db.protect_password, true);
-        assert_eq!(db.protect_title, false);
-        assert_eq!(db.protect_url, false);
-        assert_eq!(db.protect_username, false);
-        assert!(approx_equal_datetime(db.recycle_bin_changed, now));
-        assert_eq!(db.recycle_bin_enabled, true);
-        assert_eq!(db.recycle_bin_uuid, GroupUuid::nil());
-        assert!(db.root_group.uuid != GroupUuid::nil());
+        let mut db = db_with_groups_and_entries();
+        for (index, entry) in db.entries_mut().enumerate() {
+            entry.creation_time = now - Duration::days(index as i64);
+        }
+        let expected: Vec<EntryUuid> = db.entries().take(2).map(|e| e.uuid).collect();
+        let actual: Vec<EntryUuid> = db.recent_entries(2).iter().map(|e| e.uuid).collect();
+        assert_eq!(actual, expected);
+        assert_eq!(db.recent_entries(100).len(), db.entries().count());
     }
 
     #[test]
-    fn test_find_entries_returns_correct_entries() {
-        let db = db_with_groups_and_entries();
-        let result = db.find_entries("Proton");
-        assert_eq!(result.len(), 2);
+    fn test_resolve_references_resolves_nested_and_searched_references() {
+        let mut shared = Entry::new();
+        shared.set_title("Shared");
+        shared.set_password("ppass");
+        let mut middle = Entry::new();
+        middle.set_password("{REF:P@T:shared}");
+        let mut entry = Entry::new();
+        entry.set_password(format!("<{{REF:P@I:{}}}>", middle.uuid.0.simple()));
 
-        let result = db.find_entries("Unknown");
-        assert_eq!(result.len(), 0);
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.root_group.add_entry(shared);
+        db.root_group.add_entry(middle);
+
+        let password = db.resolve_references(&entry, &StringKey::Password);
+        assert_eq!(password, Some(String::from("<ppass>")));
+        assert_eq!(db.resolve_references(&entry, &StringKey::Notes), None);
     }
 
     #[test]
-    fn test_find_entries_mut_returns_correct_entries() {
-        let mut db = db_with_groups_and_entries();
-        {
-            let result = db.find_entries_mut("Proton");
-            assert_eq!(result.len(), 2);
+    fn test_resolve_references_leaves_unresolved_and_cyclic_references() {
+        let mut entry = Entry::new();
+        entry.set_username(format!("{{REF:U@I:{}}}", entry.uuid.0.simple()));
+        entry.set_url("{REF:A@I:00000000000000000000000000000000} {REF:X");
+
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.root_group.add_entry(entry.clone());
+
+        let username = db.resolve_references(&entry, &StringKey::Username);
+        assert_eq!(username, Some(String::from(entry.username().unwrap())));
+        let url = db.resolve_references(&entry, &StringKey::Url);
+        assert_eq!(url, Some(String::from(entry.url().unwrap())));
+    }
+
+    #[test]
+    fn test_resolve_references_limits_total_number_of_references() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        let mut target = Entry::new();
+        target.set_title("x");
+        for _ in 0..common::MAX_REFERENCE_DEPTH {
+            let mut entry = Entry::new();
+            entry.set_title(format!("{{REF:T@I:{0}}}{{REF:T@I:{0}}}", target.uuid.0.simple()));
+            db.root_group.add_entry(target);
+            target = entry;
         }
-        {
-            let result = db.find_entries_mut("Unknown");
-            assert_eq!(result.len(), 0);
+
+        let title = db.resolve_references(&target, &StringKey::Title).unwrap();
+        assert!(title.matches('x').count() < common::MAX_REFERENCES);
+        assert!(title.contains("{REF:T@I:"));
+    }
+
+    #[test]
+    fn test_save_twice_uses_fresh_cryptographic_material() {
+        let key = CompositeKey::from_password("test");
+        let db = Database::new(&key);
+        let mut first = Vec::new();
+        db.save(&mut first).unwrap();
+        let mut second = Vec::new();
+        db.save(&mut second).unwrap();
+        assert!(first != second);
+
+        let first_key = Database::transformed_key(&mut &first[..], &key).unwrap();
+        let second_key = Database::transformed_key(&mut &second[..], &key).unwrap();
+        assert!(first_key != second_key);
+    }
+
+    #[test]
+    fn test_save_reported_returns_size_and_hash_of_written_data() {
+        let db = Database::new(&CompositeKey::from_password("test"));
+        let mut out = Vec::new();
+        let report = db.save_reported(&mut out).unwrap();
+        assert_eq!(report.bytes_written, out.len() as u64);
+        assert_eq!(report.sha256, sha256::hash(&[&out]));
+    }
+
+    #[test]
+    fn test_next_binary_id_returns_smallest_unused_id() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        assert_eq!(db.next_binary_id(), BinaryId(String::from("0")));
+        for id in ["0", "1", "3", "5", "other"] {
+            db.binaries.insert(BinaryId(String::from(id)), Vec::new());
         }
+        assert_eq!(db.next_binary_id(), BinaryId(String::from("2")));
+        db.binaries.insert(BinaryId(String::from("2")), Vec::new());
+        assert_eq!(db.next_binary_id(), BinaryId(String::from("4")));
     }
 
     #[test]
-    fn test_find_groups_returns_correct_groups() {
-        let db = db_with_groups_and_entries();
-        let result = db.find_groups("mail");
-        assert_eq!(result.len(), 1);
+    fn test_set_entry_binary_shares_pooled_data_and_inlines_protected_data() {
+        let first = Entry::new();
+        let second = Entry::new();
+        let (first_uuid, second_uuid) = (first.uuid, second.uuid);
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.binaries.insert(BinaryId(String::from("0")), vec![9]);
+        db.root_group.add_entry(first);
+        db.root_group.add_entry(second);
 
-        let result = db.find_groups("Unknown");
-        assert_eq!(result.len(), 0);
+        db.set_entry_binary(first_uuid, "a", vec![1], false)
+            .unwrap();
+        db.set_entry_binary(second_uuid, "a", vec![1], false)
+            .unwrap();
+        db.set_entry_binary(second_uuid, "b", vec![2], true)
+            .unwrap();
+
+        let key = BinaryKey(String::from("a"));
+        let expected = BinaryValue::Ref(BinaryId(String::from("1")));
+        assert_eq!(db.binaries.len(), 2);
+        assert_eq!(db.root_group.entries[0].binaries[&key], expected);
+        assert_eq!(db.root_group.entries[1].binaries[&key], expected);
+        assert_eq!(
+            db.root_group.entries[1].binaries[&BinaryKey(String::from("b"))],
+            BinaryValue::Protected(SecStr::new(vec![2]))
+        );
     }
 
     #[test]
-    fn test_find_groups_mut_returns_correct_groups() {
+    fn test_entries_sorted_sorts_by_field_and_keeps_ties_in_order() {
         let mut db = db_with_groups_and_entries();
-        {
-            let result = db.find_groups_mut("mail");
-            assert_eq!(result.len(), 1);
-        }
-        {
-            let result = db.find_groups_mut("Unknown");
-            assert_eq!(result.len(), 0);
+        let titles = |list: Vec<&Entry>| -> Vec<String> {
+            list.iter()
+                .map(|e| e.title().unwrap_or_default().to_string())
+                .collect()
+        };
+        let sorted = db.entries_sorted(SortField::Title, true);
+        assert_eq!(titles(sorted), vec!["ProtonVPN", "ProtonMail", "Gmail"]);
+
+        let sorted = db.entries_sorted(SortField::Username, false);
+        assert_eq!(titles(sorted), vec!["Gmail", "ProtonMail", "ProtonVPN"]);
+        let sorted = db.entries_sorted(SortField::Username, true);
+        assert_eq!(titles(sorted), vec!["ProtonMail", "ProtonVPN", "Gmail"]);
+
+        db.root_group.groups[1].entries[0].usage_count = 5;
+        let sorted = db.entries_sorted(SortField::UsageCount, true);
+        assert_eq!(titles(sorted)[0], "ProtonVPN");
+    }
+
+    #[test]
+    fn test_extract_attachment_writes_plain_protected_and_pooled_data() {
+        let mut db = db_with_groups_and_entries();
+        let uuid = db.root_group.groups[0].entries[0].uuid;
+        db.set_entry_binary(uuid, "pooled", vec![1, 2], false)
+            .unwrap();
+        db.set_entry_binary(uuid, "protected", vec![3, 4], true)
+            .unwrap();
+        let entry = db.get_entry_mut(uuid).unwrap();
+        entry
+            .binaries
+            .insert(BinaryKey(String::from("plain")), BinaryValue::Plain(vec![5, 6]));
+
+        for (name, expected) in [
+            ("pooled", vec![1, 2]),
+            ("protected", vec![3, 4]),
+            ("plain", vec![5, 6]),
+        ] {
+            let mut out = Vec::new();
+            db.extract_attachment(uuid, name, &mut out).unwrap();
+            assert_eq!(out, expected);
         }
     }
 
     #[test]
-    fn test_get_entry_returns_correct_entry() {
-        let entry = Entry::new();
-        let entry_uuid = entry.uuid;
+    fn test_extract_attachment_with_missing_binary_returns_error() {
+        let mut db = db_with_groups_and_entries();
+        let uuid = db.root_group.groups[0].entries[0].uuid;
+        let mut out = Vec::new();
+        match db.extract_attachment(uuid, "file.txt", &mut out) {
+            Err(Error::MissingBinaryKey { entry, key }) => {
+                assert_eq!(entry, uuid);
+                assert_eq!(key, BinaryKey(String::from("file.txt")));
+            }
+            _ => panic!("expected missing binary key error"),
+        }
 
-        let mut group = Group::new("Group");
-        group.add_entry(entry.clone());
+        db.set_entry_binary(uuid, "file.txt", vec![1], false)
+            .unwrap();
+        db.binaries.clear();
+        match db.extract_attachment(uuid, "file.txt", &mut out) {
+            Err(Error::MissingBinary { entry, .. }) => assert_eq!(entry, uuid),
+            _ => panic!("expected missing binary error"),
+        }
+
+        let missing = EntryUuid::new_random();
+        match db.extract_attachment(missing, "file.txt", &mut out) {
+            Err(Error::MissingEntry(entry)) => assert_eq!(entry, missing),
+            _ => panic!("expected missing entry error"),
+        }
+    }
 
+    #[test]
+    fn test_set_entry_binary_with_missing_entry_returns_error() {
         let mut db = Database::new(&CompositeKey::from_password("test"));
-        assert_eq!(db.get_entry(entry_uuid), None);
+        let uuid = EntryUuid::new_random();
+        match db.set_entry_binary(uuid, "a", vec![1], false) {
+            Err(Error::MissingEntry(val)) => assert_eq!(val, uuid),
+            _ => panic!("expected MissingEntry error"),
+        }
+        assert_eq!(db.binaries.len(), 0);
+    }
 
-        db.root_group.add_group(group);
-        assert_eq!(db.get_entry(entry_uuid), Some(&entry));
+    #[test]
+    fn test_trim_history_removes_oldest_versions_beyond_limits() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        let mut entry = Entry::new();
+        entry.set_title("Email");
+        entry.add_history_snapshot();
+        entry
+            .binaries
+            .insert(BinaryKey(String::from("a")), BinaryValue::Ref(BinaryId(String::from("1"))));
+        entry.add_history_snapshot();
+        entry.set_title("Mail");
+        entry.add_history_snapshot();
+        db.binaries
+            .insert(BinaryId(String::from("1")), vec![0; 100]);
+        db.root_group.add_entry(entry);
+
+        db.history_max_items = -1;
+        db.history_max_size = 231;
+        assert_eq!(db.trim_history(), 0);
+        db.history_max_size = 230;
+        assert_eq!(db.trim_history(), 1);
+        assert_eq!(db.entries().next().unwrap().history.len(), 2);
+        db.history_max_items = 1;
+        assert_eq!(db.trim_history(), 1);
+        assert_eq!(db.entries().next().unwrap().history[0].title(), Some("Mail"));
+        db.history_max_size = 0;
+        assert_eq!(db.trim_history(), 1);
+        assert!(db.entries().next().unwrap().history.is_empty());
     }
 
     #[test]
-    fn test_get_entry_mut_returns_correct_entry() {
+    fn test_validate_reports_dangling_binary_and_parent_mismatch() {
         let mut entry = Entry::new();
+        entry.binaries.insert(
+            BinaryKey(String::from("file.txt")),
+            BinaryValue::Ref(BinaryId(String::from("0"))),
+        );
         let entry_uuid = entry.uuid;
+        let other_uuid = GroupUuid::new_random();
+        entry.parent = other_uuid;
 
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.root_group.add_entry(entry);
+        assert_eq!(
+            db.validate(),
+            vec![
+                ValidationIssue::DanglingBinary {
+                    entry: entry_uuid,
+                    id: BinaryId(String::from("0")),
+                },
+                ValidationIssue::EntryParentMismatch {
+                    entry: entry_uuid,
+                    parent: other_uuid,
+                    group: db.root_group.uuid,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_group_parent_mismatch() {
         let mut group = Group::new("Group");
-        group.add_entry(entry.clone());
+        let group_uuid = group.uuid;
+        let other_uuid = GroupUuid::new_random();
+        group.parent = other_uuid;
 
         let mut db = Database::new(&CompositeKey::from_password("test"));
-        assert_eq!(db.get_entry_mut(entry_uuid), None);
+        db.root_group.add_group(group);
+        assert_eq!(
+            db.validate(),
+            vec![ValidationIssue::GroupParentMismatch {
+                uuid: group_uuid,
+                parent: other_uuid,
+                group: db.root_group.uuid,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validate_with_unset_parents_returns_no_issues() {
+        let mut group = Group::new("Group");
+        group.add_entry(Entry::new());
+        group.add_group(Group::new("Sub"));
 
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        db.root_group.add_entry(Entry::new());
         db.root_group.add_group(group);
-        assert_eq!(db.get_entry_mut(entry_uuid), Some(&mut entry));
+        assert_eq!(db.validate(), Vec::new());
     }
 
     #[test]
-    fn test_get_group_returns_correct_group() {
-        let group = Group::new("Group");
-        let group_uuid = group.uuid;
+    fn test_validate_reports_duplicate_uuids_and_cycles() {
+        let mut db = Database::new(&CompositeKey::from_password("test"));
+        let mut child = Group::new("Child");
+        child.uuid = db.root_group.uuid;
+        child.parent = db.root_group.uuid;
+        db.root_group.add_group(child);
+        assert_eq!(
+            db.validate(),
+            vec![
+                ValidationIssue::DuplicateGroupUuid(db.root_group.uuid),
+                ValidationIssue::GroupCycle(db.root_group.uuid),
+            ]
+        );
+    }
 
+    #[test]
+    fn test_validate_with_weak_transform_rounds_returns_issue() {
         let mut db = Database::new(&CompositeKey::from_password("test"));
-        assert_eq!(db.get_group(group_uuid), None);
+        db.transform_rounds = TransformRounds(1000);
+        assert_eq!(db.validate(), vec![ValidationIssue::WeakTransformRounds(1000)]);
+    }
 
-        db.root_group.add_group(group.clone());
-        assert_eq!(db.get_group(group_uuid), Some(&group));
+    #[test]
+    fn test_from_kdb2_without_header_hash_returns_database() {
+        let key = CompositeKey::from_password("test");
+        let xml_data = XmlData {
+            header_hash: None,
+            name: String::from("name"),
+            ..XmlData::default()
+        };
+        let db = Database::from_kdb2(&key, meta_data(), xml_data).unwrap();
+        assert_eq!(db.name, "name");
     }
 
     #[test]
-    fn test_get_group_mut_returns_correct_group() {
-        let mut group = Group::new("Group");
-        let group_uuid = group.uuid;
+    fn test_from_kdb2_with_invalid_header_hash_returns_error() {
+        let key = CompositeKey::from_password("test");
+        let xml_data = XmlData {
+            header_hash: Some(HeaderHash(vec![1; 32])),
+            ..XmlData::default()
+        };
+        match Database::from_kdb2(&key, meta_data(), xml_data) {
+            Err(Error::InvalidHeaderHash) => (),
+            _ => panic!("expected invalid header hash error"),
+        }
+    }
 
-        let mut db = Database::new(&CompositeKey::from_password("test"));
-        assert_eq!(db.get_group_mut(group_uuid), None);
+    #[test]
+    fn test_from_kdb2_with_version_4_requires_header_hmac() {
+        let key = CompositeKey::from_password("test");
+        let meta_data = MetaData {
+            version: Version { major: 4, minor: 0 },
+            ..meta_data()
+        };
+        let xml_data = XmlData {
+            header_hash: Some(HeaderHash(vec![0; 32])),
+            ..XmlData::default()
+        };
+        match Database::from_kdb2(&key, meta_data, xml_data) {
+            Err(Error::UnhandledVersion(Version { major: 4, minor: 0 })) => (),
+            _ => panic!("expected unhandled version error"),
+        }
+    }
 
-        db.root_group.add_group(group.clone());
-        assert_eq!(db.get_group_mut(group_uuid), Some(&mut group));
+    #[test]
+    fn test_password_audit_reports_weak_old_and_reused_passwords() {
+        let mut db = db_with_groups_and_entries();
+        let now = Utc::now();
+        let gmail = db.root_group.groups[0].entries[0].uuid;
+        let protonmail = db.root_group.groups[0].entries[1].uuid;
+        let protonvpn = db.root_group.groups[1].entries[0].uuid;
+        db.root_group.groups[0].entries[0].set_password("61rH$b!x-Qz7#pLw@Vc2");
+        db.root_group.groups[0].entries[0].last_modified = now - Duration::days(400);
+
+        let mut recycle_bin = Group::new("Recycle Bin");
+        recycle_bin.add_entry(db.root_group.groups[1].entries[0].clone());
+        db.recycle_bin_uuid = recycle_bin.uuid;
+        db.root_group.add_group(recycle_bin);
+
+        let issues = db.password_audit(now, Duration::days(365));
+        assert_eq!(
+            issues,
+            vec![
+                PasswordIssue::OldPassword {
+                    entry: gmail,
+                    days: 400
+                },
+                PasswordIssue::WeakPassword {
+                    entry: protonmail,
+                    entropy: 23
+                },
+                PasswordIssue::WeakPassword {
+                    entry: protonvpn,
+                    entropy: 23
+                },
+                PasswordIssue::ReusedPassword(vec![protonmail, protonvpn]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_visits_tree_depth_first() {
+        let db = db_with_groups_and_entries();
+        let mut recorder = Recorder {
+            events: Vec::new(),
+            skip: None,
+            stop: None,
+        };
+        db.walk(&mut recorder);
+        assert_eq!(
+            recorder.events,
+            vec![
+                "enter Root 0",
+                "enter Email 1",
+                "entry Gmail 2",
+                "entry ProtonMail 2",
+                "leave Email 1",
+                "enter VPN 1",
+                "entry ProtonVPN 2",
+                "leave VPN 1",
+                "leave Root 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_with_skip_subtree_skips_group_contents() {
+        let db = db_with_groups_and_entries();
+        let mut recorder = Recorder {
+            events: Vec::new(),
+            skip: Some(String::from("Email")),
+            stop: None,
+        };
+        db.walk(&mut recorder);
+        assert_eq!(
+            recorder.events,
+            vec![
+                "enter Root 0",
+                "enter Email 1",
+                "leave Email 1",
+                "enter VPN 1",
+                "entry ProtonVPN 2",
+                "leave VPN 1",
+                "leave Root 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_with_stop_stops_walk() {
+        let db = db_with_groups_and_entries();
+        let mut recorder = Recorder {
+            events: Vec::new(),
+            skip: None,
+            stop: Some(String::from("ProtonMail")),
+        };
+        db.walk(&mut recorder);
+        assert_eq!(
+            recorder.events,
+            vec![
+                "enter Root 0",
+                "enter Email 1",
+                "entry Gmail 2",
+                "entry ProtonMail 2",
+            ]
+        );
+    }
+
+    #[cfg(feature = "accent-insensitive")]
+    #[test]
+    fn test_find_entries_ignores_diacritics() {
+        let mut db = db_with_groups_and_entries();
+        db.root_group.groups[0].entries[0].set_title("José");
+        assert_eq!(db.find_entries("jose").len(), 1);
+        assert_eq!(db.find_entries("JOSÉ").len(), 1);
+    }
+
+    #[cfg(feature = "accent-insensitive")]
+    #[test]
+    fn test_find_groups_ignores_diacritics() {
+        let mut db = db_with_groups_and_entries();
+        db.root_group.add_group(Group::new("Café"));
+        assert_eq!(db.find_groups("cafe").len(), 1);
+        assert_eq!(db.find_groups_mut("CAFE").len(), 1);
     }
 
     fn db_with_groups_and_entries() -> Database {
@@ -749,4 +4445,46 @@ mod tests {
         db.root_group.add_group(vpn_group);
         db
     }
+
+    struct Recorder {
+        events: Vec<String>,
+        skip: Option<String>,
+        stop: Option<String>,
+    }
+
+    impl TreeVisitor for Recorder {
+        fn enter_group(&mut self, group: &Group, depth: usize) -> WalkControl {
+            self.events.push(format!("enter {} {}", group.name, depth));
+            match self.skip {
+                Some(ref name) if *name == group.name => WalkControl::SkipSubtree,
+                _ => WalkControl::Continue,
+            }
+        }
+
+        fn leave_group(&mut self, group: &Group, depth: usize) -> WalkControl {
+            self.events.push(format!("leave {} {}", group.name, depth));
+            WalkControl::Continue
+        }
+
+        fn visit_entry(&mut self, entry: &Entry, depth: usize) -> WalkControl {
+            self.events
+                .push(format!("entry {} {}", entry.title().unwrap(), depth));
+            match self.stop {
+                Some(ref title) if Some(title.as_str()) == entry.title() => WalkControl::Stop,
+                _ => WalkControl::Continue,
+            }
+        }
+    }
+
+    fn meta_data() -> MetaData {
+        MetaData {
+            comment: None,
+            compression: Compression::GZip,
+            header_hash: HeaderHash(vec![0; 32]),
+            master_cipher: MasterCipher::Aes256,
+            stream_cipher: StreamCipher::Salsa20,
+            transform_rounds: TransformRounds(6000),
+            version: Version::new_kdb2(),
+        }
+    }
 }
@@ -0,0 +1,179 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::composite_key::CompositeKey;
+use super::compression::Compression;
+use super::database::Database;
+use super::error::Error;
+use super::master_cipher::MasterCipher;
+use super::result::Result;
+use super::stream_cipher::StreamCipher;
+use super::transform_rounds::TransformRounds;
+
+/// Builder for creating a database with custom settings.
+#[derive(Clone, Debug)]
+pub struct DatabaseBuilder {
+    key: CompositeKey,
+    compression: Compression,
+    def_username: String,
+    description: String,
+    master_cipher: MasterCipher,
+    name: String,
+    stream_cipher: StreamCipher,
+    transform_rounds: TransformRounds,
+}
+
+impl DatabaseBuilder {
+    /// Create a new database builder with the default settings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use kpdb::Result;
+    /// # fn build() -> Result<()> {
+    /// use kpdb::{CompositeKey, Compression, DatabaseBuilder, TransformRounds};
+    ///
+    /// let key = CompositeKey::from_password("password");
+    /// let db = DatabaseBuilder::new(&key)
+    ///     .name("Passwords")
+    ///     .compression(Compression::None)
+    ///     .transform_rounds(TransformRounds(20000))
+    ///     .build()?;
+    /// assert_eq!(db.name, "Passwords");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(key: &CompositeKey) -> DatabaseBuilder {
+        let db = Database::new(key);
+        DatabaseBuilder {
+            key: key.clone(),
+            compression: db.compression,
+            def_username: db.def_username,
+            description: db.description,
+            master_cipher: db.master_cipher,
+            name: db.name,
+            stream_cipher: db.stream_cipher,
+            transform_rounds: db.transform_rounds,
+        }
+    }
+
+    /// Creates the database.
+    ///
+    /// Returns an error when the number of transform rounds is zero.
+    pub fn build(self) -> Result<Database> {
+        if self.transform_rounds.0 == 0 {
+            return Err(Error::InvalidTransformRounds(self.transform_rounds.0));
+        }
+        let mut db = Database::new(&self.key);
+        db.compression = self.compression;
+        db.def_username = self.def_username;
+        db.description = self.description;
+        db.master_cipher = self.master_cipher;
+        db.name = self.name;
+        db.stream_cipher = self.stream_cipher;
+        db.transform_rounds = self.transform_rounds;
+        Ok(db)
+    }
+
+    /// Sets the compression algorithm.
+    pub fn compression(mut self, compression: Compression) -> DatabaseBuilder {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets the default username for new entries.
+    pub fn def_username<S: Into<String>>(mut self, username: S) -> DatabaseBuilder {
+        self.def_username = username.into();
+        self
+    }
+
+    /// Sets the description of the database.
+    pub fn description<S: Into<String>>(mut self, description: S) -> DatabaseBuilder {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the master encryption algorithm.
+    pub fn master_cipher(mut self, cipher: MasterCipher) -> DatabaseBuilder {
+        self.master_cipher = cipher;
+        self
+    }
+
+    /// Sets the name of the database.
+    pub fn name<S: Into<String>>(mut self, name: S) -> DatabaseBuilder {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the stream encryption algorithm.
+    pub fn stream_cipher(mut self, cipher: StreamCipher) -> DatabaseBuilder {
+        self.stream_cipher = cipher;
+        self
+    }
+
+    /// Sets the number of times the composite key must be transformed.
+    pub fn transform_rounds(mut self, rounds: TransformRounds) -> DatabaseBuilder {
+        self.transform_rounds = rounds;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::types::CompositeKey;
+    use crate::types::Compression;
+    use crate::types::Error;
+    use crate::types::TransformRounds;
+
+    #[test]
+    fn test_build_with_defaults_returns_default_database() {
+        let key = CompositeKey::from_password("test");
+        let expected = Database::new(&key);
+        let actual = DatabaseBuilder::new(&key).build().unwrap();
+        assert_eq!(actual.compression, expected.compression);
+        assert_eq!(actual.def_username, expected.def_username);
+        assert_eq!(actual.description, expected.description);
+        assert_eq!(actual.master_cipher, expected.master_cipher);
+        assert_eq!(actual.name, expected.name);
+        assert_eq!(actual.stream_cipher, expected.stream_cipher);
+        assert_eq!(actual.transform_rounds, expected.transform_rounds);
+    }
+
+    #[test]
+    fn test_build_with_settings_returns_correct_database() {
+        let key = CompositeKey::from_password("test");
+        let db = DatabaseBuilder::new(&key)
+            .compression(Compression::None)
+            .def_username("user")
+            .description("description")
+            .name("name")
+            .transform_rounds(TransformRounds(1))
+            .build()
+            .unwrap();
+        assert_eq!(db.compression, Compression::None);
+        assert_eq!(db.def_username, "user");
+        assert_eq!(db.description, "description");
+        assert_eq!(db.name, "name");
+        assert_eq!(db.transform_rounds, TransformRounds(1));
+        assert_eq!(db.composite_key, key);
+    }
+
+    #[test]
+    fn test_build_with_zero_transform_rounds_returns_error() {
+        let key = CompositeKey::from_password("test");
+        let result = DatabaseBuilder::new(&key)
+            .transform_rounds(TransformRounds(0))
+            .build();
+        match result {
+            Err(Error::InvalidTransformRounds(0)) => (),
+            _ => panic!("expected InvalidTransformRounds error"),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Summary of the contents of a database.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DatabaseStats {
+    /// The total size in bytes of the binaries (attachments).
+    pub binaries_size: usize,
+
+    /// The number of entries without a password or with an empty password.
+    pub empty_passwords: usize,
+
+    /// The number of entries, excluding history entries.
+    pub entries: usize,
+
+    /// The number of expired entries.
+    pub expired_entries: usize,
+
+    /// The number of groups, excluding the root group.
+    pub groups: usize,
+
+    /// The deepest nesting level of a group (zero when the root group has no
+    /// sub groups).
+    pub max_depth: usize,
+}
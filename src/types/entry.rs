@@ -7,35 +7,46 @@
 // except according to those terms.
 
 use super::association::Association;
+use super::auto_type::{AutoType, AutoTypeMut};
 use super::binary_key::BinaryKey;
 use super::binary_value::BinaryValue;
 use super::color::Color;
 use super::custom_icon_uuid::CustomIconUuid;
+use super::entry_field::EntryField;
 use super::entry_uuid::EntryUuid;
+use super::field_diff::FieldDiff;
+use super::field_encoding::FieldEncoding;
 use super::icon::Icon;
+use super::merge_policy::MergePolicy;
 use super::obfuscation::Obfuscation;
+use super::result::Result;
+use super::sort_field::SortField;
 use super::string_key::StringKey;
 use super::string_value::StringValue;
 use super::strings_map::StringsMap;
 use super::times::Times;
-use crate::{common, GroupUuid};
+use crate::{common, utils, GroupUuid};
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
-use std::str;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
 
 /// An entry in the database.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Entry {
     /// Auto-type associations.
+    #[deprecated(note = "use `Entry::auto_type` or `Entry::auto_type_mut` instead")]
     pub associations: Vec<Association>,
 
     /// Default auto-type sequence.
+    #[deprecated(note = "use `Entry::auto_type` or `Entry::auto_type_mut` instead")]
     pub auto_type_def_sequence: String,
 
     /// Whether auto-type is enabled.
+    #[deprecated(note = "use `Entry::auto_type` or `Entry::auto_type_mut` instead")]
     pub auto_type_enabled: bool,
 
     /// The type of obfuscation to use for auto-typing.
+    #[deprecated(note = "use `Entry::auto_type` or `Entry::auto_type_mut` instead")]
     pub auto_type_obfuscation: Obfuscation,
 
     /// The background color.
@@ -101,6 +112,431 @@ impl Entry {
         entry
     }
 
+    /// Gets the auto-type settings of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{AutoType, Entry};
+    ///
+    /// let entry = Entry::new();
+    /// assert_eq!(entry.auto_type(), AutoType::default());
+    /// ```
+    #[allow(deprecated)]
+    pub fn auto_type(&self) -> AutoType {
+        AutoType {
+            associations: self.associations.clone(),
+            def_sequence: self.auto_type_def_sequence.clone(),
+            enabled: self.auto_type_enabled,
+            obfuscation: self.auto_type_obfuscation,
+        }
+    }
+
+    /// Gets a mutable view of the auto-type settings of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Entry;
+    ///
+    /// let mut entry = Entry::new();
+    /// *entry.auto_type_mut().enabled = false;
+    /// entry.auto_type_mut().associate("Firefox - *", "{PASSWORD}{ENTER}");
+    ///
+    /// let auto_type = entry.auto_type();
+    /// assert!(!auto_type.enabled);
+    /// assert_eq!(auto_type.associations[0].window, "Firefox - *");
+    /// ```
+    #[allow(deprecated)]
+    pub fn auto_type_mut(&mut self) -> AutoTypeMut<'_> {
+        AutoTypeMut {
+            associations: &mut self.associations,
+            def_sequence: &mut self.auto_type_def_sequence,
+            enabled: &mut self.auto_type_enabled,
+            obfuscation: &mut self.auto_type_obfuscation,
+        }
+    }
+
+    /// Adds a copy of the current state of this entry to its history.
+    ///
+    /// The copy doesn't contain the history itself, like the snapshots that
+    /// KeePass creates. Call this before changing the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Entry;
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.set_title("Email");
+    /// entry.add_history_snapshot();
+    /// entry.set_title("Mail");
+    ///
+    /// assert_eq!(entry.history.len(), 1);
+    /// assert_eq!(entry.history[0].title(), Some("Email"));
+    /// ```
+    pub fn add_history_snapshot(&mut self) {
+        let mut snapshot = self.clone();
+        snapshot.history = Vec::new();
+        self.history.push(snapshot);
+    }
+
+    /// Compares this entry with the other entry by the specified field.
+    ///
+    /// Strings are compared case insensitively (see `Database::find_entries`
+    /// for the folding rules) and a missing string sorts like an empty one,
+    /// so entries without the field come first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{Entry, SortField};
+    /// use std::cmp::Ordering;
+    ///
+    /// let mut bank = Entry::new();
+    /// bank.set_title("bank");
+    /// let mut email = Entry::new();
+    /// email.set_title("Email");
+    ///
+    /// assert_eq!(bank.cmp_by(&email, SortField::Title), Ordering::Less);
+    /// assert_eq!(Entry::new().cmp_by(&bank, SortField::Title), Ordering::Less);
+    /// ```
+    pub fn cmp_by(&self, other: &Entry, field: SortField) -> Ordering {
+        let fold = |entry: &Entry, key: StringKey| utils::fold(entry.other(key).unwrap_or(""));
+        match field {
+            SortField::CreationTime => self.creation_time.cmp(&other.creation_time),
+            SortField::LastModified => self.last_modified.cmp(&other.last_modified),
+            SortField::Title => fold(self, StringKey::Title).cmp(&fold(other, StringKey::Title)),
+            SortField::Url => fold(self, StringKey::Url).cmp(&fold(other, StringKey::Url)),
+            SortField::UsageCount => self.usage_count.cmp(&other.usage_count),
+            SortField::Username => {
+                fold(self, StringKey::Username).cmp(&fold(other, StringKey::Username))
+            }
+        }
+    }
+
+    /// Returns whether this entry has the same content as the other entry.
+    ///
+    /// Unlike `==`, the identifier, parent, history, usage count and the
+    /// creation, access, modification and location change times are ignored.
+    /// Strings are compared by value regardless of their protection.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Entry;
+    /// use chrono::Utc;
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.set_title("Email");
+    ///
+    /// let mut other = entry.clone();
+    /// other.last_accessed = Utc::now();
+    /// other.usage_count += 1;
+    /// assert!(entry.content_eq(&other));
+    ///
+    /// other.set_title("Bank");
+    /// assert!(!entry.content_eq(&other));
+    /// ```
+    #[allow(deprecated)]
+    pub fn content_eq(&self, other: &Entry) -> bool {
+        self.associations == other.associations
+            && self.auto_type_def_sequence == other.auto_type_def_sequence
+            && self.auto_type_enabled == other.auto_type_enabled
+            && self.auto_type_obfuscation == other.auto_type_obfuscation
+            && self.background_color == other.background_color
+            && self.binaries == other.binaries
+            && self.custom_icon_uuid == other.custom_icon_uuid
+            && self.expires == other.expires
+            && self.expiry_time == other.expiry_time
+            && self.foreground_color == other.foreground_color
+            && self.icon == other.icon
+            && self.override_url == other.override_url
+            && self.tags == other.tags
+            && self.strings.len() == other.strings.len()
+            && self
+                .strings
+                .iter()
+                .all(|(key, val)| other.strings.get(key).map(|v| v.reveal()) == Some(val.reveal()))
+    }
+
+    /// Returns the differences between this entry and a newer version of it.
+    ///
+    /// The strings, colors, custom icon, expiry time, icon, override URL and
+    /// tags are compared.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{Entry, EntryField, StringKey};
+    ///
+    /// let mut old = Entry::new();
+    /// old.set_title("Old");
+    ///
+    /// let mut new = old.clone();
+    /// new.set_title("New");
+    ///
+    /// let diffs = old.diff(&new);
+    /// assert_eq!(diffs.len(), 1);
+    /// assert_eq!(diffs[0].field, EntryField::String(StringKey::Title));
+    /// ```
+    pub fn diff(&self, other: &Entry) -> Vec<FieldDiff> {
+        let mut list = Vec::new();
+        let keys: BTreeSet<&StringKey> = self.strings.keys().chain(other.strings.keys()).collect();
+        for key in keys {
+            let old = self.strings.get(key);
+            let new = other.strings.get(key);
+            if old.map(|v| v.reveal()) != new.map(|v| v.reveal()) {
+                list.push(FieldDiff {
+                    field: EntryField::String(key.clone()),
+                    old: old.cloned(),
+                    new: new.cloned(),
+                });
+            }
+        }
+
+        let fields = vec![
+            (
+                EntryField::BackgroundColor,
+                self.background_color.as_ref().map(|c| c.to_hex_string()),
+                other.background_color.as_ref().map(|c| c.to_hex_string()),
+            ),
+            (
+                EntryField::CustomIcon,
+                self.custom_icon_uuid.map(|u| u.0.to_string()),
+                other.custom_icon_uuid.map(|u| u.0.to_string()),
+            ),
+            (EntryField::ExpiryTime, expiry_string(self), expiry_string(other)),
+            (
+                EntryField::ForegroundColor,
+                self.foreground_color.as_ref().map(|c| c.to_hex_string()),
+                other.foreground_color.as_ref().map(|c| c.to_hex_string()),
+            ),
+            (
+                EntryField::Icon,
+                Some(self.icon.to_i32().to_string()),
+                Some(other.icon.to_i32().to_string()),
+            ),
+            (
+                EntryField::OverrideUrl,
+                Some(self.override_url.clone()),
+                Some(other.override_url.clone()),
+            ),
+            (EntryField::Tags, Some(self.tags.clone()), Some(other.tags.clone())),
+        ];
+        for (field, old, new) in fields {
+            if old != new {
+                list.push(FieldDiff {
+                    field,
+                    old: old.map(|v| StringValue::new(v, false)),
+                    new: new.map(|v| StringValue::new(v, false)),
+                });
+            }
+        }
+        list
+    }
+
+    /// Gets the binary data that is stored encoded in an other string.
+    ///
+    /// Returns `None` if the string doesn't exist and an error if it can't be
+    /// decoded using the specified encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{Entry, FieldEncoding, StringKey};
+    ///
+    /// let key = StringKey::from_string("Secret");
+    /// let mut entry = Entry::new();
+    /// entry.set_other(key.clone(), "MZXW6===");
+    ///
+    /// let data = entry.decoded_field(&key, FieldEncoding::Base32);
+    /// assert_eq!(data.unwrap().unwrap(), b"foo".to_vec());
+    /// ```
+    pub fn decoded_field(
+        &self,
+        key: &StringKey,
+        encoding: FieldEncoding,
+    ) -> Option<Result<Vec<u8>>> {
+        self.other(key.clone()).map(|text| encoding.decode(text))
+    }
+
+    /// Returns the template with the placeholders of this entry's fields
+    /// replaced by their values.
+    ///
+    /// The supported (case insensitive) placeholders are `{TITLE}`,
+    /// `{USERNAME}`, `{PASSWORD}`, `{URL}`, `{NOTES}` and `{S:<name>}` for
+    /// other strings. Placeholders of absent fields are replaced by an empty
+    /// string, unknown placeholders are left as is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Entry;
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.set_username("puser");
+    /// entry.set_url("https://mail.protonmail.com");
+    ///
+    /// let text = entry.expand_placeholders("{USERNAME} at {url} {TAB}");
+    /// assert_eq!(text, "puser at https://mail.protonmail.com {TAB}");
+    /// ```
+    pub fn expand_placeholders(&self, template: &str) -> String {
+        let mut result = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            result.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let placeholder = rest
+                .find('}')
+                .and_then(|end| placeholder_key(&rest[1..end]).map(|key| (key, end)));
+            match placeholder {
+                Some((key, end)) => {
+                    if let Some(value) = self.strings.get(&key) {
+                        result.push_str(&value.reveal());
+                    }
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    result.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        result.push_str(rest);
+        result
+    }
+
+    /// Returns the version of this entry that was current at the specified
+    /// time, or None if the entry didn't exist yet according to its history.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::{Duration, Utc};
+    /// use kpdb::Entry;
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.set_password("old");
+    /// let old = entry.clone();
+    /// entry.history.push(old);
+    /// entry.set_password("new");
+    /// entry.last_modified = entry.last_modified + Duration::days(7);
+    ///
+    /// let version = entry.history_at(Utc::now() + Duration::days(1)).unwrap();
+    /// assert_eq!(version.password(), Some("old"));
+    /// ```
+    pub fn history_at(&self, when: DateTime<Utc>) -> Option<&Entry> {
+        if self.last_modified <= when {
+            return Some(self);
+        }
+        self.history
+            .iter()
+            .filter(|e| e.last_modified <= when)
+            .max_by_key(|e| e.last_modified)
+    }
+
+    /// Returns whether this entry contains no data.
+    ///
+    /// An entry is empty when all its strings, including the title, username,
+    /// password, URL, notes and custom fields, are empty or absent and it has
+    /// no binaries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Entry;
+    ///
+    /// let mut entry = Entry::new();
+    /// assert!(entry.is_empty());
+    ///
+    /// entry.set_username("user");
+    /// assert!(!entry.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.binaries.is_empty() && self.strings.values().all(|v| v.is_empty())
+    }
+
+    /// Merges the other version of this entry into the current entry.
+    ///
+    /// The policy decides which version becomes the current one, the other
+    /// version is added to the history. The histories of both versions are
+    /// combined, versions with the same modification time and content are
+    /// only kept once and the history is ordered from old to new. The
+    /// history isn't trimmed to the limits of the database, see
+    /// `Database::trim_history`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::Duration;
+    /// use kpdb::{Entry, MergePolicy};
+    ///
+    /// let mut local = Entry::new();
+    /// local.set_password("old");
+    /// let mut remote = local.clone();
+    /// remote.set_password("new");
+    /// remote.last_modified = local.last_modified + Duration::seconds(1);
+    ///
+    /// local.merge_from(&remote, MergePolicy::KeepNewer);
+    /// assert_eq!(local.password(), Some("new"));
+    /// assert_eq!(local.history[0].password(), Some("old"));
+    /// ```
+    pub fn merge_from(&mut self, other: &Entry, policy: MergePolicy) {
+        let (current, older) = if policy.prefers_remote(self, other) {
+            (other, &*self)
+        } else {
+            (&*self, other)
+        };
+
+        let mut snapshot = older.clone();
+        snapshot.history = Vec::new();
+        let mut history: Vec<Entry> = Vec::new();
+        for old in self
+            .history
+            .iter()
+            .chain(other.history.iter())
+            .chain(Some(&snapshot))
+        {
+            let is_same = |e: &Entry| e.last_modified == old.last_modified && e.content_eq(old);
+            if !is_same(current) && !history.iter().any(is_same) {
+                history.push(old.clone());
+            }
+        }
+        history.sort_by_key(|e| e.last_modified);
+
+        let mut merged = current.clone();
+        merged.history = history;
+        merged.set_parent(self.parent);
+        *self = merged;
+    }
+
+    /// Removes the standard strings with an empty value, like KeePass does
+    /// when saving.
+    ///
+    /// Custom strings with an empty value are only removed when remove_custom
+    /// is true.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Entry;
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.set_title("Email");
+    /// entry.set_url("");
+    ///
+    /// entry.normalize_strings(false);
+    /// assert_eq!(entry.title(), Some("Email"));
+    /// assert_eq!(entry.url(), None);
+    /// ```
+    pub fn normalize_strings(&mut self, remove_custom: bool) {
+        self.strings.retain(|key, val| {
+            let is_custom = matches!(*key, StringKey::Other(_));
+            (is_custom && !remove_custom) || !val.is_empty()
+        });
+    }
+
     /// Gets the notes string if any.
     pub fn notes(&self) -> Option<&str> {
         self.other(StringKey::Notes)
@@ -108,11 +544,7 @@ impl Entry {
 
     /// Gets an other string if any.
     pub fn other(&self, key: StringKey) -> Option<&str> {
-        match self.strings.get(&key) {
-            Some(&StringValue::Plain(ref string)) => Some(string),
-            Some(&StringValue::Protected(ref secstr)) => str::from_utf8(secstr.unsecure()).ok(),
-            None => None,
-        }
+        self.strings.get(&key).and_then(|value| value.as_str())
     }
 
     /// Gets the password string if any.
@@ -120,6 +552,54 @@ impl Entry {
         self.other(StringKey::Password)
     }
 
+    /// Gets the estimated entropy of the password in bits, zero when there
+    /// is no password.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Entry;
+    ///
+    /// let mut entry = Entry::new();
+    /// assert_eq!(entry.password_entropy(), 0);
+    ///
+    /// entry.set_password("1234");
+    /// assert_eq!(entry.password_entropy(), 13);
+    /// ```
+    pub fn password_entropy(&self) -> u32 {
+        self.password().map_or(0, utils::password_entropy)
+    }
+
+    /// Returns a copy of this entry without secrets.
+    ///
+    /// Every protected string is replaced by a placeholder and protected
+    /// binaries are removed, in the entry and its history.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Entry;
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.set_title("Title");
+    /// entry.set_password("secret");
+    ///
+    /// let redacted = entry.redacted();
+    /// assert_eq!(redacted.title(), Some("Title"));
+    /// assert_eq!(redacted.password(), Some("***"));
+    /// ```
+    pub fn redacted(&self) -> Entry {
+        let mut entry = self.clone();
+        entry.redact();
+        entry
+    }
+
+    /// Sets the standard icon and removes the custom icon of the entry.
+    pub fn set_icon(&mut self, icon: Icon) {
+        self.icon = icon;
+        self.custom_icon_uuid = None;
+    }
+
     /// Sets the notes string value.
     pub fn set_notes<S: Into<String>>(&mut self, val: S) {
         self.strings
@@ -137,6 +617,30 @@ impl Entry {
             .insert(StringKey::Password, StringValue::new(val, common::PROTECT_PASSWORD_DEFAULT));
     }
 
+    /// Sets the password string value and keeps the old password in the
+    /// history.
+    ///
+    /// A snapshot of the entry is added to the history first, after which
+    /// the password is changed and the modification time is updated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Entry;
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.set_password("old");
+    /// entry.set_password_with_history("new");
+    ///
+    /// assert_eq!(entry.password(), Some("new"));
+    /// assert_eq!(entry.history[0].password(), Some("old"));
+    /// ```
+    pub fn set_password_with_history<S: Into<String>>(&mut self, val: S) {
+        self.add_history_snapshot();
+        self.set_password(val);
+        self.last_modified = Utc::now();
+    }
+
     /// Sets the title string value.
     pub fn set_title<S: Into<String>>(&mut self, val: S) {
         self.strings
@@ -165,13 +669,73 @@ impl Entry {
         self.other(StringKey::Url)
     }
 
+    /// Gets the lowercase host of the url string if any.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Entry;
+    ///
+    /// let mut entry = Entry::new();
+    /// entry.set_url("https://Mail.Google.com/mail/");
+    /// assert_eq!(entry.url_host(), Some(String::from("mail.google.com")));
+    /// ```
+    pub fn url_host(&self) -> Option<String> {
+        self.url().and_then(utils::url_host)
+    }
+
     /// Gets the username string if any.
     pub fn username(&self) -> Option<&str> {
         self.other(StringKey::Username)
     }
+
+    fn redact(&mut self) {
+        for value in self.strings.values_mut() {
+            if let StringValue::Protected(_) = *value {
+                *value = StringValue::new(common::REDACTED_PLACEHOLDER, true);
+            }
+        }
+        self.binaries.retain(|_, value| !value.is_protected());
+        for old in self.history.iter_mut() {
+            old.redact();
+        }
+    }
+
+    /// Sets the parent of the entry and of its history entries.
+    pub(crate) fn set_parent(&mut self, parent: GroupUuid) {
+        self.parent = parent;
+        for old in self.history.iter_mut() {
+            old.parent = parent;
+        }
+    }
+}
+
+fn expiry_string(entry: &Entry) -> Option<String> {
+    if entry.expires {
+        Some(format!("{:?}", entry.expiry_time))
+    } else {
+        None
+    }
+}
+
+fn placeholder_key(name: &str) -> Option<StringKey> {
+    match name.to_uppercase().as_str() {
+        "NOTES" => Some(StringKey::Notes),
+        "PASSWORD" => Some(StringKey::Password),
+        "TITLE" => Some(StringKey::Title),
+        "URL" => Some(StringKey::Url),
+        "USERNAME" => Some(StringKey::Username),
+        _ => match name.get(..2) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("S:") => {
+                Some(StringKey::from_string(&name[2..]))
+            }
+            _ => None,
+        },
+    }
 }
 
 impl Default for Entry {
+    #[allow(deprecated)]
     fn default() -> Entry {
         let now = Utc::now();
         Entry {
@@ -263,23 +827,22 @@ impl Times for Entry {
 mod tests {
 
     use super::*;
+    use crate::types::CustomIconUuid;
+    use crate::types::EntryField;
     use crate::types::EntryUuid;
     use crate::types::Icon;
-    use crate::types::Obfuscation;
     use crate::types::StringKey;
     use crate::types::StringsMap;
     use crate::utils::test::approx_equal_datetime;
-    use chrono::Utc;
+    use chrono::{Duration, Utc};
+    use secstr::SecStr;
     use std::collections::HashMap;
 
     #[test]
     fn test_new_returns_correct_instance() {
         let now = Utc::now();
         let entry = Entry::new();
-        assert_eq!(entry.associations, Vec::new());
-        assert_eq!(entry.auto_type_def_sequence, "");
-        assert_eq!(entry.auto_type_enabled, true);
-        assert_eq!(entry.auto_type_obfuscation, Obfuscation::None);
+        assert_eq!(entry.auto_type(), AutoType::default());
         assert_eq!(entry.background_color, None);
         assert_eq!(entry.binaries, HashMap::new());
         assert!(approx_equal_datetime(entry.creation_time, now));
@@ -299,6 +862,285 @@ mod tests {
         assert!(entry.uuid != EntryUuid::nil());
     }
 
+    #[test]
+    fn test_content_eq_ignores_volatile_fields() {
+        let mut entry = Entry::new();
+        entry.set_title("title");
+        entry.set_password("password");
+
+        let mut other = entry.clone();
+        other.uuid = EntryUuid::new_random();
+        other.last_accessed = entry.last_accessed + Duration::days(1);
+        other.last_modified = entry.last_modified + Duration::days(1);
+        other.usage_count = 5;
+        other.history.push(entry.clone());
+        other
+            .strings
+            .insert(StringKey::Title, StringValue::new("title", true));
+        assert!(entry.content_eq(&other));
+    }
+
+    #[test]
+    fn test_content_eq_detects_changed_content() {
+        let entry = Entry::new();
+
+        let mut other = entry.clone();
+        other.set_title("title");
+        assert!(!entry.content_eq(&other));
+
+        let mut other = entry.clone();
+        other.tags = String::from("tag");
+        assert!(!entry.content_eq(&other));
+
+        let mut other = entry.clone();
+        other.set_icon(Icon::World);
+        assert!(!entry.content_eq(&other));
+    }
+
+    #[test]
+    fn test_diff_with_equal_entries_returns_empty_vec() {
+        let mut entry = Entry::new();
+        entry.set_title("title");
+        entry.set_password("password");
+        assert_eq!(entry.diff(&entry.clone()), Vec::new());
+    }
+
+    #[test]
+    fn test_diff_returns_changed_fields() {
+        let mut old = Entry::new();
+        old.set_title("title");
+        old.set_password("old");
+        old.set_notes("notes");
+
+        let mut new = old.clone();
+        new.set_password("new");
+        new.set_url("url");
+        new.strings.remove(&StringKey::Notes);
+        new.icon = Icon::World;
+        new.tags = String::from("tag");
+
+        let diffs = old.diff(&new);
+        let fields: Vec<EntryField> = diffs.iter().map(|d| d.field.clone()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                EntryField::String(StringKey::Notes),
+                EntryField::String(StringKey::Password),
+                EntryField::String(StringKey::Url),
+                EntryField::Icon,
+                EntryField::Tags,
+            ]
+        );
+        assert_eq!(diffs[0].new, None);
+        assert_eq!(diffs[1].old, Some(StringValue::new("old", true)));
+        assert_eq!(diffs[1].new, Some(StringValue::new("new", true)));
+        assert_eq!(diffs[2].old, None);
+        assert_eq!(diffs[3].old, Some(StringValue::new("0", false)));
+        assert_eq!(diffs[3].new, Some(StringValue::new("1", false)));
+    }
+
+    #[test]
+    fn test_decoded_field_returns_none_on_default_entry() {
+        let entry = Entry::default();
+        let key = StringKey::from_string("Secret");
+        assert!(entry.decoded_field(&key, FieldEncoding::Hex).is_none());
+    }
+
+    #[test]
+    fn test_decoded_field_returns_decoded_data() {
+        let key = StringKey::from_string("Secret");
+        let mut entry = Entry::default();
+        entry.set_other(key.clone(), "deadbeef");
+        let data = entry
+            .decoded_field(&key, FieldEncoding::Hex)
+            .unwrap()
+            .unwrap();
+        assert_eq!(data, vec![0xde, 0xad, 0xbe, 0xef]);
+        entry.set_other(key.clone(), "not hex");
+        assert!(entry
+            .decoded_field(&key, FieldEncoding::Hex)
+            .unwrap()
+            .is_err());
+    }
+
+    #[test]
+    fn test_add_history_snapshot_adds_copy_without_history() {
+        let mut entry = Entry::new();
+        entry.set_title("first");
+        entry.add_history_snapshot();
+        entry.set_title("second");
+        entry.add_history_snapshot();
+        assert_eq!(entry.history.len(), 2);
+        assert_eq!(entry.history[0].title(), Some("first"));
+        assert_eq!(entry.history[1].title(), Some("second"));
+        assert_eq!(entry.history[1].history, Vec::new());
+        assert_eq!(entry.history[1].uuid, entry.uuid);
+    }
+
+    #[test]
+    fn test_cmp_by_compares_strings_case_insensitively() {
+        let mut lower = Entry::new();
+        lower.set_username("alice");
+        lower.set_url("https://b.example.com");
+        let mut upper = Entry::new();
+        upper.set_username("Bob");
+        upper.set_url("HTTPS://A.example.com");
+        assert_eq!(lower.cmp_by(&upper, SortField::Username), Ordering::Less);
+        assert_eq!(lower.cmp_by(&upper, SortField::Url), Ordering::Greater);
+        assert_eq!(Entry::new().cmp_by(&lower, SortField::Username), Ordering::Less);
+        assert_eq!(Entry::new().cmp_by(&Entry::new(), SortField::Title), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_cmp_by_compares_times_and_usage_count() {
+        let old = Entry::new();
+        let mut new = old.clone();
+        new.creation_time = old.creation_time + Duration::days(1);
+        new.last_modified = old.last_modified + Duration::days(1);
+        new.usage_count = 3;
+        assert_eq!(old.cmp_by(&new, SortField::CreationTime), Ordering::Less);
+        assert_eq!(new.cmp_by(&old, SortField::LastModified), Ordering::Greater);
+        assert_eq!(old.cmp_by(&new, SortField::UsageCount), Ordering::Less);
+    }
+
+    #[test]
+    fn test_merge_from_with_keep_newer_keeps_newer_version() {
+        let (local, remote) = diverged_entries();
+
+        let mut merged = local.clone();
+        merged.merge_from(&remote, MergePolicy::KeepNewer);
+        assert_eq!(merged.title(), Some("remote"));
+        assert_eq!(merged.history.len(), 2);
+        assert_eq!(merged.history[1].title(), Some("local"));
+
+        let mut merged = remote.clone();
+        merged.merge_from(&local, MergePolicy::KeepNewer);
+        assert_eq!(merged.title(), Some("remote"));
+        assert_eq!(merged.history[1].title(), Some("local"));
+    }
+
+    #[test]
+    fn test_merge_from_with_keep_local_and_keep_remote() {
+        let (local, remote) = diverged_entries();
+
+        let mut merged = local.clone();
+        merged.merge_from(&remote, MergePolicy::KeepLocal);
+        assert_eq!(merged.title(), Some("local"));
+        assert_eq!(merged.history[1].title(), Some("remote"));
+
+        let mut merged = remote.clone();
+        merged.merge_from(&local, MergePolicy::KeepRemote);
+        assert_eq!(merged.title(), Some("local"));
+        assert_eq!(merged.history[1].title(), Some("remote"));
+        assert!(merged.history.iter().all(|e| e.parent == remote.parent));
+    }
+
+    #[test]
+    fn test_merge_from_combines_history_without_duplicates() {
+        let (mut local, mut remote) = diverged_entries();
+        let mut extra = remote.clone();
+        extra.history = Vec::new();
+        extra.set_title("extra");
+        extra.last_modified = remote.history[0].last_modified - Duration::days(1);
+        remote.history.insert(0, extra);
+        local.history.push(remote.history[1].clone());
+
+        local.merge_from(&remote, MergePolicy::KeepNewer);
+        let titles: Vec<_> = local.history.iter().map(|e| e.title().unwrap()).collect();
+        assert_eq!(titles, vec!["extra", "base", "local"]);
+
+        let again = local.clone();
+        local.merge_from(&again, MergePolicy::KeepNewer);
+        assert_eq!(local, again);
+    }
+
+    #[test]
+    fn test_merge_from_keeps_versions_with_same_time_and_different_content() {
+        let (mut local, mut remote) = diverged_entries();
+        remote.last_modified = local.last_modified;
+
+        local.merge_from(&remote, MergePolicy::KeepLocal);
+        let titles: Vec<_> = local.history.iter().map(|e| e.title().unwrap()).collect();
+        assert_eq!(titles, vec!["base", "remote"]);
+        assert_eq!(local.title(), Some("local"));
+    }
+
+    #[test]
+    fn test_history_at_returns_version_current_at_time() {
+        let now = Utc::now();
+        let mut entry = Entry::default();
+        for (days, password) in [(30, "first"), (20, "second")] {
+            let mut old = entry.clone();
+            old.set_password(password);
+            old.last_modified = now - Duration::days(days);
+            entry.history.push(old);
+        }
+        entry.history.reverse();
+        entry.set_password("current");
+        entry.last_modified = now - Duration::days(10);
+
+        assert_eq!(entry.history_at(now).unwrap().password(), Some("current"));
+        assert_eq!(
+            entry
+                .history_at(now - Duration::days(10))
+                .unwrap()
+                .password(),
+            Some("current")
+        );
+        assert_eq!(
+            entry
+                .history_at(now - Duration::days(15))
+                .unwrap()
+                .password(),
+            Some("second")
+        );
+        assert_eq!(
+            entry
+                .history_at(now - Duration::days(25))
+                .unwrap()
+                .password(),
+            Some("first")
+        );
+        assert_eq!(entry.history_at(now - Duration::days(35)), None);
+    }
+
+    #[test]
+    fn test_is_empty_returns_correct_value() {
+        let mut entry = Entry::new();
+        assert!(entry.is_empty());
+
+        entry.set_title("");
+        assert!(entry.is_empty());
+
+        let key = StringKey::from_string("Custom");
+        entry.set_other(key, "value");
+        assert!(!entry.is_empty());
+
+        let mut entry = Entry::new();
+        entry
+            .binaries
+            .insert(BinaryKey(String::from("file")), BinaryValue::Plain(vec![1]));
+        assert!(!entry.is_empty());
+    }
+
+    #[test]
+    fn test_normalize_strings_removes_empty_strings() {
+        let key = StringKey::from_string("Custom");
+        let mut entry = Entry::new();
+        entry.set_title("title");
+        entry.set_url("");
+        entry.set_other(key.clone(), "");
+
+        entry.normalize_strings(false);
+        assert_eq!(entry.title(), Some("title"));
+        assert!(!entry.strings.contains_key(&StringKey::Url));
+        assert!(entry.strings.contains_key(&key));
+
+        entry.normalize_strings(true);
+        assert!(!entry.strings.contains_key(&key));
+        assert_eq!(entry.strings.len(), 1);
+    }
+
     #[test]
     fn test_notes_returns_none_on_default_entry() {
         let entry = Entry::default();
@@ -318,6 +1160,50 @@ mod tests {
         assert_eq!(entry.password(), None);
     }
 
+    #[test]
+    fn test_redacted_replaces_protected_values() {
+        let mut entry = Entry::new();
+        entry.set_title("title");
+        entry.set_password("password");
+        entry.set_other(StringKey::Other(String::from("pin")), "1234");
+        entry
+            .strings
+            .insert(StringKey::Other(String::from("secret")), StringValue::new("s", true));
+        entry
+            .binaries
+            .insert(BinaryKey(String::from("plain")), BinaryValue::Plain(vec![1]));
+        entry.binaries.insert(
+            BinaryKey(String::from("protected")),
+            BinaryValue::Protected(SecStr::new(vec![2])),
+        );
+        entry.history.push(entry.clone());
+
+        let redacted = entry.redacted();
+        for e in [&redacted, &redacted.history[0]] {
+            assert_eq!(e.title(), Some("title"));
+            assert_eq!(e.password(), Some("***"));
+            assert_eq!(e.other(StringKey::Other(String::from("pin"))), Some("1234"));
+            assert_eq!(
+                e.strings.get(&StringKey::Other(String::from("secret"))),
+                Some(&StringValue::new("***", true))
+            );
+            assert_eq!(e.binaries.len(), 1);
+            assert!(e.binaries.contains_key(&BinaryKey(String::from("plain"))));
+        }
+        assert_eq!(entry.password(), Some("password"));
+    }
+
+    #[test]
+    fn test_set_icon_sets_icon_and_removes_custom_icon() {
+        let mut entry = Entry {
+            custom_icon_uuid: Some(CustomIconUuid::new_random()),
+            ..Entry::default()
+        };
+        entry.set_icon(Icon::Key);
+        assert_eq!(entry.icon, Icon::Key);
+        assert_eq!(entry.custom_icon_uuid, None);
+    }
+
     #[test]
     fn test_set_notes_sets_notes() {
         let mut entry = Entry::default();
@@ -340,6 +1226,21 @@ mod tests {
         assert_eq!(entry.password(), Some("test"));
     }
 
+    #[test]
+    fn test_set_password_with_history_keeps_old_password() {
+        let mut entry = Entry::new();
+        entry.set_password("old");
+        entry.last_modified = Utc::now() - Duration::days(30);
+        let old_modified = entry.last_modified;
+
+        entry.set_password_with_history("new");
+        assert_eq!(entry.password(), Some("new"));
+        assert_eq!(entry.history.len(), 1);
+        assert_eq!(entry.history[0].password(), Some("old"));
+        assert_eq!(entry.history[0].last_modified, old_modified);
+        assert!(approx_equal_datetime(entry.last_modified, Utc::now()));
+    }
+
     #[test]
     fn test_set_title_sets_title() {
         let mut entry = Entry::default();
@@ -373,20 +1274,74 @@ mod tests {
         assert_eq!(entry.url(), None);
     }
 
+    #[test]
+    fn test_url_host_returns_correct_value() {
+        let mut entry = Entry::default();
+        assert_eq!(entry.url_host(), None);
+        entry.set_url("HTTPS://www.Example.com:443/login");
+        assert_eq!(entry.url_host(), Some(String::from("www.example.com")));
+    }
+
     #[test]
     fn test_username_returns_none_on_default_entry() {
         let entry = Entry::default();
         assert_eq!(entry.username(), None);
     }
 
+    #[test]
+    fn test_is_expired_returns_correct_value() {
+        let now = Utc::now();
+        let mut entry = Entry {
+            expiry_time: now - Duration::days(1),
+            ..Entry::default()
+        };
+        assert!(!entry.is_expired(now));
+
+        entry.expires = true;
+        assert!(entry.is_expired(now));
+
+        entry.expiry_time = now + Duration::days(1);
+        assert!(!entry.is_expired(now));
+    }
+
+    #[test]
+    fn test_set_expiry_sets_expires_and_expiry_time() {
+        let time = Utc::now() + Duration::days(1);
+        let mut entry = Entry::default();
+        entry.set_expiry(Some(time));
+        assert!(entry.expires);
+        assert_eq!(entry.expiry_time, time);
+
+        entry.set_expiry(None);
+        assert!(!entry.expires);
+        assert_eq!(entry.expiry_time, time);
+    }
+
+    #[test]
+    fn test_age_returns_correct_duration() {
+        let now = Utc::now();
+        let entry = Entry {
+            creation_time: now - Duration::days(2),
+            ..Entry::default()
+        };
+        assert_eq!(entry.age(now), Duration::days(2));
+    }
+
+    #[test]
+    fn test_expand_placeholders_replaces_known_placeholders() {
+        let mut entry = Entry::new();
+        entry.set_title("ProtonMail");
+        entry.set_password("ppass");
+        entry.set_other(StringKey::Other(String::from("PIN")), "1234");
+        let text = entry.expand_placeholders("{{Title}} {PASSWORD}{s:PIN}{NOTES}{UNKNOWN} {");
+        assert_eq!(text, "{ProtonMail} ppass1234{UNKNOWN} {");
+    }
+
     #[test]
     fn test_default_returns_correct_instance() {
         let now = Utc::now();
         let entry = Entry::default();
-        assert_eq!(entry.associations, Vec::new());
-        assert_eq!(entry.auto_type_def_sequence, "");
-        assert_eq!(entry.auto_type_enabled, true);
-        assert_eq!(entry.auto_type_obfuscation, Obfuscation::None);
+        assert_eq!(entry.auto_type(), AutoType::default());
         assert_eq!(entry.background_color, None);
         assert_eq!(entry.binaries, HashMap::new());
         assert!(approx_equal_datetime(entry.creation_time, now));
@@ -405,4 +1360,23 @@ mod tests {
         assert_eq!(entry.usage_count, 0);
         assert_eq!(entry.uuid, EntryUuid::nil());
     }
+
+    // Returns two versions of an entry with a common base version in their
+    // history, the remote version was modified last.
+    fn diverged_entries() -> (Entry, Entry) {
+        let mut base = Entry::new();
+        base.set_title("base");
+        base.last_modified = Utc::now() - Duration::days(3);
+
+        let mut local = base.clone();
+        local.add_history_snapshot();
+        local.set_title("local");
+        local.last_modified = base.last_modified + Duration::days(1);
+
+        let mut remote = base.clone();
+        remote.add_history_snapshot();
+        remote.set_title("remote");
+        remote.last_modified = base.last_modified + Duration::days(2);
+        (local, remote)
+    }
 }
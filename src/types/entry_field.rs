@@ -0,0 +1,37 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::string_key::StringKey;
+
+/// A field of an entry.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum EntryField {
+    /// The string with the specified key.
+    String(StringKey),
+
+    /// The background color.
+    BackgroundColor,
+
+    /// The identifier of the custom icon.
+    CustomIcon,
+
+    /// The expiry date and time.
+    ExpiryTime,
+
+    /// The foreground color.
+    ForegroundColor,
+
+    /// The icon.
+    Icon,
+
+    /// The override URL.
+    OverrideUrl,
+
+    /// The tags.
+    Tags,
+}
@@ -6,28 +6,49 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use super::binary_id::BinaryId;
+use super::binary_key::BinaryKey;
+use super::custom_icon_uuid::CustomIconUuid;
+use super::entry_uuid::EntryUuid;
+use super::field_encoding::FieldEncoding;
+use super::group_uuid::GroupUuid;
+use super::version::Version;
 use crate::rust_crypto::symmetriccipher::SymmetricCipherError;
 use std::error;
 use std::fmt;
 use std::io;
+#[cfg(any(feature = "database", feature = "keyfile"))]
 use xml::reader as xmlreader;
+#[cfg(any(feature = "database", feature = "keyfile"))]
 use xml::writer as xmlwriter;
 
 /// Error type for database errors.
 #[derive(Debug)]
 pub enum Error {
+    /// The comment is larger than the comment header can hold.
+    CommentTooLarge(usize),
+
     /// Error during the encryption or decryption of the database.
     CryptoError(SymmetricCipherError),
 
+    /// The password is empty or consists of whitespace only.
+    EmptyPassword,
+
     /// The hash of a data block is invalid.
     InvalidBlockHash,
 
     /// The data block has an invalid identifier.
     InvalidBlockId(u32),
 
+    /// The block size is invalid.
+    InvalidBlockSize(usize),
+
     /// The database signature is invalid.
     InvalidDbSignature([u8; 4]),
 
+    /// The field value isn't valid for the specified encoding.
+    InvalidFieldEncoding(FieldEncoding),
+
     /// The hash of the final data block is invalid.
     InvalidFinalBlockHash([u8; 32]),
 
@@ -52,12 +73,62 @@ pub enum Error {
     /// The key file is invalid.
     InvalidKeyFile,
 
+    /// The length of the key data is invalid.
+    InvalidKeyLength(usize),
+
+    /// The number of transform rounds is invalid.
+    InvalidTransformRounds(u64),
+
     /// An I/O error has occurred.
     Io(io::Error),
 
+    /// An entry references binary data that doesn't exist.
+    MissingBinary {
+        /// Entry identifier.
+        entry: EntryUuid,
+
+        /// Binary identifier.
+        id: BinaryId,
+    },
+
+    /// An entry doesn't have a binary with the specified key.
+    MissingBinaryKey {
+        /// Entry identifier.
+        entry: EntryUuid,
+
+        /// Binary key.
+        key: BinaryKey,
+    },
+
+    /// The composite key is missing, e.g. after opening the database with a
+    /// transformed key only.
+    MissingCompositeKey,
+
+    /// The custom icon with the specified identifier doesn't exist.
+    MissingCustomIcon(CustomIconUuid),
+
+    /// The entry with the specified identifier doesn't exist.
+    MissingEntry(EntryUuid),
+
+    /// The group with the specified identifier doesn't exist.
+    MissingGroup(GroupUuid),
+
     /// The supplied header is missing.
     MissingHeader(u8),
 
+    /// The database contains more data blocks than the limit.
+    TooManyBlocks(u32),
+
+    /// The database contains more header fields than the limit.
+    TooManyHeaders(usize),
+
+    /// The database requires more transform rounds than the limit.
+    TooManyTransformRounds(u64),
+
+    /// The database contains the specified number of bytes after the final
+    /// data block.
+    TrailingData(u64),
+
     /// The compression algorithm specified in the headers is not supported.
     UnhandledCompression(u32),
 
@@ -73,9 +144,15 @@ pub enum Error {
     /// The stream encryption algorithm is not supported.
     UnhandledStreamCipher(u32),
 
+    /// The database version specified in the headers is not supported.
+    UnhandledVersion(Version),
+
     /// The specified functionality is not yet supported.
     Unimplemented(String),
 
+    /// The decompressed XML data is larger than the limit.
+    XmlDataTooLarge(u64),
+
     /// The XML contains the specified error.
     XmlError(String),
 }
@@ -83,6 +160,7 @@ pub enum Error {
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            Error::CommentTooLarge(val) => write!(f, "Comment too large: {} bytes", val),
             Error::CryptoError(err) => match err {
                 SymmetricCipherError::InvalidLength => {
                     write!(f, "Crypto error: invalid length.")
@@ -93,9 +171,12 @@ impl fmt::Display for Error {
                 }
             },
 
+            Error::EmptyPassword => write!(f, "Empty password"),
             Error::InvalidBlockHash => write!(f, "Invalid block hash"),
             Error::InvalidBlockId(val) => write!(f, "Invalid block id: {}", val),
+            Error::InvalidBlockSize(val) => write!(f, "Invalid block size: {}", val),
             Error::InvalidDbSignature(val) => write!(f, "Invalid database signature: {:?}", val),
+            Error::InvalidFieldEncoding(val) => write!(f, "Invalid field encoding: {:?}", val),
             Error::InvalidFinalBlockHash(val) => write!(f, "Invalid final block hash: {:?}", val),
             Error::InvalidHeaderSize {
                 id,
@@ -111,14 +192,34 @@ impl fmt::Display for Error {
             Error::InvalidHeaderHash => write!(f, "Invalid header hash"),
             Error::InvalidKey => write!(f, "Invalid key"),
             Error::InvalidKeyFile => write!(f, "Invalid key file"),
+            Error::InvalidKeyLength(val) => write!(f, "Invalid key length: {}", val),
+            Error::InvalidTransformRounds(val) => write!(f, "Invalid transform rounds: {}", val),
             Error::Io(ref err) => write!(f, "IO error: {}", err),
+            Error::MissingBinary { entry, ref id } => {
+                write!(f, "Missing binary: id: {}, entry: {}", id.0, entry.0)
+            }
+            Error::MissingBinaryKey { entry, ref key } => {
+                write!(f, "Missing binary key: {}, entry: {}", key.0, entry.0)
+            }
+            Error::MissingCompositeKey => write!(f, "Missing composite key"),
+            Error::MissingCustomIcon(val) => write!(f, "Missing custom icon: {}", val.0),
+            Error::MissingEntry(val) => write!(f, "Missing entry: {}", val.0),
+            Error::MissingGroup(val) => write!(f, "Missing group: {}", val.0),
             Error::MissingHeader(val) => write!(f, "Missing header: {}", val),
+            Error::TooManyBlocks(val) => write!(f, "Too many blocks, limit: {}", val),
+            Error::TooManyHeaders(val) => write!(f, "Too many headers, limit: {}", val),
+            Error::TooManyTransformRounds(val) => {
+                write!(f, "Too many transform rounds, limit: {}", val)
+            }
+            Error::TrailingData(val) => write!(f, "Trailing data: {} bytes", val),
             Error::UnhandledCompression(val) => write!(f, "Unhandled compression: {}", val),
             Error::UnhandledDbType(val) => write!(f, "Unhandled database type: {:?}", val),
             Error::UnhandledHeader(val) => write!(f, "Unhandled header: {}", val),
             Error::UnhandledMasterCipher(val) => write!(f, "Unhandled master cipher: {:?}", val),
             Error::UnhandledStreamCipher(val) => write!(f, "Unhandled stream cipher: {}", val),
+            Error::UnhandledVersion(ref val) => write!(f, "Unhandled version: {}", val),
             Error::Unimplemented(ref val) => write!(f, "Unimplemented: {}", val),
+            Error::XmlDataTooLarge(val) => write!(f, "XML data too large, limit: {}", val),
             Error::XmlError(ref val) => write!(f, "XML error: {}", val),
         }
     }
@@ -139,12 +240,14 @@ impl From<io::Error> for Error {
     }
 }
 
+#[cfg(any(feature = "database", feature = "keyfile"))]
 impl From<xmlreader::Error> for Error {
     fn from(err: xmlreader::Error) -> Error {
         Error::XmlError(format!("{}", err))
     }
 }
 
+#[cfg(any(feature = "database", feature = "keyfile"))]
 impl From<xmlwriter::Error> for Error {
     fn from(err: xmlwriter::Error) -> Error {
         Error::XmlError(format!("{}", err))
@@ -0,0 +1,25 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::entry_field::EntryField;
+use super::string_value::StringValue;
+
+/// A difference in a single field between two entries.
+///
+/// Protected strings stay protected in the old and new values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldDiff {
+    /// The field that differs.
+    pub field: EntryField,
+
+    /// The old value or None if the field was absent.
+    pub old: Option<StringValue>,
+
+    /// The new value or None if the field is absent.
+    pub new: Option<StringValue>,
+}
@@ -0,0 +1,127 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::error::Error;
+use super::result::Result;
+use base64::{engine::general_purpose, Engine as _};
+use hex::FromHex;
+
+/// The encoding of binary data stored in a string field.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FieldEncoding {
+    /// Base32 (RFC 4648), case insensitive, spaces and padding are ignored.
+    Base32,
+
+    /// Standard base64 with padding.
+    Base64,
+
+    /// Hexadecimal, case insensitive.
+    Hex,
+}
+
+impl FieldEncoding {
+    /// Attempts to decode the text using this encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::FieldEncoding;
+    ///
+    /// assert_eq!(FieldEncoding::Base32.decode("MZXW6===").unwrap(), b"foo".to_vec());
+    /// assert_eq!(FieldEncoding::Base64.decode("Zm9v").unwrap(), b"foo".to_vec());
+    /// assert_eq!(FieldEncoding::Hex.decode("666f6f").unwrap(), b"foo".to_vec());
+    /// ```
+    pub fn decode(self, text: &str) -> Result<Vec<u8>> {
+        let decoded = match self {
+            FieldEncoding::Base32 => decode_base32(text),
+            FieldEncoding::Base64 => general_purpose::STANDARD.decode(text).ok(),
+            FieldEncoding::Hex => Vec::from_hex(text).ok(),
+        };
+        decoded.ok_or(Error::InvalidFieldEncoding(self))
+    }
+}
+
+fn decode_base32(text: &str) -> Option<Vec<u8>> {
+    let mut data = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    let mut padding = false;
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        if c == '=' {
+            padding = true;
+            continue;
+        }
+        if padding {
+            return None;
+        }
+        let value = match c.to_ascii_uppercase() {
+            c @ 'A'..='Z' => c as u32 - 'A' as u32,
+            c @ '2'..='7' => c as u32 - '2' as u32 + 26,
+            _ => return None,
+        };
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            data.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(data)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_decode_with_base32_returns_correct_data() {
+        let result = FieldEncoding::Base32.decode("JBSWY3DPEHPK3PXP").unwrap();
+        assert_eq!(result, b"Hello!\xde\xad\xbe\xef".to_vec());
+    }
+
+    #[test]
+    fn test_decode_with_base32_ignores_case_spaces_and_padding() {
+        let result = FieldEncoding::Base32.decode("mzxw 6ytb oi======").unwrap();
+        assert_eq!(result, b"foobar".to_vec());
+    }
+
+    #[test]
+    fn test_decode_with_invalid_base32_returns_error() {
+        assert_invalid(FieldEncoding::Base32, "MZXW1");
+        assert_invalid(FieldEncoding::Base32, "MZ=XW");
+    }
+
+    #[test]
+    fn test_decode_with_base64_returns_correct_data() {
+        assert_eq!(FieldEncoding::Base64.decode("Zm9vYmFy").unwrap(), b"foobar".to_vec());
+    }
+
+    #[test]
+    fn test_decode_with_invalid_base64_returns_error() {
+        assert_invalid(FieldEncoding::Base64, "Zm9v!");
+    }
+
+    #[test]
+    fn test_decode_with_hex_returns_correct_data() {
+        assert_eq!(FieldEncoding::Hex.decode("DEADbeef").unwrap(), vec![0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn test_decode_with_invalid_hex_returns_error() {
+        assert_invalid(FieldEncoding::Hex, "abc");
+    }
+
+    fn assert_invalid(encoding: FieldEncoding, text: &str) {
+        match encoding.decode(text) {
+            Err(Error::InvalidFieldEncoding(e)) if e == encoding => (),
+            _ => panic!("expected InvalidFieldEncoding error"),
+        }
+    }
+}
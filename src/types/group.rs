@@ -11,6 +11,7 @@ use super::entry::Entry;
 use super::entry_uuid::EntryUuid;
 use super::group_uuid::GroupUuid;
 use super::icon::Icon;
+use super::merge_policy::MergePolicy;
 use super::times::Times;
 use chrono::{DateTime, Utc};
 use std::collections::vec_deque::VecDeque;
@@ -28,10 +29,12 @@ pub struct Group {
     /// Default auto-type sequence.
     pub def_auto_type_sequence: String,
 
-    /// Whether auto-type is enabled.
+    /// Whether auto-type is enabled, None means the setting is inherited
+    /// from the parent group (see `Database::is_auto_type_enabled`).
     pub enable_auto_type: Option<bool>,
 
-    /// Whether searching is enabled.
+    /// Whether searching is enabled, None means the setting is inherited
+    /// from the parent group (see `Database::is_searchable`).
     pub enable_searching: Option<bool>,
 
     /// Vector with entries that belong to this group.
@@ -135,8 +138,46 @@ impl Group {
         self.groups.push(group);
     }
 
+    /// Gets the notes string if any.
+    ///
+    /// Empty notes are returned as `None`, like `Entry::notes`. Group notes
+    /// are never protected in KeePass 2 databases, so they stay a plain
+    /// string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Group;
+    ///
+    /// let mut group = Group::new("group");
+    /// assert_eq!(group.notes(), None);
+    /// group.set_notes("Shared with the team");
+    /// assert_eq!(group.notes(), Some("Shared with the team"));
+    /// ```
+    pub fn notes(&self) -> Option<&str> {
+        if self.notes.is_empty() {
+            None
+        } else {
+            Some(&self.notes)
+        }
+    }
+
+    /// Sets the standard icon and removes the custom icon of the group.
+    pub fn set_icon(&mut self, icon: Icon) {
+        self.icon = icon;
+        self.custom_icon_uuid = None;
+    }
+
+    /// Sets the notes string value.
+    pub fn set_notes<S: Into<String>>(&mut self, val: S) {
+        self.notes = val.into();
+    }
+
     /// Returns an iterator over the group and sub groups.
     ///
+    /// The groups are visited breadth-first, `for group in &group` does the
+    /// same.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -175,6 +216,115 @@ impl Group {
         IterMut::new(self)
     }
 
+    /// Returns whether this group has the same content as the other group.
+    ///
+    /// The entries and sub groups are compared in order using their own
+    /// `content_eq`. The identifier, parent, expanded state, last top visible
+    /// entry, usage count and the creation, access, modification and location
+    /// change times are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{Entry, Group};
+    ///
+    /// let mut group = Group::new("Email");
+    /// group.add_entry(Entry::new());
+    ///
+    /// let mut other = group.clone();
+    /// other.is_expanded = !group.is_expanded;
+    /// other.usage_count += 1;
+    /// assert!(group.content_eq(&other));
+    ///
+    /// other.name = String::from("Bank");
+    /// assert!(!group.content_eq(&other));
+    /// ```
+    pub fn content_eq(&self, other: &Group) -> bool {
+        self.custom_icon_uuid == other.custom_icon_uuid
+            && self.def_auto_type_sequence == other.def_auto_type_sequence
+            && self.enable_auto_type == other.enable_auto_type
+            && self.enable_searching == other.enable_searching
+            && self.expires == other.expires
+            && self.expiry_time == other.expiry_time
+            && self.icon == other.icon
+            && self.name == other.name
+            && self.notes == other.notes
+            && self.entries.len() == other.entries.len()
+            && self
+                .entries
+                .iter()
+                .zip(other.entries.iter())
+                .all(|(a, b)| a.content_eq(b))
+            && self.groups.len() == other.groups.len()
+            && self
+                .groups
+                .iter()
+                .zip(other.groups.iter())
+                .all(|(a, b)| a.content_eq(b))
+    }
+
+    /// Returns whether this group has no entries and no sub groups.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{Entry, Group};
+    ///
+    /// let mut group = Group::new("Email");
+    /// assert!(group.is_empty());
+    ///
+    /// group.add_entry(Entry::new());
+    /// assert!(!group.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty() && self.groups.is_empty()
+    }
+
+    /// Merges the other version of this group into the current group.
+    ///
+    /// Entries and sub groups are matched by UUID among the direct children
+    /// and merged recursively, see `Entry::merge_from` for entries. Conflicts
+    /// are resolved using the policy, children that only exist in the other
+    /// group are added.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{Entry, Group, MergePolicy};
+    ///
+    /// let mut local = Group::new("Email");
+    /// let mut remote = local.clone();
+    /// remote.add_entry(Entry::new());
+    ///
+    /// local.merge_from(&remote, MergePolicy::KeepNewer);
+    /// assert_eq!(local.entries.len(), 1);
+    /// ```
+    pub fn merge_from(&mut self, other: &Group, policy: MergePolicy) {
+        if policy.prefers_remote(self, other) {
+            self.copy_properties(other);
+        }
+        for entry in other.entries.iter() {
+            match self.entries.iter().position(|x| x.uuid == entry.uuid) {
+                Some(x) => self.entries[x].merge_from(entry, policy),
+                None => {
+                    let mut entry = entry.clone();
+                    entry.set_parent(self.uuid);
+                    self.entries.push(entry);
+                }
+            }
+        }
+        for group in other.groups.iter() {
+            match self.groups.iter().position(|x| x.uuid == group.uuid) {
+                Some(x) => self.groups[x].merge_from(group, policy),
+                None => {
+                    let mut group = group.clone();
+                    group.parent = self.uuid;
+                    self.groups.push(group);
+                }
+            }
+        }
+    }
+
     /// Remove an entry from the current group.
     ///
     /// # Examples
@@ -216,6 +366,25 @@ impl Group {
             None => None,
         }
     }
+
+    fn copy_properties(&mut self, other: &Group) {
+        self.creation_time = other.creation_time;
+        self.custom_icon_uuid = other.custom_icon_uuid;
+        self.def_auto_type_sequence = other.def_auto_type_sequence.clone();
+        self.enable_auto_type = other.enable_auto_type;
+        self.enable_searching = other.enable_searching;
+        self.expires = other.expires;
+        self.expiry_time = other.expiry_time;
+        self.icon = other.icon;
+        self.is_expanded = other.is_expanded;
+        self.last_accessed = other.last_accessed;
+        self.last_modified = other.last_modified;
+        self.last_top_visible_entry = other.last_top_visible_entry;
+        self.location_changed = other.location_changed;
+        self.name = other.name.clone();
+        self.notes = other.notes.clone();
+        self.usage_count = other.usage_count;
+    }
 }
 
 impl Default for Group {
@@ -246,6 +415,24 @@ impl Default for Group {
     }
 }
 
+impl<'a> IntoIterator for &'a Group {
+    type Item = &'a Group;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Group {
+    type Item = &'a mut Group;
+    type IntoIter = IterMut<'a>;
+
+    fn into_iter(self) -> IterMut<'a> {
+        self.iter_mut()
+    }
+}
+
 impl Times for Group {
     fn creation_time(&self) -> DateTime<Utc> {
         self.creation_time
@@ -377,11 +564,12 @@ impl<'a> Iterator for IterMut<'a> {
 mod tests {
 
     use super::*;
+    use crate::types::CustomIconUuid;
     use crate::types::EntryUuid;
     use crate::types::GroupUuid;
     use crate::types::Icon;
     use crate::utils::test::approx_equal_datetime;
-    use chrono::Utc;
+    use chrono::{Duration, Utc};
 
     #[test]
     fn test_new_returns_correct_instance() {
@@ -431,6 +619,46 @@ mod tests {
         assert_eq!(root.groups[0], child);
     }
 
+    #[test]
+    fn test_content_eq_ignores_volatile_fields() {
+        let mut group = Group::new("group");
+        group.add_entry(Entry::new());
+
+        let mut other = group.clone();
+        other.uuid = GroupUuid::new_random();
+        other.last_accessed = group.last_accessed + Duration::days(1);
+        other.is_expanded = !group.is_expanded;
+        other.entries[0].usage_count = 5;
+        assert!(group.content_eq(&other));
+    }
+
+    #[test]
+    fn test_content_eq_detects_changed_content() {
+        let mut group = Group::new("group");
+        group.add_entry(Entry::new());
+
+        let mut other = group.clone();
+        other.notes = String::from("notes");
+        assert!(!group.content_eq(&other));
+
+        let mut other = group.clone();
+        other.entries[0].set_title("title");
+        assert!(!group.content_eq(&other));
+
+        let mut other = group.clone();
+        other.add_group(Group::new("child"));
+        assert!(!group.content_eq(&other));
+    }
+
+    #[test]
+    fn test_is_empty_returns_correct_value() {
+        let mut group = Group::new("group");
+        assert!(group.is_empty());
+
+        group.add_group(Group::new("child"));
+        assert!(!group.is_empty());
+    }
+
     #[test]
     fn test_iter_returns_correct_iterator() {
         let mut root = Group::new("root");
@@ -479,6 +707,93 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_into_iter_returns_correct_iterator() {
+        let mut root = Group::new("root");
+        root.add_group(Group::new("child"));
+
+        let names: Vec<&str> = (&root).into_iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, vec!["root", "child"]);
+
+        for group in &mut root {
+            group.name = String::from("name");
+        }
+        for group in &root {
+            assert_eq!(group.name, "name");
+        }
+    }
+
+    #[test]
+    fn test_merge_from_adds_missing_children() {
+        let mut local = Group::new("local");
+        let mut remote = local.clone();
+        let mut entry = Entry::new();
+        entry.history.push(Entry::new());
+        entry.parent = GroupUuid::new_random();
+        entry.history[0].parent = entry.parent;
+        let group = Group::new("child");
+        remote.add_entry(entry.clone());
+        remote.add_group(group.clone());
+
+        local.merge_from(&remote, MergePolicy::KeepLocal);
+        assert_eq!(local.entries.len(), 1);
+        assert_eq!(local.entries[0].uuid, entry.uuid);
+        assert_eq!(local.entries[0].parent, local.uuid);
+        assert_eq!(local.entries[0].history[0].parent, local.uuid);
+        assert_eq!(local.groups.len(), 1);
+        assert_eq!(local.groups[0].uuid, group.uuid);
+        assert_eq!(local.groups[0].parent, local.uuid);
+    }
+
+    #[test]
+    fn test_merge_from_with_keep_newer_keeps_newer_versions() {
+        let mut local = Group::new("local");
+        let mut child = Group::new("child");
+        let mut old = Entry::new();
+        old.set_title("old");
+        child.add_entry(old.clone());
+        local.add_group(child);
+
+        let mut remote = local.clone();
+        let mut new = old.clone();
+        new.set_title("new");
+        new.last_modified = old.last_modified + Duration::seconds(1);
+        remote.groups[0].entries[0] = new;
+        remote.groups[0].name = String::from("renamed");
+        remote.groups[0].last_modified = local.groups[0].last_modified + Duration::seconds(1);
+
+        let mut merged = local.clone();
+        merged.merge_from(&remote, MergePolicy::KeepNewer);
+        assert_eq!(merged.groups[0].name, "renamed");
+        assert_eq!(merged.groups[0].entries[0].title(), Some("new"));
+
+        let mut merged = remote.clone();
+        merged.merge_from(&local, MergePolicy::KeepNewer);
+        assert_eq!(merged.groups[0].name, "renamed");
+        assert_eq!(merged.groups[0].entries[0].title(), Some("new"));
+    }
+
+    #[test]
+    fn test_merge_from_with_keep_local_and_keep_remote() {
+        let mut local = Group::new("local");
+        let mut entry = Entry::new();
+        entry.set_title("local");
+        local.add_entry(entry);
+        let mut remote = local.clone();
+        remote.name = String::from("remote");
+        remote.entries[0].set_title("remote");
+
+        let mut merged = local.clone();
+        merged.merge_from(&remote, MergePolicy::KeepLocal);
+        assert_eq!(merged.name, "local");
+        assert_eq!(merged.entries[0].title(), Some("local"));
+
+        let mut merged = local.clone();
+        merged.merge_from(&remote, MergePolicy::KeepRemote);
+        assert_eq!(merged.name, "remote");
+        assert_eq!(merged.entries[0].title(), Some("remote"));
+    }
+
     #[test]
     fn test_remove_entry_removes_entry() {
         let mut group = Group::new("Sample");
@@ -505,6 +820,41 @@ mod tests {
         assert_eq!(parent.remove_group(child.uuid), None);
     }
 
+    #[test]
+    fn test_notes_returns_none_for_empty_notes() {
+        let mut group = Group::new("group");
+        assert_eq!(group.notes(), None);
+        group.set_notes("notes");
+        assert_eq!(group.notes(), Some("notes"));
+        assert_eq!(group.notes, "notes");
+        group.set_notes("");
+        assert_eq!(group.notes(), None);
+    }
+
+    #[test]
+    fn test_set_icon_sets_icon_and_removes_custom_icon() {
+        let mut group = Group {
+            custom_icon_uuid: Some(CustomIconUuid::new_random()),
+            ..Group::default()
+        };
+        group.set_icon(Icon::Key);
+        assert_eq!(group.icon, Icon::Key);
+        assert_eq!(group.custom_icon_uuid, None);
+    }
+
+    #[test]
+    fn test_set_expiry_sets_expires_and_expiry_time() {
+        let time = Utc::now() + Duration::days(1);
+        let mut group = Group::default();
+        group.set_expiry(Some(time));
+        assert!(group.expires);
+        assert_eq!(group.expiry_time, time);
+
+        group.set_expiry(None);
+        assert!(!group.expires);
+        assert_eq!(group.expiry_time, time);
+    }
+
     #[test]
     fn test_default_returns_correct_instance() {
         let now = Utc::now();
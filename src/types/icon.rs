@@ -6,9 +6,14 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use super::icon_category::IconCategory;
 use std::error;
 use std::fmt;
 use std::result::Result;
+use std::str::FromStr;
+
+/// The number of standard icons.
+const ICON_COUNT: i32 = 69;
 
 /// The icon of an entry or group.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
@@ -222,6 +227,88 @@ pub enum Icon {
 }
 
 impl Icon {
+    /// Gets the category of the icon.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::{Icon, IconCategory};
+    ///
+    /// assert_eq!(Icon::Key.category(), IconCategory::Security);
+    /// assert_eq!(Icon::Email.category(), IconCategory::Communication);
+    /// ```
+    pub fn category(self) -> IconCategory {
+        match self {
+            Icon::World
+            | Icon::UserCommunication
+            | Icon::WorldSocket
+            | Icon::IRCommunication
+            | Icon::WorldStar
+            | Icon::Email
+            | Icon::Inbox
+            | Icon::WorldComputer
+            | Icon::Smb
+            | Icon::EmailSearch
+            | Icon::Wikipedia
+            | Icon::Phone => IconCategory::Communication,
+            Icon::Server
+            | Icon::Parts
+            | Icon::Energy
+            | Icon::Scanner
+            | Icon::CDRom
+            | Icon::Monitor
+            | Icon::Configuration
+            | Icon::Screen
+            | Icon::EnergyCareful
+            | Icon::Disk
+            | Icon::Drive
+            | Icon::Console
+            | Icon::Printer
+            | Icon::Run
+            | Icon::Settings
+            | Icon::Memory
+            | Icon::Tool
+            | Icon::Tux
+            | Icon::Apple => IconCategory::System,
+            Icon::Digicam | Icon::QuickTime | Icon::Icons | Icon::Thumbnail | Icon::Book => {
+                IconCategory::Media
+            }
+            Icon::Key
+            | Icon::Warning
+            | Icon::Identity
+            | Icon::MultipleKeys
+            | Icon::EncryptedTerminal
+            | Icon::LockOpen
+            | Icon::PaperLocked
+            | Icon::UserKey
+            | Icon::Certificate => IconCategory::Security,
+            Icon::MarkedDirectory
+            | Icon::Notepad
+            | Icon::PaperReady
+            | Icon::ClipboardReady
+            | Icon::PaperNew
+            | Icon::Archive
+            | Icon::Banking
+            | Icon::Clock
+            | Icon::PaperFlag
+            | Icon::RecycleBin
+            | Icon::Note
+            | Icon::Expired
+            | Icon::Info
+            | Icon::Package
+            | Icon::Folder
+            | Icon::FolderOpen
+            | Icon::FolderPackage
+            | Icon::Checked
+            | Icon::Pen
+            | Icon::Listing
+            | Icon::Home
+            | Icon::Star
+            | Icon::Feather
+            | Icon::Money => IconCategory::Misc,
+        }
+    }
+
     /// Attempts to convert an identifier to an icon.
     ///
     /// # Examples
@@ -310,6 +397,89 @@ impl Icon {
         }
     }
 
+    /// Gets the icon's name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Icon;
+    ///
+    /// assert_eq!(Icon::Key.name(), "Key");
+    /// ```
+    pub fn name(self) -> &'static str {
+        match self {
+            Icon::Key => "Key",
+            Icon::World => "World",
+            Icon::Warning => "Warning",
+            Icon::Server => "Server",
+            Icon::MarkedDirectory => "MarkedDirectory",
+            Icon::UserCommunication => "UserCommunication",
+            Icon::Parts => "Parts",
+            Icon::Notepad => "Notepad",
+            Icon::WorldSocket => "WorldSocket",
+            Icon::Identity => "Identity",
+            Icon::PaperReady => "PaperReady",
+            Icon::Digicam => "Digicam",
+            Icon::IRCommunication => "IRCommunication",
+            Icon::MultipleKeys => "MultipleKeys",
+            Icon::Energy => "Energy",
+            Icon::Scanner => "Scanner",
+            Icon::WorldStar => "WorldStar",
+            Icon::CDRom => "CDRom",
+            Icon::Monitor => "Monitor",
+            Icon::Email => "Email",
+            Icon::Configuration => "Configuration",
+            Icon::ClipboardReady => "ClipboardReady",
+            Icon::PaperNew => "PaperNew",
+            Icon::Screen => "Screen",
+            Icon::EnergyCareful => "EnergyCareful",
+            Icon::Inbox => "Inbox",
+            Icon::Disk => "Disk",
+            Icon::Drive => "Drive",
+            Icon::QuickTime => "QuickTime",
+            Icon::EncryptedTerminal => "EncryptedTerminal",
+            Icon::Console => "Console",
+            Icon::Printer => "Printer",
+            Icon::Icons => "Icons",
+            Icon::Run => "Run",
+            Icon::Settings => "Settings",
+            Icon::WorldComputer => "WorldComputer",
+            Icon::Archive => "Archive",
+            Icon::Banking => "Banking",
+            Icon::Smb => "Smb",
+            Icon::Clock => "Clock",
+            Icon::EmailSearch => "EmailSearch",
+            Icon::PaperFlag => "PaperFlag",
+            Icon::Memory => "Memory",
+            Icon::RecycleBin => "RecycleBin",
+            Icon::Note => "Note",
+            Icon::Expired => "Expired",
+            Icon::Info => "Info",
+            Icon::Package => "Package",
+            Icon::Folder => "Folder",
+            Icon::FolderOpen => "FolderOpen",
+            Icon::FolderPackage => "FolderPackage",
+            Icon::LockOpen => "LockOpen",
+            Icon::PaperLocked => "PaperLocked",
+            Icon::Checked => "Checked",
+            Icon::Pen => "Pen",
+            Icon::Thumbnail => "Thumbnail",
+            Icon::Book => "Book",
+            Icon::Listing => "Listing",
+            Icon::UserKey => "UserKey",
+            Icon::Tool => "Tool",
+            Icon::Home => "Home",
+            Icon::Star => "Star",
+            Icon::Tux => "Tux",
+            Icon::Feather => "Feather",
+            Icon::Apple => "Apple",
+            Icon::Wikipedia => "Wikipedia",
+            Icon::Money => "Money",
+            Icon::Certificate => "Certificate",
+            Icon::Phone => "Phone",
+        }
+    }
+
     /// Gets the icon's identifier.
     ///
     /// # Examples
@@ -325,17 +495,43 @@ impl Icon {
     }
 }
 
+impl fmt::Display for Icon {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Icon {
+    type Err = IconError;
+
+    /// Attempts to convert an identifier (e.g. "0") or a case insensitive
+    /// name (e.g. "Key") to an icon.
+    fn from_str(s: &str) -> Result<Icon, IconError> {
+        if let Ok(id) = s.parse::<i32>() {
+            return Icon::from_i32(id);
+        }
+        (0..ICON_COUNT)
+            .filter_map(|id| Icon::from_i32(id).ok())
+            .find(|icon| icon.name().eq_ignore_ascii_case(s))
+            .ok_or(IconError::InvalidIconName)
+    }
+}
+
 /// Error type for icon conversion errors.
 #[derive(Debug, PartialEq)]
 pub enum IconError {
     /// Invalid icon identifier.
     InvalidIconId,
+
+    /// Invalid icon name.
+    InvalidIconName,
 }
 
 impl IconError {
     fn msg(&self) -> &str {
         match *self {
             IconError::InvalidIconId => "invalid icon identifier",
+            IconError::InvalidIconName => "invalid icon name",
         }
     }
 }
@@ -344,6 +540,7 @@ impl fmt::Display for IconError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             IconError::InvalidIconId => write!(f, "Icon error: {}", self.msg()),
+            IconError::InvalidIconName => write!(f, "Icon error: {}", self.msg()),
         }
     }
 }
@@ -389,4 +586,47 @@ mod tests {
             assert_eq!(icon_id, i);
         }
     }
+
+    #[test]
+    fn test_category_returns_correct_category() {
+        assert_eq!(Icon::Smb.category(), IconCategory::Communication);
+        assert_eq!(Icon::Tux.category(), IconCategory::System);
+        assert_eq!(Icon::Digicam.category(), IconCategory::Media);
+        assert_eq!(Icon::Certificate.category(), IconCategory::Security);
+        assert_eq!(Icon::RecycleBin.category(), IconCategory::Misc);
+    }
+
+    #[test]
+    fn test_name_returns_correct_name() {
+        assert_eq!(Icon::Key.name(), "Key");
+        assert_eq!(Icon::IRCommunication.name(), "IRCommunication");
+        assert_eq!(Icon::Phone.name(), "Phone");
+    }
+
+    #[test]
+    fn test_fmt_returns_name() {
+        assert_eq!(format!("{}", Icon::WorldSocket), "WorldSocket");
+    }
+
+    #[test]
+    fn test_from_str_with_id_returns_icon() {
+        assert_eq!("0".parse::<Icon>(), Ok(Icon::Key));
+        assert_eq!("68".parse::<Icon>(), Ok(Icon::Phone));
+        assert_eq!("69".parse::<Icon>(), Err(IconError::InvalidIconId));
+    }
+
+    #[test]
+    fn test_from_str_with_name_returns_icon() {
+        assert_eq!("Key".parse::<Icon>(), Ok(Icon::Key));
+        assert_eq!("recyclebin".parse::<Icon>(), Ok(Icon::RecycleBin));
+        assert_eq!("Unknown".parse::<Icon>(), Err(IconError::InvalidIconName));
+    }
+
+    #[test]
+    fn test_from_str_inverses_fmt() {
+        for i in 0..69 {
+            let icon = Icon::from_i32(i).unwrap();
+            assert_eq!(format!("{}", icon).parse::<Icon>(), Ok(icon));
+        }
+    }
 }
@@ -0,0 +1,67 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+/// The category of a standard icon.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum IconCategory {
+    /// Icons related to communication and networking.
+    Communication,
+
+    /// Icons related to computers, hardware and software.
+    System,
+
+    /// Icons related to media such as images, video and books.
+    Media,
+
+    /// Icons related to keys, locks and identities.
+    Security,
+
+    /// All other icons.
+    Misc,
+}
+
+impl IconCategory {
+    /// Gets the category's name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::IconCategory;
+    ///
+    /// assert_eq!(IconCategory::Security.name(), "Security");
+    /// ```
+    pub fn name(self) -> &'static str {
+        match self {
+            IconCategory::Communication => "Communication",
+            IconCategory::System => "System",
+            IconCategory::Media => "Media",
+            IconCategory::Security => "Security",
+            IconCategory::Misc => "Misc",
+        }
+    }
+}
+
+impl fmt::Display for IconCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_fmt_returns_name() {
+        assert_eq!(format!("{}", IconCategory::Communication), "Communication");
+        assert_eq!(format!("{}", IconCategory::Misc), "Misc");
+    }
+}
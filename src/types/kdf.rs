@@ -0,0 +1,52 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+/// The key derivation function without its parameters.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Kdf {
+    /// The composite key is encrypted with AES-256 a number of rounds.
+    Aes,
+}
+
+impl Kdf {
+    /// Returns all supported key derivation functions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Kdf;
+    ///
+    /// for kdf in Kdf::all() {
+    ///     println!("{}", kdf);
+    /// }
+    /// ```
+    pub fn all() -> &'static [Kdf] {
+        &[Kdf::Aes]
+    }
+}
+
+impl fmt::Display for Kdf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Kdf::Aes => write!(f, "AES-KDF"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_fmt_returns_human_name() {
+        assert_eq!(format!("{}", Kdf::Aes), "AES-KDF");
+    }
+}
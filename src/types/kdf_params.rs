@@ -0,0 +1,53 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::kdf::Kdf;
+use super::transform_rounds::TransformRounds;
+use std::fmt;
+
+/// The key derivation function and its parameters.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum KdfParams {
+    /// The composite key is encrypted with AES-256 the specified number of
+    /// rounds.
+    Aes {
+        /// Number of times the composite key is transformed.
+        rounds: TransformRounds,
+    },
+}
+
+impl KdfParams {
+    /// Gets the key derivation function of these parameters.
+    pub fn kdf(&self) -> Kdf {
+        match *self {
+            KdfParams::Aes { .. } => Kdf::Aes,
+        }
+    }
+}
+
+impl fmt::Display for KdfParams {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            KdfParams::Aes { ref rounds } => write!(f, "AES-KDF ({} rounds)", rounds.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_fmt_returns_human_name() {
+        let params = KdfParams::Aes {
+            rounds: TransformRounds(6000),
+        };
+        assert_eq!(format!("{}", params), "AES-KDF (6000 rounds)");
+    }
+}
@@ -6,15 +6,17 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use super::error::Error;
 use super::key_file_type::KeyFileType;
 use super::result::Result;
 use crate::crypto::random_gen::RandomGen;
 use crate::format::{kf_reader, kf_writer};
 use secstr::SecStr;
+use std::fmt;
 use std::io::{Read, Write};
 
 /// A key file used for encrypting and decrypting the database.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct KeyFile {
     /// The key data.
     pub key: SecStr,
@@ -29,6 +31,37 @@ impl KeyFile {
         KeyFile::new_xml()
     }
 
+    /// Attempts to create a copy of the key file with the same key and the
+    /// supplied type, so that `save` writes the key in that format.
+    ///
+    /// Binary and hexadecimal key files hold exactly 32 bytes, converting a
+    /// key of any other length to those types returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use kpdb::Result;
+    /// use kpdb::{KeyFile, KeyFileType};
+    ///
+    /// # fn convert_example() -> Result<()> {
+    /// let binary = KeyFile::new_binary()?;
+    /// let xml = binary.convert(KeyFileType::Xml)?;
+    /// assert_eq!(xml.key, binary.key);
+    /// assert_eq!(xml.file_type, KeyFileType::Xml);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn convert(&self, to: KeyFileType) -> Result<KeyFile> {
+        let len = self.key.unsecure().len();
+        if to != KeyFileType::Xml && len != 32 {
+            return Err(Error::InvalidKeyLength(len));
+        }
+        Ok(KeyFile {
+            key: self.key.clone(),
+            file_type: to,
+        })
+    }
+
     /// Attempts to create a new binary key file.
     ///
     /// # Examples
@@ -139,6 +172,15 @@ impl KeyFile {
     }
 }
 
+impl fmt::Debug for KeyFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KeyFile")
+            .field("key", &format_args!("<{} bytes>", self.key.unsecure().len()))
+            .field("file_type", &self.file_type)
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -177,4 +219,43 @@ mod tests {
         assert_eq!(a.file_type, KeyFileType::Xml);
         assert_eq!(b.file_type, KeyFileType::Xml);
     }
+
+    #[test]
+    fn test_convert_and_save_preserves_key() {
+        let types = [KeyFileType::Binary, KeyFileType::Hex, KeyFileType::Xml];
+        let original = KeyFile::new_binary().unwrap();
+        for file_type in types.iter() {
+            let converted = original.convert(file_type.clone()).unwrap();
+            let mut writer = Vec::new();
+            converted.save(&mut writer).unwrap();
+            let actual = KeyFile::open(&mut &writer[..]).unwrap();
+            assert_eq!(actual.key, original.key);
+            assert_eq!(actual.file_type, *file_type);
+        }
+    }
+
+    #[test]
+    fn test_convert_with_invalid_key_length_returns_error() {
+        let key_file = KeyFile {
+            key: SecStr::new(vec![1; 16]),
+            file_type: KeyFileType::Xml,
+        };
+        for file_type in [KeyFileType::Binary, KeyFileType::Hex].iter() {
+            match key_file.convert(file_type.clone()) {
+                Err(Error::InvalidKeyLength(16)) => (),
+                _ => panic!("expected InvalidKeyLength error"),
+            }
+        }
+        assert!(key_file.convert(KeyFileType::Xml).is_ok());
+    }
+
+    #[test]
+    fn test_fmt_debug_does_not_contain_key_bytes() {
+        let key_file = KeyFile {
+            key: SecStr::new(vec![0x70, 0x61, 0x73, 0x73]),
+            file_type: KeyFileType::Binary,
+        };
+        let debug = format!("{:?}", key_file);
+        assert_eq!(debug, "KeyFile { key: <4 bytes>, file_type: Binary }");
+    }
 }
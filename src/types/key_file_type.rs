@@ -6,6 +6,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use crate::common;
+
 /// The type of the key file.
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub enum KeyFileType {
@@ -18,3 +20,72 @@ pub enum KeyFileType {
     /// XML key file.
     Xml,
 }
+
+impl KeyFileType {
+    /// Detects the type of the key file from its contents.
+    ///
+    /// Data starting with an XML declaration or a KeyFile tag is XML, 32
+    /// bytes of other data is binary and 64 bytes is hexadecimal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::KeyFileType;
+    ///
+    /// assert_eq!(KeyFileType::detect(&[0u8; 32]), KeyFileType::Binary);
+    /// assert_eq!(KeyFileType::detect(&[b'a'; 64]), KeyFileType::Hex);
+    /// assert_eq!(KeyFileType::detect(b"<?xml version=\"1.0\"?>"), KeyFileType::Xml);
+    /// ```
+    pub fn detect(data: &[u8]) -> KeyFileType {
+        if is_xml(data) {
+            return KeyFileType::Xml;
+        }
+        match data.len() {
+            common::BINARY_KEY_FILE_LEN => KeyFileType::Binary,
+            common::HEX_KEY_FILE_LEN => KeyFileType::Hex,
+            _ => KeyFileType::Xml,
+        }
+    }
+}
+
+fn is_xml(data: &[u8]) -> bool {
+    let data = data.strip_prefix(common::UTF8_BOM).unwrap_or(data);
+    let start = data
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(data.len());
+    let data = &data[start..];
+    data.starts_with(common::XML_DECLARATION_START) || data.starts_with(common::KEY_FILE_TAG_START)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_detect_with_32_bytes_returns_binary() {
+        assert_eq!(KeyFileType::detect(&[0x70; 32]), KeyFileType::Binary);
+    }
+
+    #[test]
+    fn test_detect_with_64_bytes_returns_hex() {
+        assert_eq!(KeyFileType::detect(&[0x61; 64]), KeyFileType::Hex);
+    }
+
+    #[test]
+    fn test_detect_with_other_length_returns_xml() {
+        assert_eq!(KeyFileType::detect(&[0x61; 100]), KeyFileType::Xml);
+    }
+
+    #[test]
+    fn test_detect_with_short_xml_returns_xml() {
+        let mut data = b"<KeyFile><Key><Data>YWJj</Data></Key>".to_vec();
+        data.resize(32, b' ');
+        assert_eq!(KeyFileType::detect(&data), KeyFileType::Xml);
+
+        let mut data = b"\xef\xbb\xbf  <?xml version=\"1.0\"?><KeyFile>".to_vec();
+        data.resize(64, b' ');
+        assert_eq!(KeyFileType::detect(&data), KeyFileType::Xml);
+    }
+}
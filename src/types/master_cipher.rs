@@ -6,9 +6,52 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::fmt;
+
 /// The encryption algorithm for the master data.
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub enum MasterCipher {
     /// Advanced Encryption Standard (Rijndael) with 256 bit key.
     Aes256,
 }
+
+impl MasterCipher {
+    /// Returns all supported master ciphers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::MasterCipher;
+    ///
+    /// for cipher in MasterCipher::all() {
+    ///     println!("{}", cipher);
+    /// }
+    /// ```
+    pub fn all() -> &'static [MasterCipher] {
+        &[MasterCipher::Aes256]
+    }
+}
+
+impl fmt::Display for MasterCipher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MasterCipher::Aes256 => write!(f, "AES-256"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_all_returns_supported_ciphers() {
+        assert_eq!(MasterCipher::all(), &[MasterCipher::Aes256]);
+    }
+
+    #[test]
+    fn test_fmt_returns_human_name() {
+        assert_eq!(format!("{}", MasterCipher::Aes256), "AES-256");
+    }
+}
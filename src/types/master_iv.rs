@@ -6,6 +6,30 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use super::result::Result;
+use crate::crypto::random_gen::RandomGen;
+
 /// Initialization vector for the master encryption algorithm.
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct MasterIV(pub [u8; 16]);
+
+impl MasterIV {
+    /// Attempts to create a new random master IV.
+    pub fn new_random() -> Result<MasterIV> {
+        let mut random = RandomGen::new()?;
+        Ok(MasterIV(random.next_16_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_new_random_returns_random_value() {
+        let a = MasterIV::new_random().unwrap();
+        let b = MasterIV::new_random().unwrap();
+        assert!(a != b);
+    }
+}
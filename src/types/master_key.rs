@@ -6,16 +6,19 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use super::error::Error;
 use super::master_seed::MasterSeed;
+use super::result::Result;
 use super::transformed_key::TransformedKey;
 use crate::crypto::sha256;
 use secstr::SecStr;
+use std::fmt;
 
 /// Key used for encrypting and decrypting the master data.
 ///
 /// This data type uses secstr's `SecStr` to protect the key data. To
-/// retrieve the protected data use the `unsecure` method.
-#[derive(Clone, Debug, PartialEq)]
+/// retrieve the protected data use the `try_unsecure` method.
+#[derive(Clone, PartialEq)]
 pub struct MasterKey(SecStr);
 
 impl MasterKey {
@@ -24,14 +27,12 @@ impl MasterKey {
         MasterKey::secure(sha256::hash(&[&seed.0, &key.unsecure()]))
     }
 
-    /// Gets the protected data from this master key.
-    pub fn unsecure(&self) -> [u8; 32] {
+    /// Attempts to get the protected data from this master key.
+    ///
+    /// Returns an error when the protected data isn't exactly 32 bytes.
+    pub fn try_unsecure(&self) -> Result<[u8; 32]> {
         let unsecure = self.0.unsecure();
-        let mut array = [0u8; 32];
-        for (u, a) in unsecure.iter().zip(array.iter_mut()) {
-            *a = *u;
-        }
-        array
+        <[u8; 32]>::try_from(unsecure).map_err(|_| Error::InvalidKeyLength(unsecure.len()))
     }
 
     fn secure(key: [u8; 32]) -> MasterKey {
@@ -39,6 +40,12 @@ impl MasterKey {
     }
 }
 
+impl fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MasterKey(<{} bytes>)", self.0.unsecure().len())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -62,13 +69,34 @@ mod tests {
     }
 
     #[test]
-    fn test_unsecure_inverses_secure() {
+    fn test_try_unsecure_inverses_secure() {
         let array = [
             1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
             25, 26, 27, 28, 29, 30, 31, 32,
         ];
         let expected = array.clone();
-        let actual = MasterKey::unsecure(&MasterKey::secure(array));
+        let actual = MasterKey::try_unsecure(&MasterKey::secure(array)).unwrap();
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_fmt_debug_does_not_contain_key_bytes() {
+        let key = MasterKey::secure([1u8; 32]);
+        assert_eq!(format!("{:?}", key), "MasterKey(<32 bytes>)");
+    }
+
+    #[test]
+    fn test_try_unsecure_returns_correct_array() {
+        let target = MasterKey::secure([7u8; 32]);
+        assert_eq!(target.try_unsecure().unwrap(), [7u8; 32]);
+    }
+
+    #[test]
+    fn test_try_unsecure_with_wrong_length_returns_error() {
+        let target = MasterKey(SecStr::new(vec![7u8; 16]));
+        match target.try_unsecure() {
+            Err(Error::InvalidKeyLength(16)) => (),
+            _ => panic!("expected InvalidKeyLength error"),
+        }
+    }
 }
@@ -6,6 +6,30 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use super::result::Result;
+use crate::crypto::random_gen::RandomGen;
+
 /// Seed for creating the master key.
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct MasterSeed(pub [u8; 32]);
+
+impl MasterSeed {
+    /// Attempts to create a new random master seed.
+    pub fn new_random() -> Result<MasterSeed> {
+        let mut random = RandomGen::new()?;
+        Ok(MasterSeed(random.next_32_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_new_random_returns_random_value() {
+        let a = MasterSeed::new_random().unwrap();
+        let b = MasterSeed::new_random().unwrap();
+        assert!(a != b);
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::times::Times;
+
+/// The policy used to resolve conflicts while merging.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MergePolicy {
+    /// Keep the version that was modified last (local wins on a tie).
+    KeepNewer,
+
+    /// Always keep the local version.
+    KeepLocal,
+
+    /// Always keep the remote version.
+    KeepRemote,
+}
+
+impl MergePolicy {
+    /// Returns whether the remote version should replace the local version.
+    pub(crate) fn prefers_remote<T: Times>(self, local: &T, remote: &T) -> bool {
+        match self {
+            MergePolicy::KeepNewer => remote.last_modified() > local.last_modified(),
+            MergePolicy::KeepLocal => false,
+            MergePolicy::KeepRemote => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::types::Entry;
+    use chrono::Duration;
+
+    #[test]
+    fn test_prefers_remote_returns_correct_value() {
+        let local = Entry::new();
+        let mut remote = local.clone();
+        remote.last_modified = local.last_modified + Duration::seconds(1);
+        assert!(MergePolicy::KeepNewer.prefers_remote(&local, &remote));
+        assert!(!MergePolicy::KeepNewer.prefers_remote(&remote, &local));
+        assert!(!MergePolicy::KeepNewer.prefers_remote(&local, &local));
+        assert!(!MergePolicy::KeepLocal.prefers_remote(&local, &remote));
+        assert!(MergePolicy::KeepRemote.prefers_remote(&remote, &local));
+    }
+}
@@ -7,6 +7,10 @@
 // except according to those terms.
 
 pub use self::association::Association;
+#[cfg(feature = "database")]
+pub use self::auto_type::AutoType;
+#[cfg(feature = "database")]
+pub use self::auto_type::AutoTypeMut;
 pub use self::binaries_map::BinariesMap;
 pub use self::binary_id::BinaryId;
 pub use self::binary_key::BinaryKey;
@@ -18,40 +22,89 @@ pub use self::compression::Compression;
 pub use self::custom_data_map::CustomDataMap;
 pub use self::custom_icon_uuid::CustomIconUuid;
 pub use self::custom_icons_map::CustomIconsMap;
+#[cfg(feature = "database")]
 pub use self::database::Database;
+#[cfg(feature = "database")]
+pub use self::database_builder::DatabaseBuilder;
+#[cfg(feature = "database")]
+pub use self::database_stats::DatabaseStats;
 pub use self::db_type::DbType;
+#[cfg(feature = "database")]
 pub use self::entry::Entry;
+pub use self::entry_field::EntryField;
+#[cfg(feature = "database")]
 pub use self::entry_state::EntryState;
 pub use self::entry_uuid::EntryUuid;
 pub use self::error::Error;
+#[cfg(feature = "database")]
+pub use self::field_diff::FieldDiff;
+pub use self::field_encoding::FieldEncoding;
+#[cfg(feature = "database")]
 pub use self::group::Group;
 pub use self::group_uuid::GroupUuid;
+#[cfg(feature = "database")]
 pub use self::header_hash::HeaderHash;
 pub use self::icon::{Icon, IconError};
+pub use self::icon_category::IconCategory;
+pub use self::kdf::Kdf;
+pub use self::kdf_params::KdfParams;
+#[cfg(any(feature = "database", feature = "keyfile"))]
 pub use self::key_file::KeyFile;
+#[cfg(any(feature = "database", feature = "keyfile"))]
 pub use self::key_file_type::KeyFileType;
 pub use self::master_cipher::MasterCipher;
+#[cfg(feature = "database")]
 pub use self::master_iv::MasterIV;
+#[cfg(feature = "database")]
 pub use self::master_key::MasterKey;
+#[cfg(feature = "database")]
 pub use self::master_seed::MasterSeed;
+#[cfg(feature = "database")]
+pub use self::merge_policy::MergePolicy;
+#[cfg(feature = "database")]
 pub use self::meta_data::MetaData;
 pub use self::obfuscation::{Obfuscation, ObfuscationError};
+#[cfg(feature = "database")]
+pub use self::open_options::OpenOptions;
+#[cfg(feature = "database")]
+pub use self::password_issue::PasswordIssue;
 pub use self::protected_stream_key::ProtectedStreamKey;
+#[cfg(feature = "database")]
+pub use self::read_limits::ReadLimits;
+#[cfg(feature = "database")]
+pub use self::recovered_error::RecoveredError;
 pub use self::result::Result;
+#[cfg(feature = "database")]
+pub use self::save_options::SaveOptions;
+#[cfg(feature = "database")]
+pub use self::save_report::SaveReport;
+#[cfg(feature = "database")]
+pub use self::sort_field::SortField;
 pub use self::stream_cipher::StreamCipher;
 pub use self::stream_key::StreamKey;
+#[cfg(feature = "database")]
 pub use self::stream_start_bytes::StreamStartBytes;
 pub use self::string_key::StringKey;
 pub use self::string_value::StringValue;
 pub use self::strings_map::StringsMap;
+#[cfg(feature = "database")]
 pub use self::times::Times;
 pub use self::transform_rounds::TransformRounds;
 pub use self::transform_seed::TransformSeed;
 pub use self::transformed_key::TransformedKey;
+#[cfg(feature = "database")]
+pub use self::tree_visitor::TreeVisitor;
+#[cfg(feature = "database")]
+pub use self::validation_issue::ValidationIssue;
 pub use self::version::Version;
+#[cfg(feature = "database")]
+pub use self::walk_control::WalkControl;
+#[cfg(feature = "database")]
 pub use self::xml_data::XmlData;
 
 mod association;
+#[cfg(feature = "database")]
+mod auto_type;
 mod binaries_map;
 mod binary_id;
 mod binary_key;
@@ -63,35 +116,82 @@ mod compression;
 mod custom_data_map;
 mod custom_icon_uuid;
 mod custom_icons_map;
+#[cfg(feature = "database")]
 mod database;
+#[cfg(feature = "database")]
+mod database_builder;
+#[cfg(feature = "database")]
+mod database_stats;
 mod db_type;
+#[cfg(feature = "database")]
 mod entry;
+mod entry_field;
+#[cfg(feature = "database")]
 mod entry_state;
 mod entry_uuid;
 mod error;
+#[cfg(feature = "database")]
+mod field_diff;
+mod field_encoding;
+#[cfg(feature = "database")]
 mod group;
 mod group_uuid;
+#[cfg(feature = "database")]
 mod header_hash;
 mod icon;
+mod icon_category;
+mod kdf;
+mod kdf_params;
+#[cfg(any(feature = "database", feature = "keyfile"))]
 mod key_file;
+#[cfg(any(feature = "database", feature = "keyfile"))]
 mod key_file_type;
 mod master_cipher;
+#[cfg(feature = "database")]
 mod master_iv;
+#[cfg(feature = "database")]
 mod master_key;
+#[cfg(feature = "database")]
 mod master_seed;
+#[cfg(feature = "database")]
+mod merge_policy;
+#[cfg(feature = "database")]
 mod meta_data;
 mod obfuscation;
+#[cfg(feature = "database")]
+mod open_options;
+#[cfg(feature = "database")]
+mod password_issue;
 mod protected_stream_key;
+#[cfg(feature = "database")]
+mod read_limits;
+#[cfg(feature = "database")]
+mod recovered_error;
 mod result;
+#[cfg(feature = "database")]
+mod save_options;
+#[cfg(feature = "database")]
+mod save_report;
+#[cfg(feature = "database")]
+mod sort_field;
 mod stream_cipher;
 mod stream_key;
+#[cfg(feature = "database")]
 mod stream_start_bytes;
 mod string_key;
 mod string_value;
 mod strings_map;
+#[cfg(feature = "database")]
 mod times;
 mod transform_rounds;
 mod transform_seed;
 mod transformed_key;
+#[cfg(feature = "database")]
+mod tree_visitor;
+#[cfg(feature = "database")]
+mod validation_issue;
 mod version;
+#[cfg(feature = "database")]
+mod walk_control;
+#[cfg(feature = "database")]
 mod xml_data;
@@ -0,0 +1,132 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::read_limits::ReadLimits;
+
+/// Options for opening a database, see `Database::open_with_options`.
+///
+/// The defaults are suitable for databases from trusted sources:
+///
+/// * at most 1048576 data blocks
+/// * at most 256 header fields
+/// * at most 1000000000 transform rounds
+/// * at most 1 GiB of decompressed XML data
+///
+/// Lower the limits when opening databases from untrusted sources (e.g. on
+/// a server or sync backend), since a crafted file can otherwise make the
+/// open take a long time or use a lot of memory.
+///
+/// By default any unreadable value in the XML data and an unsupported
+/// database version are errors. In lenient mode such values are replaced by
+/// their defaults, an unknown minor version of a supported major version is
+/// accepted and both are reported instead, see
+/// `Database::open_with_recovery`. An unsupported major version is always an
+/// error.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct OpenOptions {
+    pub(crate) lenient: bool,
+    pub(crate) limits: ReadLimits,
+}
+
+impl OpenOptions {
+    /// Create new open options with the default settings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::OpenOptions;
+    ///
+    /// let options = OpenOptions::new()
+    ///     .max_transform_rounds(10000000)
+    ///     .max_xml_size(16 * 1024 * 1024);
+    /// assert_eq!(options.read_limits().max_transform_rounds, 10000000);
+    /// ```
+    pub fn new() -> OpenOptions {
+        OpenOptions::default()
+    }
+
+    /// Gets whether unreadable XML values are replaced by defaults.
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Sets whether unreadable XML values are replaced by defaults.
+    pub fn lenient(mut self, lenient: bool) -> OpenOptions {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Sets the maximum number of data blocks.
+    pub fn max_blocks(mut self, max: u32) -> OpenOptions {
+        self.limits.max_blocks = max;
+        self
+    }
+
+    /// Sets the maximum number of header fields.
+    pub fn max_headers(mut self, max: usize) -> OpenOptions {
+        self.limits.max_headers = max;
+        self
+    }
+
+    /// Sets the maximum number of transform rounds.
+    pub fn max_transform_rounds(mut self, max: u64) -> OpenOptions {
+        self.limits.max_transform_rounds = max;
+        self
+    }
+
+    /// Sets the maximum size in bytes of the decompressed XML data.
+    pub fn max_xml_size(mut self, max: u64) -> OpenOptions {
+        self.limits.max_xml_size = max;
+        self
+    }
+
+    /// Gets the limits that are used while reading.
+    pub fn read_limits(&self) -> &ReadLimits {
+        &self.limits
+    }
+
+    /// Sets all limits that are used while reading.
+    pub fn with_read_limits(mut self, limits: ReadLimits) -> OpenOptions {
+        self.limits = limits;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_new_returns_default_limits() {
+        assert_eq!(*OpenOptions::new().read_limits(), ReadLimits::default());
+    }
+
+    #[test]
+    fn test_lenient_changes_is_lenient() {
+        assert!(!OpenOptions::new().is_lenient());
+        assert!(OpenOptions::new().lenient(true).is_lenient());
+    }
+
+    #[test]
+    fn test_setters_change_limits() {
+        let options = OpenOptions::new()
+            .max_blocks(1)
+            .max_headers(2)
+            .max_transform_rounds(3)
+            .max_xml_size(4);
+        let expected = ReadLimits {
+            max_blocks: 1,
+            max_headers: 2,
+            max_transform_rounds: 3,
+            max_xml_size: 4,
+        };
+        assert_eq!(*options.read_limits(), expected);
+        assert_eq!(*OpenOptions::new().with_read_limits(expected).read_limits(), expected);
+    }
+}
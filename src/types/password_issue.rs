@@ -0,0 +1,66 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::entry_uuid::EntryUuid;
+use std::fmt;
+
+/// A problem with the password of one or more entries, see
+/// `Database::password_audit`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PasswordIssue {
+    /// The password hasn't been changed for the specified number of days.
+    OldPassword {
+        /// Entry identifier.
+        entry: EntryUuid,
+
+        /// Number of days since the entry was last modified.
+        days: i64,
+    },
+
+    /// The entries with the specified identifiers share the same password.
+    ReusedPassword(Vec<EntryUuid>),
+
+    /// The estimated entropy of the password is below the minimum.
+    WeakPassword {
+        /// Entry identifier.
+        entry: EntryUuid,
+
+        /// Estimated entropy in bits.
+        entropy: u32,
+    },
+}
+
+impl fmt::Display for PasswordIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PasswordIssue::OldPassword { entry, days } => {
+                write!(f, "Old password: entry: {}, days: {}", entry.0, days)
+            }
+            PasswordIssue::ReusedPassword(ref entries) => {
+                let uuids: Vec<String> = entries.iter().map(|e| e.0.to_string()).collect();
+                write!(f, "Reused password: entries: {}", uuids.join(", "))
+            }
+            PasswordIssue::WeakPassword { entry, entropy } => {
+                write!(f, "Weak password: entry: {}, entropy: {}", entry.0, entropy)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_fmt_returns_description() {
+        let entry = EntryUuid::nil();
+        let issue = PasswordIssue::WeakPassword { entry, entropy: 13 };
+        assert_eq!(format!("{}", issue), format!("Weak password: entry: {}, entropy: 13", entry.0));
+    }
+}
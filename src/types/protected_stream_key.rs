@@ -6,6 +6,30 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use super::result::Result;
+use crate::crypto::random_gen::RandomGen;
+
 /// Key used for creating the stream key.
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct ProtectedStreamKey(pub [u8; 32]);
+
+impl ProtectedStreamKey {
+    /// Attempts to create a new random protected stream key.
+    pub fn new_random() -> Result<ProtectedStreamKey> {
+        let mut random = RandomGen::new()?;
+        Ok(ProtectedStreamKey(random.next_32_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_new_random_returns_random_value() {
+        let a = ProtectedStreamKey::new_random().unwrap();
+        let b = ProtectedStreamKey::new_random().unwrap();
+        assert!(a != b);
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::common;
+
+/// Limits that protect against maliciously crafted databases while reading.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ReadLimits {
+    /// The maximum number of data blocks.
+    pub max_blocks: u32,
+
+    /// The maximum number of header fields.
+    pub max_headers: usize,
+
+    /// The maximum number of transform rounds, this bounds the time spent
+    /// on transforming the key before anything can be verified.
+    pub max_transform_rounds: u64,
+
+    /// The maximum size in bytes of the decompressed XML data.
+    pub max_xml_size: u64,
+}
+
+impl ReadLimits {
+    /// Returns the maximum size in bytes of the signatures, version and
+    /// header fields.
+    #[cfg(feature = "database")]
+    pub(crate) fn max_header_size(&self) -> usize {
+        let field_size = 1 + 2 + u16::MAX as usize;
+        12usize.saturating_add(self.max_headers.saturating_mul(field_size))
+    }
+}
+
+impl Default for ReadLimits {
+    fn default() -> ReadLimits {
+        ReadLimits {
+            max_blocks: common::MAX_BLOCKS_DEFAULT,
+            max_headers: common::MAX_HEADERS_DEFAULT,
+            max_transform_rounds: common::MAX_TRANSFORM_ROUNDS_DEFAULT,
+            max_xml_size: common::MAX_XML_SIZE_DEFAULT,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_default_returns_correct_instance() {
+        let limits = ReadLimits::default();
+        assert_eq!(limits.max_blocks, 1048576);
+        assert_eq!(limits.max_headers, 256);
+        assert_eq!(limits.max_transform_rounds, 1000000000);
+        assert_eq!(limits.max_xml_size, 1073741824);
+    }
+
+    #[cfg(feature = "database")]
+    #[test]
+    fn test_max_header_size_returns_correct_value() {
+        let limits = ReadLimits {
+            max_headers: 2,
+            ..ReadLimits::default()
+        };
+        assert_eq!(limits.max_header_size(), 12 + 2 * 65538);
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+/// An unreadable part of the XML data that was skipped, or an unsupported
+/// header that was accepted, while opening a database in lenient mode, see
+/// `Database::open_with_recovery`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveredError {
+    /// The name of the XML element or header that couldn't be read.
+    pub element: String,
+
+    /// Description of the error, including the position in the XML data.
+    pub message: String,
+}
+
+impl fmt::Display for RecoveredError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.element, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_fmt_returns_element_and_message() {
+        let error = RecoveredError {
+            element: String::from("IconID"),
+            message: String::from("XML error: 3:4 Invalid icon ID"),
+        };
+        assert_eq!(format!("{}", error), "IconID: XML error: 3:4 Invalid icon ID");
+    }
+}
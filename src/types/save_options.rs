@@ -0,0 +1,103 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::common;
+
+/// Options for saving a database, see `Database::save_with_options`.
+///
+/// The defaults match the output of `save`:
+///
+/// * GZip compression level 6 (when the database uses GZip compression)
+/// * payload blocks of at most 1 MiB
+/// * XML indented with tabs
+/// * the generator of the database is kept
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SaveOptions {
+    pub(crate) block_size: usize,
+    pub(crate) compression_level: u32,
+    pub(crate) indent: bool,
+    pub(crate) regenerate_generator: bool,
+}
+
+impl SaveOptions {
+    /// Create new save options with the default settings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::SaveOptions;
+    ///
+    /// let options = SaveOptions::new()
+    ///     .compression_level(9)
+    ///     .indent(false)
+    ///     .regenerate_generator(true);
+    /// ```
+    pub fn new() -> SaveOptions {
+        SaveOptions::default()
+    }
+
+    /// Sets the maximum size in bytes of a payload block.
+    ///
+    /// Saving fails with an invalid block size error when the size is zero
+    /// or doesn't fit in the 32-bit block size field.
+    pub fn block_size(mut self, size: usize) -> SaveOptions {
+        self.block_size = size;
+        self
+    }
+
+    /// Sets the GZip compression level, from 0 (none) to 9 (best), higher
+    /// levels are treated as 9.
+    pub fn compression_level(mut self, level: u32) -> SaveOptions {
+        self.compression_level = level.min(9);
+        self
+    }
+
+    /// Sets whether the XML data is indented.
+    pub fn indent(mut self, indent: bool) -> SaveOptions {
+        self.indent = indent;
+        self
+    }
+
+    /// Sets whether the generator is replaced by the name of this library
+    /// instead of keeping the generator of the database.
+    pub fn regenerate_generator(mut self, regenerate: bool) -> SaveOptions {
+        self.regenerate_generator = regenerate;
+        self
+    }
+}
+
+impl Default for SaveOptions {
+    fn default() -> SaveOptions {
+        SaveOptions {
+            block_size: common::BLOCK_SIZE_DEFAULT,
+            compression_level: common::COMPRESSION_LEVEL_DEFAULT,
+            indent: true,
+            regenerate_generator: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_default_returns_correct_instance() {
+        let options = SaveOptions::default();
+        assert_eq!(options.block_size, 1048576);
+        assert_eq!(options.compression_level, 6);
+        assert!(options.indent);
+        assert!(!options.regenerate_generator);
+    }
+
+    #[test]
+    fn test_compression_level_limits_level_to_nine() {
+        assert_eq!(SaveOptions::new().compression_level(42).compression_level, 9);
+    }
+}
@@ -0,0 +1,17 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Integrity information about a saved database.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SaveReport {
+    /// The number of bytes written.
+    pub bytes_written: u64,
+
+    /// The SHA-256 of the written bytes.
+    pub sha256: [u8; 32],
+}
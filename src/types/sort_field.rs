@@ -0,0 +1,29 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// The field that entries are sorted by, see `Entry::cmp_by`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SortField {
+    /// Sort by the creation time.
+    CreationTime,
+
+    /// Sort by the modification time.
+    LastModified,
+
+    /// Sort by the title, ignoring case.
+    Title,
+
+    /// Sort by the URL, ignoring case.
+    Url,
+
+    /// Sort by the number of times the entry has been used.
+    UsageCount,
+
+    /// Sort by the username, ignoring case.
+    Username,
+}
@@ -6,9 +6,52 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::fmt;
+
 /// The encryption algorithm for the stream data (e.g. passwords).
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub enum StreamCipher {
     /// The Salsa20 stream cipher.
     Salsa20,
 }
+
+impl StreamCipher {
+    /// Returns all supported stream ciphers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::StreamCipher;
+    ///
+    /// for cipher in StreamCipher::all() {
+    ///     println!("{}", cipher);
+    /// }
+    /// ```
+    pub fn all() -> &'static [StreamCipher] {
+        &[StreamCipher::Salsa20]
+    }
+}
+
+impl fmt::Display for StreamCipher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StreamCipher::Salsa20 => write!(f, "Salsa20"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_all_returns_supported_ciphers() {
+        assert_eq!(StreamCipher::all(), &[StreamCipher::Salsa20]);
+    }
+
+    #[test]
+    fn test_fmt_returns_human_name() {
+        assert_eq!(format!("{}", StreamCipher::Salsa20), "Salsa20");
+    }
+}
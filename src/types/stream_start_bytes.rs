@@ -6,6 +6,30 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use super::result::Result;
+use crate::crypto::random_gen::RandomGen;
+
 /// Bytes to determine whether the master key is correct.
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct StreamStartBytes(pub [u8; 32]);
+
+impl StreamStartBytes {
+    /// Attempts to create new random stream start bytes.
+    pub fn new_random() -> Result<StreamStartBytes> {
+        let mut random = RandomGen::new()?;
+        Ok(StreamStartBytes(random.next_32_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_new_random_returns_random_value() {
+        let a = StreamStartBytes::new_random().unwrap();
+        let b = StreamStartBytes::new_random().unwrap();
+        assert!(a != b);
+    }
+}
@@ -6,6 +6,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::fmt;
+
 /// A key for the map with strings.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub enum StringKey {
@@ -53,29 +55,33 @@ impl StringKey {
             _ => StringKey::Other(String::from(string)),
         }
     }
+}
 
-    /// Gets the string representation of the string key.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use kpdb::StringKey;
-    ///
-    /// let notes_key = StringKey::Notes;
-    /// let string = notes_key.to_string();
-    /// ```
-    pub fn to_string(&self) -> String {
+impl fmt::Display for StringKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            StringKey::Notes => String::from("Notes"),
-            StringKey::Other(ref string) => string.clone(),
-            StringKey::Password => String::from("Password"),
-            StringKey::Title => String::from("Title"),
-            StringKey::Url => String::from("URL"),
-            StringKey::Username => String::from("UserName"),
+            StringKey::Notes => f.write_str("Notes"),
+            StringKey::Other(ref string) => f.write_str(string),
+            StringKey::Password => f.write_str("Password"),
+            StringKey::Title => f.write_str("Title"),
+            StringKey::Url => f.write_str("URL"),
+            StringKey::Username => f.write_str("UserName"),
         }
     }
 }
 
+impl<'a> From<&'a str> for StringKey {
+    fn from(string: &'a str) -> StringKey {
+        StringKey::from_string(string)
+    }
+}
+
+impl From<String> for StringKey {
+    fn from(string: String) -> StringKey {
+        StringKey::from_string(&string)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -99,6 +105,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_str_and_string_return_correct_string_key() {
+        for tuple in get_test_tuples() {
+            assert_eq!(StringKey::from(tuple.0), tuple.1);
+            assert_eq!(StringKey::from(String::from(tuple.0)), tuple.1);
+        }
+    }
+
+    #[test]
+    fn test_fmt_returns_string_representation() {
+        for tuple in get_test_tuples() {
+            assert_eq!(format!("{}", tuple.1), tuple.0);
+        }
+    }
+
     fn get_test_tuples() -> Vec<(&'static str, StringKey)> {
         vec![
             ("Notes", StringKey::Notes),
@@ -7,9 +7,12 @@
 // except according to those terms.
 
 use secstr::SecStr;
+use std::borrow::Cow;
+use std::fmt;
+use std::str;
 
 /// A value for the map with strings.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub enum StringValue {
     /// Plain string value.
     Plain(String),
@@ -36,6 +39,90 @@ impl StringValue {
             StringValue::Plain(value.into())
         }
     }
+
+    /// Gets the string slice of a plain or protected string value.
+    ///
+    /// Returns None when the protected bytes are not valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kpdb::StringValue;
+    ///
+    /// let value = StringValue::new("secret", true);
+    /// assert_eq!(value.as_str(), Some("secret"));
+    /// ```
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            StringValue::Plain(ref string) => Some(string),
+            StringValue::Protected(ref secstr) => str::from_utf8(secstr.unsecure()).ok(),
+        }
+    }
+
+    /// Returns true when the plain or protected string value is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kpdb::StringValue;
+    ///
+    /// assert!(StringValue::new("", true).is_empty());
+    /// assert!(!StringValue::new("secret", true).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the length in bytes of a plain or protected string value.
+    ///
+    /// The protected bytes are not copied or decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kpdb::StringValue;
+    ///
+    /// let value = StringValue::new("secret", true);
+    /// assert_eq!(value.len(), 6);
+    /// ```
+    pub fn len(&self) -> usize {
+        match *self {
+            StringValue::Plain(ref string) => string.len(),
+            StringValue::Protected(ref secstr) => secstr.unsecure().len(),
+        }
+    }
+
+    /// Gets the contents of a plain or protected string value.
+    ///
+    /// Invalid UTF-8 sequences in protected bytes are replaced with the
+    /// replacement character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kpdb::StringValue;
+    ///
+    /// let value = StringValue::new("secret", true);
+    /// assert_eq!(value.reveal(), "secret");
+    /// ```
+    pub fn reveal(&self) -> Cow<'_, str> {
+        match *self {
+            StringValue::Plain(ref string) => Cow::Borrowed(string),
+            StringValue::Protected(ref secstr) => String::from_utf8_lossy(secstr.unsecure()),
+        }
+    }
+}
+
+impl fmt::Debug for StringValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StringValue::Plain(ref string) => f.debug_tuple("Plain").field(string).finish(),
+            StringValue::Protected(ref secstr) => f
+                .debug_tuple("Protected")
+                .field(&format_args!("<{} bytes>", secstr.unsecure().len()))
+                .finish(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -59,4 +146,49 @@ mod tests {
         let actual = StringValue::new(value, true);
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_as_str_returns_correct_str() {
+        assert_eq!(StringValue::new("FooBar", false).as_str(), Some("FooBar"));
+        assert_eq!(StringValue::new("FooBar", true).as_str(), Some("FooBar"));
+    }
+
+    #[test]
+    fn test_as_str_with_invalid_utf8_returns_none() {
+        let value = StringValue::Protected(SecStr::new(vec![0xff, 0xfe]));
+        assert_eq!(value.as_str(), None);
+    }
+
+    #[test]
+    fn test_is_empty_returns_true_for_empty_values_only() {
+        assert!(StringValue::new("", false).is_empty());
+        assert!(StringValue::new("", true).is_empty());
+        assert!(!StringValue::new("FooBar", false).is_empty());
+        assert!(!StringValue::new("FooBar", true).is_empty());
+    }
+
+    #[test]
+    fn test_len_returns_byte_length() {
+        assert_eq!(StringValue::new("FooBär", false).len(), 7);
+        assert_eq!(StringValue::new("FooBär", true).len(), 7);
+        assert_eq!(StringValue::Protected(SecStr::new(vec![0xff, 0xfe])).len(), 2);
+    }
+
+    #[test]
+    fn test_reveal_returns_correct_string() {
+        assert_eq!(StringValue::new("FooBar", false).reveal(), "FooBar");
+        assert_eq!(StringValue::new("FooBar", true).reveal(), "FooBar");
+    }
+
+    #[test]
+    fn test_fmt_debug_with_plain_value_shows_value() {
+        let value = StringValue::new("plain", false);
+        assert_eq!(format!("{:?}", value), "Plain(\"plain\")");
+    }
+
+    #[test]
+    fn test_fmt_debug_with_protected_value_hides_value() {
+        let value = StringValue::new("secret", true);
+        assert_eq!(format!("{:?}", value), "Protected(<6 bytes>)");
+    }
 }
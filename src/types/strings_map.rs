@@ -8,7 +8,10 @@
 
 use super::string_key::StringKey;
 use super::string_value::StringValue;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// A type alias for the map with strings.
-pub type StringsMap = HashMap<StringKey, StringValue>;
+///
+/// The map iterates in the order of its keys, so saving the same database
+/// produces the same output.
+pub type StringsMap = BTreeMap<StringKey, StringValue>;
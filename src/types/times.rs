@@ -6,7 +6,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 
 /// Trait for getting and setting of time related data.
 pub trait Times {
@@ -51,4 +51,30 @@ pub trait Times {
 
     /// Sets the usage count for the implementor.
     fn set_usage_count(&mut self, _: i32);
+
+    /// Gets whether the implementor expires and is expired at the supplied
+    /// date and time.
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires() && self.expiry_time() <= now
+    }
+
+    /// Sets the expiry of the implementor.
+    ///
+    /// Some sets the expiry time and makes the implementor expire, None makes
+    /// the implementor not expire.
+    fn set_expiry(&mut self, when: Option<DateTime<Utc>>) {
+        match when {
+            Some(time) => {
+                self.set_expires(true);
+                self.set_expiry_time(time);
+            }
+            None => self.set_expires(false),
+        }
+    }
+
+    /// Gets the time elapsed between the creation of the implementor and the
+    /// supplied date and time.
+    fn age(&self, now: DateTime<Utc>) -> Duration {
+        now.signed_duration_since(self.creation_time())
+    }
 }
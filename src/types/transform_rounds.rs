@@ -6,6 +6,52 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::fmt;
+
 /// Number of times the composite key must be transformed.
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct TransformRounds(pub u64);
+
+impl TransformRounds {
+    /// Gets the number of transform rounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::TransformRounds;
+    ///
+    /// let rounds = TransformRounds::from(6000);
+    /// assert_eq!(rounds.get(), 6000);
+    /// ```
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for TransformRounds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u64> for TransformRounds {
+    fn from(rounds: u64) -> TransformRounds {
+        TransformRounds(rounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_from_u64_inverses_get() {
+        assert_eq!(TransformRounds::from(60000).get(), 60000);
+    }
+
+    #[test]
+    fn test_fmt_returns_number_of_rounds() {
+        assert_eq!(format!("{}", TransformRounds(6000)), "6000");
+    }
+}
@@ -6,6 +6,30 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use super::result::Result;
+use crate::crypto::random_gen::RandomGen;
+
 /// Seed used in transforming the composite key.
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct TransformSeed(pub [u8; 32]);
+
+impl TransformSeed {
+    /// Attempts to create a new random transform seed.
+    pub fn new_random() -> Result<TransformSeed> {
+        let mut random = RandomGen::new()?;
+        Ok(TransformSeed(random.next_32_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_new_random_returns_random_seed() {
+        let a = TransformSeed::new_random().unwrap();
+        let b = TransformSeed::new_random().unwrap();
+        assert!(a != b);
+    }
+}
@@ -9,6 +9,8 @@
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 use crate::rust_crypto::aesni;
 
+use super::error::Error;
+use super::result::Result;
 use crate::crypto::sha256;
 use crate::rust_crypto::aes;
 use crate::rust_crypto::aessafe;
@@ -18,12 +20,13 @@ use crate::types::composite_key::CompositeKey;
 use crate::types::transform_rounds::TransformRounds;
 use crate::types::transform_seed::TransformSeed;
 use secstr::SecStr;
+use std::fmt;
 
 /// Key used for generating the master key.
 ///
 /// This data type uses secstr's `SecStr` to protect the key data. To
 /// retrieve the protected data use the `unsecure` method.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct TransformedKey(SecStr);
 
 impl TransformedKey {
@@ -55,6 +58,8 @@ impl TransformedKey {
     }
 
     /// Gets the protected data from this transformed key.
+    ///
+    /// Use `try_unsecure` to detect protected data with an invalid length.
     pub fn unsecure(&self) -> [u8; 32] {
         let unsecure = self.0.unsecure();
         let mut array = [0u8; 32];
@@ -64,11 +69,25 @@ impl TransformedKey {
         array
     }
 
+    /// Attempts to get the protected data from this transformed key.
+    ///
+    /// Returns an error when the protected data isn't exactly 32 bytes.
+    pub fn try_unsecure(&self) -> Result<[u8; 32]> {
+        let unsecure = self.0.unsecure();
+        <[u8; 32]>::try_from(unsecure).map_err(|_| Error::InvalidKeyLength(unsecure.len()))
+    }
+
     fn secure(key: [u8; 32]) -> TransformedKey {
         TransformedKey(SecStr::new(key.to_vec()))
     }
 }
 
+impl fmt::Debug for TransformedKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TransformedKey(<{} bytes>)", self.0.unsecure().len())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -99,4 +118,25 @@ mod tests {
         let actual = TransformedKey::unsecure(&TransformedKey::secure(array));
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_fmt_debug_does_not_contain_key_bytes() {
+        let key = TransformedKey::secure([1u8; 32]);
+        assert_eq!(format!("{:?}", key), "TransformedKey(<32 bytes>)");
+    }
+
+    #[test]
+    fn test_try_unsecure_returns_correct_array() {
+        let target = TransformedKey::secure([7u8; 32]);
+        assert_eq!(target.try_unsecure().unwrap(), [7u8; 32]);
+    }
+
+    #[test]
+    fn test_try_unsecure_with_wrong_length_returns_error() {
+        let target = TransformedKey(SecStr::new(vec![7u8; 16]));
+        match target.try_unsecure() {
+            Err(Error::InvalidKeyLength(16)) => (),
+            _ => panic!("expected InvalidKeyLength error"),
+        }
+    }
 }
@@ -0,0 +1,34 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::entry::Entry;
+use super::group::Group;
+use super::walk_control::WalkControl;
+
+/// Trait for visiting the groups and entries of a database in depth-first
+/// order, see `Database::walk`.
+///
+/// The depth of the root group is zero and the depth of an entry is one more
+/// than the depth of its group. All methods continue the walk by default.
+pub trait TreeVisitor {
+    /// Called before the entries and child groups of the group are visited.
+    fn enter_group(&mut self, _group: &Group, _depth: usize) -> WalkControl {
+        WalkControl::Continue
+    }
+
+    /// Called after the entries and child groups of the group are visited,
+    /// also when they were skipped.
+    fn leave_group(&mut self, _group: &Group, _depth: usize) -> WalkControl {
+        WalkControl::Continue
+    }
+
+    /// Called for each entry of a group, before its child groups are visited.
+    fn visit_entry(&mut self, _entry: &Entry, _depth: usize) -> WalkControl {
+        WalkControl::Continue
+    }
+}
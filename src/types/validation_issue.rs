@@ -0,0 +1,114 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::binary_id::BinaryId;
+use super::entry_uuid::EntryUuid;
+use super::group_uuid::GroupUuid;
+use std::fmt;
+
+/// A structural problem in a database, see `Database::validate`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationIssue {
+    /// An entry (or one of its history items) references binary data that
+    /// doesn't exist in the binaries map.
+    DanglingBinary {
+        /// Entry identifier.
+        entry: EntryUuid,
+
+        /// Binary identifier.
+        id: BinaryId,
+    },
+
+    /// More than one entry has the specified identifier.
+    DuplicateEntryUuid(EntryUuid),
+
+    /// More than one group has the specified identifier.
+    DuplicateGroupUuid(GroupUuid),
+
+    /// An entry's parent is set but doesn't match the group that contains it.
+    EntryParentMismatch {
+        /// Entry identifier.
+        entry: EntryUuid,
+
+        /// The parent stored in the entry.
+        parent: GroupUuid,
+
+        /// The group that contains the entry.
+        group: GroupUuid,
+    },
+
+    /// A group has the same identifier as one of its ancestors.
+    GroupCycle(GroupUuid),
+
+    /// A group's parent is set but doesn't match the group that contains it.
+    GroupParentMismatch {
+        /// Group identifier.
+        uuid: GroupUuid,
+
+        /// The parent stored in the group.
+        parent: GroupUuid,
+
+        /// The group that contains the group.
+        group: GroupUuid,
+    },
+
+    /// The recycle bin is enabled but the group with the specified
+    /// identifier doesn't exist.
+    MissingRecycleBin(GroupUuid),
+
+    /// The number of transform rounds is zero or too low to slow down
+    /// brute force attacks.
+    WeakTransformRounds(u64),
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ValidationIssue::DanglingBinary { entry, ref id } => {
+                write!(f, "Dangling binary: id: {}, entry: {}", id.0, entry.0)
+            }
+            ValidationIssue::DuplicateEntryUuid(val) => write!(f, "Duplicate entry: {}", val.0),
+            ValidationIssue::DuplicateGroupUuid(val) => write!(f, "Duplicate group: {}", val.0),
+            ValidationIssue::EntryParentMismatch {
+                entry,
+                parent,
+                group,
+            } => write!(
+                f,
+                "Entry parent mismatch: entry: {}, parent: {}, group: {}",
+                entry.0, parent.0, group.0
+            ),
+            ValidationIssue::GroupCycle(val) => write!(f, "Group cycle: {}", val.0),
+            ValidationIssue::GroupParentMismatch {
+                uuid,
+                parent,
+                group,
+            } => write!(
+                f,
+                "Group parent mismatch: uuid: {}, parent: {}, group: {}",
+                uuid.0, parent.0, group.0
+            ),
+            ValidationIssue::MissingRecycleBin(val) => write!(f, "Missing recycle bin: {}", val.0),
+            ValidationIssue::WeakTransformRounds(val) => {
+                write!(f, "Weak transform rounds: {}", val)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_fmt_returns_description() {
+        let issue = ValidationIssue::WeakTransformRounds(0);
+        assert_eq!(format!("{}", issue), "Weak transform rounds: 0");
+    }
+}
@@ -7,6 +7,7 @@
 // except according to those terms.
 
 use crate::common;
+use std::fmt;
 
 /// The database version.
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -26,6 +27,52 @@ impl Version {
             minor: common::KDB2_MINOR_VERSION,
         }
     }
+
+    /// Returns whether the XML data of this version contains the header hash.
+    ///
+    /// Version 4 replaced the header hash in the XML data by an HMAC of the
+    /// headers that is stored after the headers.
+    pub fn has_xml_header_hash(&self) -> bool {
+        self.major < 4
+    }
+
+    /// Returns whether databases with this version can be read.
+    ///
+    /// Versions 2.0, 3.0 and 3.1 are supported. Newer minor versions may
+    /// change the meaning of existing fields, so they're rejected instead of
+    /// being read as the latest known version.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kpdb::Version;
+    ///
+    /// assert!(Version::new_kdb2().is_supported());
+    /// assert!(!Version { major: 3, minor: 2 }.is_supported());
+    /// assert!(!Version { major: 4, minor: 0 }.is_supported());
+    /// ```
+    pub fn is_supported(&self) -> bool {
+        match self.major {
+            2 => self.minor == 0,
+            common::KDB2_MAJOR_VERSION => self.minor <= common::KDB2_MINOR_VERSION,
+            _ => false,
+        }
+    }
+
+    /// Returns whether the major version of this version can be read.
+    ///
+    /// Databases with an unknown minor version of a supported major version
+    /// can still be read in lenient mode.
+    #[cfg(feature = "database")]
+    pub(crate) fn is_supported_major(&self) -> bool {
+        self.major == 2 || self.major == common::KDB2_MAJOR_VERSION
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
 }
 
 #[cfg(test)]
@@ -39,4 +86,35 @@ mod tests {
         assert_eq!(version.major, 3);
         assert_eq!(version.minor, 1);
     }
+
+    #[test]
+    fn test_is_supported_checks_minor_version_per_major_version() {
+        assert!(Version { major: 2, minor: 0 }.is_supported());
+        assert!(!Version { major: 2, minor: 7 }.is_supported());
+        assert!(Version { major: 3, minor: 0 }.is_supported());
+        assert!(Version { major: 3, minor: 1 }.is_supported());
+        assert!(!Version { major: 3, minor: 2 }.is_supported());
+        assert!(!Version { major: 4, minor: 1 }.is_supported());
+        assert!(!Version { major: 1, minor: 0 }.is_supported());
+    }
+
+    #[test]
+    #[cfg(feature = "database")]
+    fn test_is_supported_major_ignores_minor_version() {
+        assert!(Version { major: 2, minor: 7 }.is_supported_major());
+        assert!(Version { major: 3, minor: 2 }.is_supported_major());
+        assert!(!Version { major: 4, minor: 0 }.is_supported_major());
+        assert!(!Version { major: 1, minor: 0 }.is_supported_major());
+    }
+
+    #[test]
+    fn test_has_xml_header_hash_returns_false_from_version_4() {
+        assert!(Version { major: 3, minor: 1 }.has_xml_header_hash());
+        assert!(!Version { major: 4, minor: 0 }.has_xml_header_hash());
+    }
+
+    #[test]
+    fn test_fmt_returns_major_and_minor_version() {
+        assert_eq!(format!("{}", Version { major: 3, minor: 2 }), "3.2");
+    }
 }
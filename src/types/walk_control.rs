@@ -0,0 +1,21 @@
+// Copyright (c) 2016-2017 Martijn Rijkeboer <mrr@sru-systems.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Controls how a tree walk continues after a visitor callback.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WalkControl {
+    /// Continue walking the tree.
+    Continue,
+
+    /// Skip the entries and child groups of the group that is entered, or
+    /// the remaining entries when returned from visiting an entry.
+    SkipSubtree,
+
+    /// Stop walking the tree.
+    Stop,
+}
@@ -13,8 +13,10 @@ use super::custom_icons_map::CustomIconsMap;
 use super::group::Group;
 use super::group_uuid::GroupUuid;
 use super::header_hash::HeaderHash;
+use super::recovered_error::RecoveredError;
 use crate::common;
 use chrono::{DateTime, Utc};
+use uuid::Uuid;
 
 /// Represents the XML data of the database.
 #[derive(Clone, Debug, PartialEq)]
@@ -37,6 +39,9 @@ pub struct XmlData {
     /// The date and time the default username was changed.
     pub def_username_changed: DateTime<Utc>,
 
+    /// Identifiers of deleted objects with their deletion time.
+    pub deleted_objects: Vec<(Uuid, DateTime<Utc>)>,
+
     /// Description of this database.
     pub description: String,
 
@@ -98,6 +103,9 @@ pub struct XmlData {
     /// Whether usernames must be protected.
     pub protect_username: bool,
 
+    /// The unreadable parts that were skipped in lenient mode.
+    pub recovered: Vec<RecoveredError>,
+
     /// The date and time the recycle bin was changed.
     pub recycle_bin_changed: DateTime<Utc>,
 
@@ -109,6 +117,9 @@ pub struct XmlData {
 
     /// The root group.
     pub root_group: Option<Group>,
+
+    /// The date and time the database settings were changed.
+    pub settings_changed: Option<DateTime<Utc>>,
 }
 
 impl Default for XmlData {
@@ -121,6 +132,7 @@ impl Default for XmlData {
             custom_icons: CustomIconsMap::new(),
             def_username: String::new(),
             def_username_changed: now,
+            deleted_objects: Vec::new(),
             description: String::new(),
             description_changed: now,
             entry_templates_group_changed: now,
@@ -142,10 +154,12 @@ impl Default for XmlData {
             protect_title: common::PROTECT_TITLE_DEFAULT,
             protect_url: common::PROTECT_URL_DEFAULT,
             protect_username: common::PROTECT_USERNAME_DEFAULT,
+            recovered: Vec::new(),
             recycle_bin_changed: now,
             recycle_bin_enabled: common::RECYCLE_BIN_ENABLED_DEFAULT,
             recycle_bin_uuid: GroupUuid::nil(),
             root_group: None,
+            settings_changed: None,
         }
     }
 }
@@ -171,6 +185,7 @@ mod tests {
         assert_eq!(data.custom_icons, CustomIconsMap::new());
         assert_eq!(data.def_username, "");
         assert!(approx_equal_datetime(data.def_username_changed, now));
+        assert_eq!(data.deleted_objects, Vec::new());
         assert_eq!(data.description, "");
         assert!(approx_equal_datetime(data.description_changed, now));
         assert!(approx_equal_datetime(data.entry_templates_group_changed, now));
@@ -192,9 +207,11 @@ mod tests {
         assert_eq!(data.protect_title, false);
         assert_eq!(data.protect_url, false);
         assert_eq!(data.protect_username, false);
+        assert_eq!(data.recovered, Vec::new());
         assert!(approx_equal_datetime(data.recycle_bin_changed, now));
         assert_eq!(data.recycle_bin_enabled, true);
         assert_eq!(data.recycle_bin_uuid, GroupUuid::nil());
         assert_eq!(data.root_group, None);
+        assert_eq!(data.settings_changed, None);
     }
 }
@@ -6,9 +6,16 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#![cfg(feature = "database")]
+
+extern crate crypto;
 extern crate kpdb;
 
-use kpdb::{CompositeKey, Database, KeyFile, KeyFileType};
+use crypto::symmetriccipher::SymmetricCipherError;
+use kpdb::{
+    CompositeKey, Compression, Database, Entry, Error, Group, KeyFile, KeyFileType, MasterCipher,
+    Obfuscation, OpenOptions, ReadLimits, SaveOptions, StreamCipher,
+};
 use std::fs::File;
 use std::io::Cursor;
 
@@ -52,6 +59,291 @@ fn test_database_open_with_incorrect_password_fails() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_database_verify_key_with_correct_password_returns_true() {
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let key = CompositeKey::from_password(PASSWORD);
+    let result = Database::verify_key(&mut file, &key);
+    assert!(result.unwrap());
+}
+
+#[test]
+fn test_database_verify_key_with_incorrect_password_returns_false() {
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let key = CompositeKey::from_password("wrong");
+    let result = Database::verify_key(&mut file, &key);
+    assert!(!result.unwrap());
+}
+
+#[test]
+fn test_database_verify_key_can_check_saved_database() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut writer = Vec::new();
+    Database::new(&key).save(&mut writer).unwrap();
+    let result = Database::verify_key(&mut Cursor::new(&writer), &key);
+    assert!(result.unwrap());
+    let key = CompositeKey::from_password("wrong");
+    let result = Database::verify_key(&mut Cursor::new(&writer), &key);
+    assert!(!result.unwrap());
+}
+
+#[test]
+fn test_database_verify_key_with_options_with_too_many_transform_rounds_fails() {
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let key = CompositeKey::from_password(PASSWORD);
+    let options = OpenOptions::new().max_transform_rounds(1);
+    match Database::verify_key_with_options(&mut file, &key, &options) {
+        Err(Error::TooManyTransformRounds(1)) => (),
+        _ => panic!("expected too many transform rounds error"),
+    }
+}
+
+// A KDBX 3.1 file that wasn't written by this crate and has no header hash
+// in the XML data.
+#[test]
+fn test_database_open_without_header_hash_succeeds() {
+    let mut file = File::open("data/db-no-header-hash.kdbx").unwrap();
+    let key = CompositeKey::from_password(PASSWORD);
+    let db = Database::open(&mut file, &key).unwrap();
+    assert_eq!(db.name, "Passwords");
+    assert_eq!(db.root_group.entries.len(), 1);
+    let entry = &db.root_group.entries[0];
+    assert_eq!(entry.title(), Some("ProtonMail"));
+    assert_eq!(entry.username(), Some("puser"));
+    assert_eq!(entry.password(), Some("ppass"));
+    assert_eq!(entry.url(), Some("https://mail.protonmail.com"));
+}
+
+#[test]
+fn test_database_open_seek_opens_database() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let expected = Database::open(&mut file, &key).unwrap();
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let actual = Database::open_seek(&mut file, &key).unwrap();
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_database_open_seek_with_truncated_database_returns_error() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut writer = Vec::new();
+    Database::new(&key).save(&mut writer).unwrap();
+    let size = writer.len();
+    writer.truncate(size - 5);
+    match Database::open_seek(&mut Cursor::new(writer), &key) {
+        Err(Error::CryptoError(_)) => (),
+        _ => panic!("expected CryptoError error"),
+    }
+}
+
+#[test]
+fn test_database_open_seek_with_options_applies_read_limits() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let limits = ReadLimits {
+        max_headers: 2,
+        ..ReadLimits::default()
+    };
+    let options = OpenOptions::new().with_read_limits(limits);
+    match Database::open_seek_with_options(&mut file, &key, &options) {
+        Err(Error::TooManyHeaders(2)) => (),
+        _ => panic!("expected TooManyHeaders error"),
+    }
+}
+
+#[test]
+fn test_database_open_with_appended_data_returns_error() {
+    let mut data = std::fs::read("data/db-password.kdbx").unwrap();
+    data.extend_from_slice(&[0u8; 16]);
+    let key = CompositeKey::from_password(PASSWORD);
+    match Database::open(&mut Cursor::new(data), &key) {
+        Err(Error::CryptoError(SymmetricCipherError::InvalidPadding)) => (),
+        _ => panic!("expected invalid padding error"),
+    }
+}
+
+#[test]
+fn test_database_open_with_options_with_too_many_transform_rounds_fails() {
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let key = CompositeKey::from_password(PASSWORD);
+    let options = OpenOptions::new().max_transform_rounds(1);
+    match Database::open_with_options(&mut file, &key, &options) {
+        Err(Error::TooManyTransformRounds(1)) => (),
+        _ => panic!("expected too many transform rounds error"),
+    }
+}
+
+#[test]
+fn test_database_open_with_recovery_without_errors_returns_empty_list() {
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let key = CompositeKey::from_password(PASSWORD);
+    let options = OpenOptions::new().lenient(true);
+    let (_, recovered) = Database::open_with_recovery(&mut file, &key, &options).unwrap();
+    assert_eq!(recovered, Vec::new());
+}
+
+#[test]
+fn test_database_open_with_master_key_opens_database() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let escrowed = Database::transformed_key(&mut file, &key).unwrap();
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let expected = Database::open(&mut file, &key).unwrap();
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let mut actual = Database::open_with_master_key(&mut file, &escrowed).unwrap();
+    assert!(actual.composite_key.is_empty());
+    actual.composite_key = key;
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_database_open_with_master_key_saves_only_after_setting_key() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let escrowed = Database::transformed_key(&mut file, &key).unwrap();
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let mut db = Database::open_with_master_key(&mut file, &escrowed).unwrap();
+    match db.save(&mut Vec::new()) {
+        Err(Error::MissingCompositeKey) => (),
+        _ => panic!("expected MissingCompositeKey error"),
+    }
+
+    let new_key = CompositeKey::from_password("new password");
+    db.composite_key = new_key.clone();
+    let mut writer = Vec::new();
+    db.save(&mut writer).unwrap();
+    let actual = Database::open(&mut Cursor::new(writer), &new_key).unwrap();
+    assert_eq!(actual.root_group, db.root_group);
+}
+
+#[test]
+fn test_database_open_with_master_key_with_options_applies_read_limits() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let escrowed = Database::transformed_key(&mut file, &key).unwrap();
+    let limits = ReadLimits {
+        max_headers: 2,
+        ..ReadLimits::default()
+    };
+    let options = OpenOptions::new().with_read_limits(limits);
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    match Database::open_with_master_key_with_options(&mut file, &escrowed, &options) {
+        Err(Error::TooManyHeaders(2)) => (),
+        _ => panic!("expected TooManyHeaders error"),
+    }
+}
+
+#[test]
+fn test_database_open_with_transformed_key_and_new_key_saves_with_new_key() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let new_key = CompositeKey::from_password("new password");
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let escrowed = Database::transformed_key(&mut file, &key).unwrap();
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let db = Database::open_with_transformed_key(&mut file, &new_key, &escrowed).unwrap();
+    assert_eq!(db.composite_key, new_key);
+
+    let mut writer = Vec::new();
+    db.save(&mut writer).unwrap();
+    let actual = Database::open(&mut Cursor::new(writer), &new_key).unwrap();
+    assert_eq!(actual.root_group, db.root_group);
+}
+
+#[test]
+fn test_database_open_with_transformed_key_opens_database() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let transformed_key = Database::transformed_key(&mut file, &key).unwrap();
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let expected = Database::open(&mut file, &key).unwrap();
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let actual = Database::open_with_transformed_key(&mut file, &key, &transformed_key).unwrap();
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_database_open_with_transformed_key_with_options_applies_read_limits() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let transformed_key = Database::transformed_key(&mut file, &key).unwrap();
+    let limits = ReadLimits {
+        max_headers: 2,
+        ..ReadLimits::default()
+    };
+    let options = OpenOptions::new().with_read_limits(limits);
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let result = Database::open_with_transformed_key_with_options(
+        &mut file,
+        &key,
+        &transformed_key,
+        &options,
+    );
+    match result {
+        Err(Error::TooManyHeaders(2)) => (),
+        _ => panic!("expected TooManyHeaders error"),
+    }
+}
+
+#[test]
+fn test_database_open_with_transformed_key_of_other_file_returns_error() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let transformed_key = Database::transformed_key(&mut file, &key).unwrap();
+    let mut writer = Vec::new();
+    Database::new(&key).save(&mut writer).unwrap();
+    let result =
+        Database::open_with_transformed_key(&mut Cursor::new(writer), &key, &transformed_key);
+    match result {
+        Err(Error::InvalidKey) => (),
+        _ => panic!("expected InvalidKey error"),
+    }
+}
+
+#[test]
+fn test_database_save_writes_rust_kpdb_generator_for_new_database() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut db = Database::new(&key);
+    db.generator = String::new();
+    let mut writer = Vec::new();
+    db.save(&mut writer).unwrap();
+    let actual = Database::open(&mut Cursor::new(writer), &key).unwrap();
+    assert_eq!(actual.generator, "rust-kpdb");
+}
+
+#[test]
+fn test_database_save_preserves_generator_of_opened_database() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut db = Database::new(&key);
+    db.generator = String::from("KeePassXC");
+    let mut writer = Vec::new();
+    db.save(&mut writer).unwrap();
+    let db = Database::open(&mut Cursor::new(writer), &key).unwrap();
+    assert_eq!(db.generator, "KeePassXC");
+
+    let mut writer = Vec::new();
+    db.save(&mut writer).unwrap();
+    let actual = Database::open(&mut Cursor::new(writer), &key).unwrap();
+    assert_eq!(actual.generator, "KeePassXC");
+}
+
+#[test]
+fn test_database_save_with_options_can_be_read() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut db = Database::new(&key);
+    db.generator = String::from("KeePassXC");
+    let options = SaveOptions::new()
+        .block_size(64)
+        .compression_level(9)
+        .indent(false)
+        .regenerate_generator(true);
+    let mut writer = Vec::new();
+    db.save_with_options(&mut writer, &options).unwrap();
+    let actual = Database::open(&mut Cursor::new(writer), &key).unwrap();
+    assert_eq!(actual.generator, "rust-kpdb");
+    assert_eq!(actual.name, db.name);
+}
+
 #[test]
 fn test_database_open_can_read_saved_database() {
     let key = CompositeKey::from_password(PASSWORD);
@@ -63,6 +355,117 @@ fn test_database_open_can_read_saved_database() {
     assert_eq!(expected, actual);
 }
 
+#[test]
+fn test_database_open_can_read_saved_auto_type_settings() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut expected = Database::new(&key);
+    let mut entry = Entry::new();
+    {
+        let mut auto_type = entry.auto_type_mut();
+        *auto_type.def_sequence = String::from("{USERNAME}{TAB}{PASSWORD}{ENTER}");
+        *auto_type.enabled = false;
+        *auto_type.obfuscation = Obfuscation::UseClipboard;
+        auto_type
+            .associate("Firefox - *", "{PASSWORD}{ENTER}")
+            .associate("Chromium - *", "");
+    }
+    expected.root_group.add_entry(entry.clone());
+    let mut writer = Vec::new();
+    expected.save(&mut writer).unwrap();
+    let mut reader = Cursor::new(writer);
+    let actual = Database::open(&mut reader, &key).unwrap();
+    assert_eq!(actual.root_group.entries[0].auto_type(), entry.auto_type());
+}
+
+#[test]
+fn test_database_open_can_read_saved_database_with_each_compression() {
+    let key = CompositeKey::from_password(PASSWORD);
+    for compression in [Compression::None, Compression::GZip].iter() {
+        let mut expected = Database::new(&key);
+        expected.compression = *compression;
+        let mut entry = Entry::new();
+        entry.set_title("ProtonMail");
+        expected.root_group.add_entry(entry);
+        let mut writer = Vec::new();
+        expected.save(&mut writer).unwrap();
+        let mut reader = Cursor::new(writer);
+        let actual = Database::open(&mut reader, &key).unwrap();
+        assert_eq!(actual.compression, *compression);
+        assert_eq!(actual.root_group.entries[0].title(), Some("ProtonMail"));
+    }
+}
+
+#[test]
+fn test_database_open_can_read_saved_database_with_each_configuration() {
+    let key = CompositeKey::from_password(PASSWORD);
+    for compression in [Compression::None, Compression::GZip].iter() {
+        for master_cipher in MasterCipher::all() {
+            for stream_cipher in StreamCipher::all() {
+                let mut expected = populated_database(&key);
+                expected.compression = *compression;
+                expected
+                    .set_ciphers(master_cipher.clone(), stream_cipher.clone())
+                    .unwrap();
+                let mut writer = Vec::new();
+                expected.save(&mut writer).unwrap();
+                let mut reader = Cursor::new(writer);
+                let actual = Database::open(&mut reader, &key).unwrap();
+                assert_eq!(expected, actual);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_database_open_can_read_saved_multi_block_database() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut expected = Database::new(&key);
+    expected.compression = Compression::None;
+    let mut entry = Entry::new();
+    let notes: String = (0..3 * 1024 * 1024)
+        .map(|i| (b'a' + (i % 26) as u8) as char)
+        .collect();
+    entry.set_notes(notes.clone());
+    expected.root_group.add_entry(entry);
+    let mut writer = Vec::new();
+    expected.save(&mut writer).unwrap();
+    assert!(writer.len() > 3 * 1024 * 1024);
+    let mut reader = Cursor::new(writer);
+    let actual = Database::open(&mut reader, &key).unwrap();
+    assert_eq!(actual.root_group.entries.len(), 1);
+    assert_eq!(actual.root_group.entries[0].notes(), Some(notes.as_str()));
+}
+
+#[test]
+fn test_database_open_sets_parent_of_entries_to_containing_group() {
+    let key = CompositeKey::from_password(PASSWORD);
+    let mut db = Database::new(&key);
+    let mut entry = Entry::new();
+    entry.history.push(entry.clone());
+    db.root_group.add_entry(entry.clone());
+    let mut group = Group::new("Group");
+    group.add_entry(entry);
+    db.root_group.add_group(group);
+    let mut writer = Vec::new();
+    db.save(&mut writer).unwrap();
+
+    let mut file = File::open("data/db-password.kdbx").unwrap();
+    let opened = Database::open(&mut file, &key).unwrap();
+    let mut reader = Cursor::new(writer);
+    let saved = Database::open(&mut reader, &key).unwrap();
+    assert_eq!(saved.root_group.entries.len(), 1);
+    for db in [opened, saved].iter() {
+        for group in db.root_group.iter() {
+            for entry in group.entries.iter() {
+                assert_eq!(entry.parent, group.uuid);
+                for old in entry.history.iter() {
+                    assert_eq!(old.parent, group.uuid);
+                }
+            }
+        }
+    }
+}
+
 #[test]
 fn test_key_file_open_with_binary_key_returns_correct_data() {
     let key = [
@@ -218,3 +621,39 @@ fn test_key_file_open_can_read_saved_xml_key_file() {
     let actual = KeyFile::open(&mut reader).unwrap();
     assert_eq!(expected, actual);
 }
+
+fn populated_database(key: &CompositeKey) -> Database {
+    let mut db = Database::new(key);
+    db.name = String::from("Passwords");
+    let icon = db.add_custom_icon(vec![1, 2, 3]);
+
+    let mut group = Group::new("Email");
+    group.parent = db.root_group.uuid;
+    let mut entry = Entry::new();
+    entry.parent = group.uuid;
+    entry.set_title("ProtonMail");
+    entry.set_username("puser");
+    entry.set_password("ppass");
+    entry.history.push(entry.clone());
+    let entry_uuid = entry.uuid;
+    group.add_entry(entry);
+    let mut sub_group = Group::new("Work");
+    sub_group.parent = group.uuid;
+    group.add_group(sub_group);
+    db.root_group.add_group(group);
+
+    let mut entry = Entry::new();
+    entry.parent = db.root_group.uuid;
+    entry.set_title("ProtonVPN");
+    let other_uuid = entry.uuid;
+    db.root_group.add_entry(entry);
+
+    db.set_entry_binary(entry_uuid, "public.txt", vec![4, 5, 6], false)
+        .unwrap();
+    db.set_entry_binary(entry_uuid, "secret.txt", vec![7, 8, 9], true)
+        .unwrap();
+    db.set_entry_binary(other_uuid, "public.txt", vec![4, 5, 6], false)
+        .unwrap();
+    db.set_entry_custom_icon(other_uuid, Some(icon)).unwrap();
+    db
+}